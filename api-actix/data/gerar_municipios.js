@@ -0,0 +1,108 @@
+// Gera data/municipios.csv (codigoIbge;nome;cepInicial;cepFinal) a partir das fontes oficiais:
+//   - lista de municípios do IBGE (API de localidades ou o JSON baixado dela)
+//   - e-DNE dos Correios: LOG_LOCALIDADE.TXT e LOG_FAIXA_LOCALIDADE.TXT (separados por @, ISO-8859-1)
+//
+// Uso: node data/gerar_municipios.js --dne <pasta do e-DNE> [--ibge municipios.json] [--saida data/municipios.csv]
+// Sem --dne os municípios saem sem faixa de CEP (o nome continua disponível, a busca por CEP não)
+const fs = require('fs');
+const path = require('path');
+
+const URL_IBGE = 'https://servicodados.ibge.gov.br/api/v1/localidades/municipios';
+
+function argumento(nome) {
+    const indice = process.argv.indexOf(`--${nome}`);
+    return indice >= 0 ? process.argv[indice + 1] : undefined;
+}
+
+async function carregarMunicipiosIbge(arquivo) {
+    const municipios = arquivo
+        ? JSON.parse(fs.readFileSync(arquivo, 'utf8'))
+        : await fetch(URL_IBGE).then((resposta) => {
+            if (!resposta.ok) {
+                throw new Error(`API do IBGE respondeu ${resposta.status}`);
+            }
+            return resposta.json();
+        });
+
+    return new Map(municipios.map((municipio) => [String(municipio.id), municipio.nome]));
+}
+
+function lerDne(pasta, arquivo) {
+    return fs.readFileSync(path.join(pasta, arquivo), 'latin1')
+        .split(/\r?\n/)
+        .filter((linha) => linha.trim() !== '')
+        .map((linha) => linha.split('@'));
+}
+
+// Faixas de CEP por código IBGE; distritos e povoados herdam o município da localidade a que estão subordinados
+function carregarFaixasDne(pasta) {
+    const localidades = new Map();
+    for (const [numero, , , cep, , , numeroSubordinada, , codigoIbge] of lerDne(pasta, 'LOG_LOCALIDADE.TXT')) {
+        localidades.set(numero, { cep, numeroSubordinada, codigoIbge });
+    }
+
+    const codigoIbgeDa = (numero) => {
+        for (let localidade = localidades.get(numero); localidade; localidade = localidades.get(localidade.numeroSubordinada)) {
+            if (localidade.codigoIbge) {
+                return localidade.codigoIbge;
+            }
+        }
+        return undefined;
+    };
+
+    const faixas = new Map();
+    const adicionar = (codigoIbge, inicio, fim) => {
+        const chave = `${inicio}-${fim}`;
+        const doMunicipio = faixas.get(codigoIbge) ?? new Map();
+        doMunicipio.set(chave, [inicio, fim]);
+        faixas.set(codigoIbge, doMunicipio);
+    };
+
+    const comFaixa = new Set();
+    for (const [numero, inicio, fim] of lerDne(pasta, 'LOG_FAIXA_LOCALIDADE.TXT')) {
+        const codigoIbge = codigoIbgeDa(numero);
+        if (codigoIbge) {
+            adicionar(codigoIbge, inicio, fim);
+            comFaixa.add(numero);
+        }
+    }
+
+    // Localidades de CEP único (não codificadas por logradouro) não aparecem no arquivo de faixas
+    for (const [numero, localidade] of localidades) {
+        const codigoIbge = codigoIbgeDa(numero);
+        if (codigoIbge && localidade.cep && !comFaixa.has(numero)) {
+            adicionar(codigoIbge, localidade.cep, localidade.cep);
+        }
+    }
+
+    return faixas;
+}
+
+async function main() {
+    const pastaDne = argumento('dne');
+    const saida = argumento('saida') ?? path.join(__dirname, 'municipios.csv');
+
+    const municipios = await carregarMunicipiosIbge(argumento('ibge'));
+    const faixas = pastaDne ? carregarFaixasDne(pastaDne) : new Map();
+
+    const linhas = ['codigoIbge;nome;cepInicial;cepFinal'];
+    for (const codigo of [...municipios.keys()].sort()) {
+        const nome = municipios.get(codigo);
+        const doMunicipio = [...(faixas.get(codigo)?.values() ?? [])].sort(([a], [b]) => a.localeCompare(b));
+
+        if (doMunicipio.length === 0) {
+            linhas.push(`${codigo};${nome};;`);
+        }
+        for (const [inicio, fim] of doMunicipio) {
+            linhas.push(`${codigo};${nome};${inicio};${fim}`);
+        }
+    }
+
+    fs.writeFileSync(saida, `${linhas.join('\n')}\n`, 'utf8');
+    console.log(`${municipios.size} municípios gravados em ${saida}`);
+}
+
+main().catch((erro) => {
+    console.error(erro.message);
+    process.exit(1);
+});
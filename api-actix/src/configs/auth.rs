@@ -1,6 +1,7 @@
 pub struct AuthConfig {
     pub secret: String,
     pub expires_in: i64,
+    pub admin_token: Option<String>,
 }
 
 impl AuthConfig {
@@ -9,6 +10,9 @@ impl AuthConfig {
             secret: std::env::var("JWT_SECRET")
                 .unwrap_or_else(|_| "seu_jwt_secret_aqui".to_string()),
             expires_in: 86400,
+            admin_token: std::env::var("ADMIN_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegurancaSmtp {
+    Nenhuma,
+    StartTls,
+    Tls,
+}
+
+pub struct EmailConfig {
+    pub smtp_host: String,
+    pub smtp_porta: u16,
+    pub smtp_seguranca: SegurancaSmtp,
+    pub smtp_usuario: Option<String>,
+    pub smtp_senha: Option<String>,
+    pub smtp_timeout_segundos: u64,
+    pub remetente: String,
+    pub nome_loja: String,
+}
+
+impl EmailConfig {
+    // O padrão aponta para um MailHog local, que aceita SMTP sem TLS nem autenticação na porta 1025
+    pub fn new() -> Self {
+        Self {
+            smtp_host: std::env::var("SMTP_HOST")
+                .unwrap_or_else(|_| "localhost".to_string()),
+            smtp_porta: std::env::var("SMTP_PORTA")
+                .ok()
+                .and_then(|valor| valor.parse::<u16>().ok())
+                .filter(|porta| *porta > 0)
+                .unwrap_or(1025),
+            smtp_seguranca: match std::env::var("SMTP_SEGURANCA").map(|valor| valor.trim().to_lowercase()).as_deref() {
+                Ok("starttls") => SegurancaSmtp::StartTls,
+                Ok("tls") => SegurancaSmtp::Tls,
+                _ => SegurancaSmtp::Nenhuma,
+            },
+            smtp_usuario: std::env::var("SMTP_USUARIO")
+                .ok()
+                .filter(|usuario| !usuario.trim().is_empty()),
+            smtp_senha: std::env::var("SMTP_SENHA")
+                .ok()
+                .filter(|senha| !senha.is_empty()),
+            smtp_timeout_segundos: std::env::var("SMTP_TIMEOUT_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<u64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(10),
+            remetente: std::env::var("EMAIL_REMETENTE")
+                .unwrap_or_else(|_| "Loja <nao-responda@loja.com.br>".to_string()),
+            nome_loja: std::env::var("EMAIL_NOME_LOJA")
+                .unwrap_or_else(|_| "Loja".to_string()),
+        }
+    }
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,31 @@
+pub struct FreteConfig {
+    pub cache_segundos: u64,
+    pub fator_cubagem: i64,
+    pub retirada_prazo_dias_uteis: u32,
+}
+
+impl FreteConfig {
+    pub fn new() -> Self {
+        Self {
+            cache_segundos: std::env::var("FRETE_CACHE_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<u64>().ok())
+                .unwrap_or(300),
+            fator_cubagem: std::env::var("FRETE_FATOR_CUBAGEM")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|fator| *fator > 0)
+                .unwrap_or(6000),
+            retirada_prazo_dias_uteis: std::env::var("FRETE_RETIRADA_PRAZO_DIAS_UTEIS")
+                .ok()
+                .and_then(|valor| valor.parse::<u32>().ok())
+                .unwrap_or(1),
+        }
+    }
+}
+
+impl Default for FreteConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,28 @@
+pub struct IdempotenciaConfig {
+    pub validade_horas: i64,
+    // Prazo de uma reserva sem resposta; depois dele a requisição é dada como perdida e a chave pode ser retomada
+    pub reserva_segundos: i64,
+}
+
+impl IdempotenciaConfig {
+    pub fn new() -> Self {
+        Self {
+            validade_horas: std::env::var("IDEMPOTENCIA_VALIDADE_HORAS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|horas| *horas > 0)
+                .unwrap_or(24),
+            reserva_segundos: std::env::var("IDEMPOTENCIA_RESERVA_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(120),
+        }
+    }
+}
+
+impl Default for IdempotenciaConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
\ No newline at end of file
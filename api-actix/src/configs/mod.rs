@@ -1 +1,7 @@
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod email;
+pub mod frete;
+pub mod idempotencia;
+pub mod nfe;
+pub mod outbox;
+pub mod pagamento;
\ No newline at end of file
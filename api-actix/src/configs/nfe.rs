@@ -0,0 +1,85 @@
+pub struct NfeConfig {
+    // 1 = produção, 2 = homologação
+    pub ambiente: u8,
+    pub serie: u32,
+    pub numero_inicial: u32,
+    pub natureza_operacao: String,
+    pub cnpj: String,
+    pub ie: String,
+    pub razao_social: String,
+    pub nome_fantasia: Option<String>,
+    pub logradouro: String,
+    pub numero: String,
+    pub complemento: Option<String>,
+    pub bairro: String,
+    pub codigo_municipio: String,
+    pub municipio: String,
+    pub cep: String,
+    pub telefone: Option<String>,
+    pub crt: u8,
+    // Arquivos PEM com o certificado A1 e a chave privada sem senha (podem ser o mesmo arquivo)
+    pub certificado: String,
+    pub chave_privada: String,
+}
+
+impl NfeConfig {
+    pub fn new() -> Self {
+        let opcional = |nome: &str| std::env::var(nome).ok().filter(|valor| !valor.trim().is_empty());
+        let certificado = std::env::var("NFE_CERTIFICADO")
+            .unwrap_or_else(|_| "certificado.pem".to_string());
+
+        Self {
+            ambiente: std::env::var("NFE_AMBIENTE")
+                .ok()
+                .and_then(|valor| valor.parse::<u8>().ok())
+                .filter(|ambiente| *ambiente == 1 || *ambiente == 2)
+                .unwrap_or(2),
+            serie: std::env::var("NFE_SERIE")
+                .ok()
+                .and_then(|valor| valor.parse::<u32>().ok())
+                .filter(|serie| *serie <= 999)
+                .unwrap_or(1),
+            numero_inicial: std::env::var("NFE_NUMERO_INICIAL")
+                .ok()
+                .and_then(|valor| valor.parse::<u32>().ok())
+                .filter(|numero| (1..=999_999_999).contains(numero))
+                .unwrap_or(1),
+            natureza_operacao: std::env::var("NFE_NATUREZA_OPERACAO")
+                .unwrap_or_else(|_| "Venda de mercadoria".to_string()),
+            cnpj: std::env::var("NFE_CNPJ")
+                .unwrap_or_default(),
+            ie: std::env::var("NFE_IE")
+                .unwrap_or_default(),
+            razao_social: std::env::var("NFE_RAZAO_SOCIAL")
+                .unwrap_or_else(|_| "LOJA".to_string()),
+            nome_fantasia: opcional("NFE_NOME_FANTASIA"),
+            logradouro: std::env::var("NFE_LOGRADOURO")
+                .unwrap_or_else(|_| "Praça da Sé".to_string()),
+            numero: std::env::var("NFE_NUMERO")
+                .unwrap_or_else(|_| "1".to_string()),
+            complemento: opcional("NFE_COMPLEMENTO"),
+            bairro: std::env::var("NFE_BAIRRO")
+                .unwrap_or_else(|_| "Sé".to_string()),
+            codigo_municipio: std::env::var("NFE_CODIGO_MUNICIPIO")
+                .unwrap_or_else(|_| "3550308".to_string()),
+            municipio: std::env::var("NFE_MUNICIPIO")
+                .unwrap_or_else(|_| "São Paulo".to_string()),
+            cep: std::env::var("NFE_CEP")
+                .unwrap_or_else(|_| "01001000".to_string()),
+            telefone: opcional("NFE_TELEFONE"),
+            crt: std::env::var("NFE_CRT")
+                .ok()
+                .and_then(|valor| valor.parse::<u8>().ok())
+                .unwrap_or(1),
+            chave_privada: std::env::var("NFE_CHAVE_PRIVADA")
+                .unwrap_or_else(|_| certificado.clone()),
+            certificado,
+        }
+    }
+}
+
+impl Default for NfeConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,63 @@
+pub struct OutboxConfig {
+    pub worker_habilitado: bool,
+    pub sinks: Vec<String>,
+    pub intervalo_ms: u64,
+    pub tamanho_lote: i64,
+    pub lease_segundos: i64,
+    pub max_tentativas: i32,
+    pub backoff_base_segundos: i64,
+    pub backoff_max_segundos: i64,
+}
+
+impl OutboxConfig {
+    pub fn new() -> Self {
+        Self {
+            worker_habilitado: std::env::var("OUTBOX_WORKER")
+                .map(|valor| !matches!(valor.trim().to_lowercase().as_str(), "0" | "false" | "off"))
+                .unwrap_or(true),
+            // O e-mail exige SMTP configurado e precisa ser ligado explicitamente (OUTBOX_SINKS=log,email)
+            sinks: std::env::var("OUTBOX_SINKS")
+                .unwrap_or_else(|_| "log".to_string())
+                .split(',')
+                .map(|sink| sink.trim().to_lowercase())
+                .filter(|sink| !sink.is_empty())
+                .collect(),
+            intervalo_ms: std::env::var("OUTBOX_INTERVALO_MS")
+                .ok()
+                .and_then(|valor| valor.parse::<u64>().ok())
+                .filter(|ms| *ms > 0)
+                .unwrap_or(1000),
+            tamanho_lote: std::env::var("OUTBOX_TAMANHO_LOTE")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|tamanho| *tamanho > 0)
+                .unwrap_or(50),
+            lease_segundos: std::env::var("OUTBOX_LEASE_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(300),
+            max_tentativas: std::env::var("OUTBOX_MAX_TENTATIVAS")
+                .ok()
+                .and_then(|valor| valor.parse::<i32>().ok())
+                .filter(|tentativas| *tentativas > 0)
+                .unwrap_or(10),
+            backoff_base_segundos: std::env::var("OUTBOX_BACKOFF_BASE_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(5),
+            backoff_max_segundos: std::env::var("OUTBOX_BACKOFF_MAX_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(3600),
+        }
+    }
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
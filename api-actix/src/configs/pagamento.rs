@@ -0,0 +1,56 @@
+pub struct PagamentoConfig {
+    pub usar_mock: bool,
+    pub boleto_banco: String,
+    pub boleto_agencia: String,
+    pub boleto_carteira: String,
+    pub boleto_conta: String,
+    pub boleto_dias_vencimento: i64,
+    pub pix_chave: String,
+    pub pix_nome_recebedor: String,
+    pub pix_cidade: String,
+    pub webhook_secret: Option<String>,
+    pub webhook_tolerancia_segundos: i64,
+}
+
+impl PagamentoConfig {
+    pub fn new() -> Self {
+        Self {
+            usar_mock: std::env::var("PAGAMENTO_PROVIDER")
+                .map(|provider| provider.eq_ignore_ascii_case("mock"))
+                .unwrap_or(false),
+            boleto_banco: std::env::var("BOLETO_BANCO")
+                .unwrap_or_else(|_| "237".to_string()),
+            boleto_agencia: std::env::var("BOLETO_AGENCIA")
+                .unwrap_or_else(|_| "0001".to_string()),
+            boleto_carteira: std::env::var("BOLETO_CARTEIRA")
+                .unwrap_or_else(|_| "09".to_string()),
+            boleto_conta: std::env::var("BOLETO_CONTA")
+                .unwrap_or_else(|_| "0000001".to_string()),
+            boleto_dias_vencimento: std::env::var("BOLETO_DIAS_VENCIMENTO")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|dias| *dias > 0)
+                .unwrap_or(3),
+            pix_chave: std::env::var("PIX_CHAVE")
+                .unwrap_or_else(|_| "pix@loja.com.br".to_string()),
+            pix_nome_recebedor: std::env::var("PIX_NOME_RECEBEDOR")
+                .unwrap_or_else(|_| "LOJA".to_string()),
+            pix_cidade: std::env::var("PIX_CIDADE")
+                .unwrap_or_else(|_| "SAO PAULO".to_string()),
+            webhook_secret: std::env::var("PAGAMENTO_WEBHOOK_SECRET")
+                .ok()
+                .filter(|segredo| !segredo.is_empty()),
+            webhook_tolerancia_segundos: std::env::var("PAGAMENTO_WEBHOOK_TOLERANCIA_SEGUNDOS")
+                .ok()
+                .and_then(|valor| valor.parse::<i64>().ok())
+                .filter(|segundos| *segundos > 0)
+                .unwrap_or(300),
+        }
+    }
+}
+
+impl Default for PagamentoConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Result};
+use crate::services::carrinho_service::CarrinhoService;
+use crate::controllers::pedido_controller::{get_chave_idempotencia, idempotent_response};
+use crate::utils::app_message::{success_response, ApiError};
+use crate::middlewares::is_authenticated::get_cliente_from_request;
+use crate::db::AppState;
+use crate::models::carrinho::{AdicionarItemCarrinhoPayload, AtualizarItemCarrinhoPayload, CarrinhoQueryParams, CheckoutCarrinhoRequest};
+
+#[get("")]
+async fn get(
+    app_state: web::Data<AppState>,
+    query: web::Query<CarrinhoQueryParams>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let carrinho = CarrinhoService::get(&app_state.db_pool, &cliente.id, query.into_inner()).await?;
+    Ok(success_response("Carrinho obtido com sucesso", 200, carrinho))
+}
+
+#[delete("")]
+async fn limpar(
+    app_state: web::Data<AppState>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let carrinho = CarrinhoService::limpar(&app_state.db_pool, &cliente.id).await?;
+    Ok(success_response("Carrinho esvaziado com sucesso", 200, carrinho))
+}
+
+#[post("/itens")]
+async fn adicionar_item(
+    app_state: web::Data<AppState>,
+    payload: web::Json<AdicionarItemCarrinhoPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let carrinho = CarrinhoService::adicionar_item(&app_state.db_pool, &cliente.id, payload.into_inner()).await?;
+    Ok(success_response("Produto adicionado ao carrinho", 200, carrinho))
+}
+
+#[put("/itens/{sku}")]
+async fn atualizar_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<AtualizarItemCarrinhoPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let sku = path.into_inner();
+
+    let carrinho = CarrinhoService::atualizar_item(&app_state.db_pool, &cliente.id, &sku, payload.into_inner()).await?;
+    Ok(success_response("Item do carrinho atualizado com sucesso", 200, carrinho))
+}
+
+#[delete("/itens/{sku}")]
+async fn remover_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let sku = path.into_inner();
+
+    let carrinho = CarrinhoService::remover_item(&app_state.db_pool, &cliente.id, &sku).await?;
+    Ok(success_response("Produto removido do carrinho", 200, carrinho))
+}
+
+#[post("/checkout")]
+async fn checkout(
+    app_state: web::Data<AppState>,
+    payload: web::Json<CheckoutCarrinhoRequest>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let Some(chave) = get_chave_idempotencia(&req)? else {
+        let result = CarrinhoService::checkout(&app_state.db_pool, &cliente.id, payload.into_inner()).await?;
+        return Ok(success_response("Pedido criado com sucesso", 201, result));
+    };
+
+    let resposta = CarrinhoService::checkout_idempotente(&app_state.db_pool, &cliente.id, &chave, payload.into_inner()).await?;
+    Ok(idempotent_response("Pedido criado com sucesso", resposta))
+}
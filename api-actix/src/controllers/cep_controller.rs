@@ -0,0 +1,11 @@
+use actix_web::{get, web, HttpResponse, Result};
+use crate::services::cep_service::CepService;
+use crate::utils::app_message::{success_response, ApiError};
+
+#[get("/{cep}")]
+async fn consultar(path: web::Path<String>) -> Result<HttpResponse, ApiError> {
+    let cep = path.into_inner();
+
+    let consulta = CepService::consultar(&cep)?;
+    Ok(success_response("CEP consultado com sucesso", 200, consulta))
+}
@@ -0,0 +1,20 @@
+use actix_web::{get, post, web, HttpResponse, Result};
+use crate::services::cupom_service::CupomService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::db::AppState;
+use crate::models::cupom::CreateCupomPayload;
+
+#[post("/cupons")]
+async fn create(
+    app_state: web::Data<AppState>,
+    payload: web::Json<CreateCupomPayload>
+) -> Result<HttpResponse, ApiError> {
+    let cupom = CupomService::create(&app_state.db_pool, payload.into_inner()).await?;
+    Ok(success_response("Cupom criado com sucesso", 201, cupom))
+}
+
+#[get("/cupons")]
+async fn get_all(app_state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let cupons = CupomService::get_all(&app_state.db_pool).await?;
+    Ok(success_response("Cupons obtidos com sucesso", 200, cupons))
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use actix_web::{get, post, web, HttpResponse, Result, HttpRequest};
+use crate::services::devolucao_service::DevolucaoService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::middlewares::is_authenticated::get_cliente_from_request;
+use crate::middlewares::is_admin::get_admin_from_request;
+use crate::db::AppState;
+use crate::models::devolucao::{CreateDevolucaoRequest, DecisaoDevolucaoPayload, DevolucaoFiltro};
+
+#[post("/{id}/devolucoes")]
+async fn create(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<CreateDevolucaoRequest>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let devolucao = DevolucaoService::create(&app_state.db_pool, &cliente.id, &id, payload.into_inner()).await?;
+    Ok(success_response("Devolução solicitada com sucesso", 201, devolucao))
+}
+
+#[get("/{id}/devolucoes")]
+async fn get_by_pedido(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let devolucoes = DevolucaoService::get_all_by_pedido(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(success_response("Devoluções obtidas com sucesso", 200, devolucoes))
+}
+
+#[get("/devolucoes")]
+async fn get_all(
+    app_state: web::Data<AppState>,
+    filtro: web::Query<DevolucaoFiltro>
+) -> Result<HttpResponse, ApiError> {
+    let devolucoes = DevolucaoService::get_all(&app_state.db_pool, filtro.into_inner()).await?;
+    Ok(success_response("Devoluções obtidas com sucesso", 200, devolucoes))
+}
+
+#[get("/devolucoes/{id}")]
+async fn get_by_id(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let devolucao = DevolucaoService::get_by_id(&app_state.db_pool, &path.into_inner()).await?;
+    Ok(success_response("Devolução obtida com sucesso", 200, devolucao))
+}
+
+#[post("/devolucoes/{id}/aprovar")]
+async fn aprovar(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<DecisaoDevolucaoPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let admin = get_admin_from_request(&req)?;
+    let id = path.into_inner();
+
+    let devolucao = DevolucaoService::aprovar(&app_state.db_pool, &id, payload.into_inner(), &admin.usuario).await?;
+    Ok(success_response("Devolução aprovada com sucesso", 200, devolucao))
+}
+
+#[post("/devolucoes/{id}/recusar")]
+async fn recusar(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<DecisaoDevolucaoPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let admin = get_admin_from_request(&req)?;
+    let id = path.into_inner();
+
+    let devolucao = DevolucaoService::recusar(&app_state.db_pool, &id, payload.into_inner(), &admin.usuario).await?;
+    Ok(success_response("Devolução recusada com sucesso", 200, devolucao))
+}
@@ -0,0 +1,69 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse, Result};
+use crate::services::endereco_service::EnderecoService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::middlewares::is_authenticated::get_cliente_from_request;
+use crate::db::AppState;
+use crate::models::endereco::EnderecoClientePayload;
+
+#[get("")]
+async fn get_all(
+    app_state: web::Data<AppState>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let enderecos = EnderecoService::get_all(&app_state.db_pool, &cliente.id).await?;
+    Ok(success_response("Endereços obtidos com sucesso", 200, enderecos))
+}
+
+#[get("/{id}")]
+async fn get_by_id(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let endereco = EnderecoService::get_by_id(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(success_response("Endereço obtido com sucesso", 200, endereco))
+}
+
+#[post("")]
+async fn create(
+    app_state: web::Data<AppState>,
+    payload: web::Json<EnderecoClientePayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let endereco = EnderecoService::create(&app_state.db_pool, &cliente.id, payload.into_inner()).await?;
+    Ok(success_response("Endereço criado com sucesso", 201, endereco))
+}
+
+#[put("/{id}")]
+async fn update(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<EnderecoClientePayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let endereco = EnderecoService::update(&app_state.db_pool, &cliente.id, &id, payload.into_inner()).await?;
+    Ok(success_response("Endereço atualizado com sucesso", 200, endereco))
+}
+
+#[delete("/{id}")]
+async fn delete(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    EnderecoService::delete(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(success_response("Endereço removido com sucesso", 200, serde_json::Value::Null))
+}
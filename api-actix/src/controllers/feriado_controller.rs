@@ -0,0 +1,31 @@
+use actix_web::{delete, get, post, web, HttpResponse, Result};
+use crate::services::feriado_service::FeriadoService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::db::AppState;
+use crate::models::feriado::CreateFeriadoPayload;
+
+#[post("/feriados")]
+async fn create(
+    app_state: web::Data<AppState>,
+    payload: web::Json<CreateFeriadoPayload>
+) -> Result<HttpResponse, ApiError> {
+    let feriado = FeriadoService::create(&app_state.db_pool, payload.into_inner()).await?;
+    Ok(success_response("Feriado criado com sucesso", 201, feriado))
+}
+
+#[get("/feriados")]
+async fn get_all(app_state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let feriados = FeriadoService::get_all(&app_state.db_pool).await?;
+    Ok(success_response("Feriados obtidos com sucesso", 200, feriados))
+}
+
+#[delete("/feriados/{id}")]
+async fn delete(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    FeriadoService::delete(&app_state.db_pool, &id).await?;
+    Ok(success_response("Feriado removido com sucesso", 200, serde_json::Value::Null))
+}
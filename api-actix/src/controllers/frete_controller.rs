@@ -0,0 +1,52 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Result};
+use crate::services::frete_service::FreteService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::db::AppState;
+use crate::models::frete::{CotacaoFreteQueryParams, RegraFretePayload};
+
+#[post("/fretes/regras")]
+async fn create(
+    app_state: web::Data<AppState>,
+    payload: web::Json<RegraFretePayload>
+) -> Result<HttpResponse, ApiError> {
+    let regra = FreteService::create(&app_state.db_pool, payload.into_inner()).await?;
+    Ok(success_response("Regra de frete criada com sucesso", 201, regra))
+}
+
+#[get("/fretes/regras")]
+async fn get_all(app_state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let regras = FreteService::get_all(&app_state.db_pool).await?;
+    Ok(success_response("Regras de frete obtidas com sucesso", 200, regras))
+}
+
+#[put("/fretes/regras/{id}")]
+async fn update(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<RegraFretePayload>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    let regra = FreteService::update(&app_state.db_pool, &id, payload.into_inner()).await?;
+    Ok(success_response("Regra de frete atualizada com sucesso", 200, regra))
+}
+
+#[delete("/fretes/regras/{id}")]
+async fn delete(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    FreteService::delete(&app_state.db_pool, &id).await?;
+    Ok(success_response("Regra de frete removida com sucesso", 200, serde_json::Value::Null))
+}
+
+#[get("/cotacao")]
+async fn cotacao(
+    app_state: web::Data<AppState>,
+    query: web::Query<CotacaoFreteQueryParams>
+) -> Result<HttpResponse, ApiError> {
+    let cotacao = FreteService::cotacao(&app_state.db_pool, query.into_inner()).await?;
+    Ok(success_response("Cotação de frete obtida com sucesso", 200, cotacao))
+}
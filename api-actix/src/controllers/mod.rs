@@ -3,3 +3,14 @@ pub mod produto_controller;
 pub mod home_controller;
 pub mod pedido_controller;
 pub mod cliente_controller;
+
+pub mod cupom_controller;
+pub mod pagamento_controller;
+pub mod carrinho_controller;
+pub mod feriado_controller;
+pub mod frete_controller;
+pub mod cep_controller;
+pub mod endereco_controller;
+pub mod nfe_controller;
+pub mod outbox_controller;
+pub mod devolucao_controller;
\ No newline at end of file
@@ -0,0 +1,52 @@
+use actix_web::{get, post, web, HttpResponse, Result, HttpRequest};
+use crate::services::nfe_service::NfeService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::middlewares::is_authenticated::get_cliente_from_request;
+use crate::db::AppState;
+use crate::models::nfe::NotaFiscal;
+
+#[post("/pedidos/{id}/nfe")]
+async fn emitir(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    let (nota, emitida) = NfeService::emitir(&app_state.db_pool, &id).await?;
+    if emitida {
+        Ok(success_response("NF-e emitida com sucesso", 201, nota))
+    } else {
+        Ok(success_response("Pedido já possui NF-e emitida", 200, nota))
+    }
+}
+
+#[get("/pedidos/{id}/nfe")]
+async fn get_xml_admin(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    let nota = NfeService::get_xml(&app_state.db_pool, &id, None).await?;
+    Ok(xml_response(nota))
+}
+
+#[get("/{id}/nfe")]
+async fn get_xml(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let nota = NfeService::get_xml(&app_state.db_pool, &id, Some(&cliente.id)).await?;
+    Ok(xml_response(nota))
+}
+
+fn xml_response(nota: NotaFiscal) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/xml")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}-nfe.xml\"", nota.chave_acesso)))
+        .body(nota.xml)
+}
@@ -0,0 +1,23 @@
+use actix_web::{get, post, web, HttpResponse, Result};
+use crate::services::outbox_service::OutboxService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::db::AppState;
+use crate::models::outbox::EventoOutboxFiltro;
+
+#[get("/outbox")]
+async fn get_all(
+    app_state: web::Data<AppState>,
+    filtro: web::Query<EventoOutboxFiltro>
+) -> Result<HttpResponse, ApiError> {
+    let eventos = OutboxService::get_all(&app_state.db_pool, filtro.into_inner()).await?;
+    Ok(success_response("Eventos obtidos com sucesso", 200, eventos))
+}
+
+#[post("/outbox/{id}/reprocessar")]
+async fn reprocessar(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let evento = OutboxService::reprocessar(&app_state.db_pool, &path.into_inner()).await?;
+    Ok(success_response("Evento devolvido à fila", 200, evento))
+}
@@ -0,0 +1,23 @@
+use actix_web::{post, web, HttpRequest, HttpResponse, Result};
+use crate::services::pagamento_service::PagamentoService;
+use crate::utils::app_message::{success_response, ApiError};
+use crate::db::AppState;
+
+#[post("/webhook")]
+async fn webhook(
+    app_state: web::Data<AppState>,
+    body: web::Bytes,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let header = |nome: &str| req.headers().get(nome).and_then(|valor| valor.to_str().ok());
+
+    let resultado = PagamentoService::processar_webhook(
+        &app_state.db_pool,
+        header("X-Webhook-Timestamp"),
+        header("X-Webhook-Signature"),
+        &body
+    ).await?;
+
+    let mensagem = if resultado.duplicado { "Evento já processado" } else { "Evento processado com sucesso" };
+    Ok(success_response(mensagem, 200, resultado))
+}
@@ -1,23 +1,186 @@
-use actix_web::{post, web, HttpResponse, Result, HttpRequest};
-use serde_json::Value;
+use actix_web::{get, patch, post, web, HttpResponse, Result, HttpRequest};
 use crate::services::pedido_service::PedidoService;
-use crate::utils::app_message::{success_response, ApiError};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use crate::utils::app_message::{success_response, ApiError, AppMessage};
 use crate::middlewares::is_authenticated::get_cliente_from_request;
+use crate::middlewares::is_admin::get_admin_from_request;
 use crate::db::AppState;
+use crate::models::idempotencia::RespostaIdempotente;
+use crate::models::pedido::{AlterarStatusPayload, CancelarPedidoPayload, CreatePedidoRequest, PedidoQueryParams, QrCodeQueryParams};
 
 #[post("")]
 async fn create(
     app_state: web::Data<AppState>,
-    payload: web::Json<Value>,
+    payload: web::Json<CreatePedidoRequest>,
     req: HttpRequest
 ) -> Result<HttpResponse, ApiError> {
     let cliente = get_cliente_from_request(&req)?;
-    let mut pedido_payload = payload.into_inner();
 
-    if let Some(obj) = pedido_payload.as_object_mut() {
-        obj.insert("idCliente".to_string(), Value::String(cliente.id));
+    let Some(chave) = get_chave_idempotencia(&req)? else {
+        let result = PedidoService::create(&app_state.db_pool, &cliente.id, payload.into_inner()).await?;
+        return Ok(success_response("Pedido criado com sucesso", 201, result));
+    };
+
+    let resposta = PedidoService::create_idempotente(&app_state.db_pool, &cliente.id, &chave, payload.into_inner()).await?;
+    Ok(idempotent_response("Pedido criado com sucesso", resposta))
+}
+
+#[post("/simular")]
+async fn simular(
+    app_state: web::Data<AppState>,
+    payload: web::Json<CreatePedidoRequest>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let simulacao = PedidoService::simular(&app_state.db_pool, &cliente.id, payload.into_inner()).await?;
+    Ok(success_response("Pedido simulado com sucesso", 200, simulacao))
+}
+
+pub(crate) fn get_chave_idempotencia(req: &HttpRequest) -> Result<Option<String>, ApiError> {
+    match req.headers().get("Idempotency-Key") {
+        Some(valor) => Ok(Some(valor.to_str()
+            .map_err(|_| AppMessage::new("Idempotency-Key inválida", 400))?
+            .to_string())),
+        None => Ok(None),
     }
+}
+
+pub(crate) fn idempotent_response(message: &str, resposta: RespostaIdempotente) -> HttpResponse {
+    let mut response = success_response(message, resposta.status_code, resposta.corpo);
+    if resposta.repetida {
+        response.headers_mut().insert(
+            HeaderName::from_static("idempotent-replayed"),
+            HeaderValue::from_static("true"),
+        );
+    }
+    response
+}
+
+#[get("")]
+async fn get_all(
+    app_state: web::Data<AppState>,
+    query: web::Query<PedidoQueryParams>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+
+    let pedidos = PedidoService::get_all(&app_state.db_pool, &cliente.id, query.into_inner()).await?;
+    Ok(success_response("Pedidos obtidos com sucesso", 200, pedidos))
+}
+
+#[get("/{id}")]
+async fn get_by_id(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let pedido = PedidoService::get_by_id(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(success_response("Pedido obtido com sucesso", 200, pedido))
+}
+
+#[get("/{id}/historico")]
+async fn get_historico(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let historico = PedidoService::get_historico(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(success_response("Histórico do pedido obtido com sucesso", 200, historico))
+}
+
+#[get("/{id}/boleto")]
+async fn get_boleto(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let svg = PedidoService::get_boleto_svg(&app_state.db_pool, &cliente.id, &id).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .body(svg))
+}
+
+#[get("/{id}/pix/qrcode")]
+async fn get_pix_qrcode(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<QrCodeQueryParams>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let (imagem, content_type) = PedidoService::get_pix_qrcode(&app_state.db_pool, &cliente.id, &id, query.formato.as_deref()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .body(imagem))
+}
+
+#[get("/{id}/comprovante")]
+async fn get_comprovante(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let pdf = PedidoService::get_comprovante(&app_state.db_pool, &id, Some(&cliente.id)).await?;
+    Ok(comprovante_response(&id, pdf))
+}
+
+#[get("/pedidos/{id}/comprovante")]
+async fn get_comprovante_admin(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    let pdf = PedidoService::get_comprovante(&app_state.db_pool, &id, None).await?;
+    Ok(comprovante_response(&id, pdf))
+}
+
+fn comprovante_response(id: &str, pdf: Vec<u8>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(("Content-Disposition", format!("inline; filename=\"comprovante-{}.pdf\"", id)))
+        .body(pdf)
+}
+
+#[post("/{id}/cancelar")]
+async fn cancelar(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<CancelarPedidoPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let cliente = get_cliente_from_request(&req)?;
+    let id = path.into_inner();
+
+    let pedido = PedidoService::cancelar(&app_state.db_pool, &cliente.id, &id, payload.into_inner()).await?;
+    Ok(success_response("Pedido cancelado com sucesso", 200, pedido))
+}
+
+#[patch("/pedidos/{id}/status")]
+async fn alterar_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    payload: web::Json<AlterarStatusPayload>,
+    req: HttpRequest
+) -> Result<HttpResponse, ApiError> {
+    let admin = get_admin_from_request(&req)?;
+    let id = path.into_inner();
 
-    let result = PedidoService::create(&app_state.db_pool, pedido_payload).await?;
-    Ok(success_response("Pedido criado com sucesso", 201, result))
+    let pedido = PedidoService::alterar_status(&app_state.db_pool, &id, payload.into_inner(), &admin.usuario).await?;
+    Ok(success_response("Status do pedido alterado com sucesso", 200, pedido))
 }
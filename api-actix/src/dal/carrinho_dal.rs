@@ -0,0 +1,139 @@
+use crate::db::DbPool;
+use crate::schema::itensCarrinho;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::carrinho::ItemCarrinho;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+pub struct CarrinhoDal;
+
+impl CarrinhoDal {
+    pub async fn get_itens(pool: &DbPool, id_cliente: &str) -> Result<Vec<ItemCarrinho>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            itensCarrinho::table
+                .select(ItemCarrinho::as_select())
+                .filter(itensCarrinho::idCliente.eq(&id_cliente_owned))
+                .order(itensCarrinho::createdAt.asc())
+                .load::<ItemCarrinho>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Adicionar um produto que já está no carrinho soma as quantidades
+    pub async fn adicionar_item(pool: &DbPool, id_cliente: &str, sku_produto: &str, quantidade: i32) -> Result<ItemCarrinho, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let sku_owned = sku_produto.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::insert_into(itensCarrinho::table)
+                .values((
+                    itensCarrinho::id.eq(Uuid::new_v4().to_string()),
+                    itensCarrinho::idCliente.eq(&id_cliente_owned),
+                    itensCarrinho::skuProduto.eq(&sku_owned),
+                    itensCarrinho::quantidade.eq(quantidade),
+                    itensCarrinho::createdAt.eq(diesel::dsl::now),
+                    itensCarrinho::updatedAt.eq(diesel::dsl::now),
+                ))
+                .on_conflict((itensCarrinho::idCliente, itensCarrinho::skuProduto))
+                .do_update()
+                .set((
+                    itensCarrinho::quantidade.eq(itensCarrinho::quantidade + excluded(itensCarrinho::quantidade)),
+                    itensCarrinho::updatedAt.eq(diesel::dsl::now),
+                ))
+                .get_result::<ItemCarrinho>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn atualizar_item(pool: &DbPool, id_cliente: &str, sku_produto: &str, quantidade: i32) -> Result<ItemCarrinho, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let sku_owned = sku_produto.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::update(
+                itensCarrinho::table
+                    .filter(itensCarrinho::idCliente.eq(&id_cliente_owned))
+                    .filter(itensCarrinho::skuProduto.eq(&sku_owned))
+            )
+            .set((
+                itensCarrinho::quantidade.eq(quantidade),
+                itensCarrinho::updatedAt.eq(diesel::dsl::now),
+            ))
+            .get_result::<ItemCarrinho>(&mut connection)
+            .optional()
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::from(AppMessage::new("Produto não está no carrinho", 404)))
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn remover_item(pool: &DbPool, id_cliente: &str, sku_produto: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let sku_owned = sku_produto.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let removidos = diesel::delete(
+                itensCarrinho::table
+                    .filter(itensCarrinho::idCliente.eq(&id_cliente_owned))
+                    .filter(itensCarrinho::skuProduto.eq(&sku_owned))
+            )
+            .execute(&mut connection)?;
+
+            if removidos == 0 {
+                return Err(AppMessage::new("Produto não está no carrinho", 404).into());
+            }
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn limpar(pool: &DbPool, id_cliente: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::delete(itensCarrinho::table.filter(itensCarrinho::idCliente.eq(&id_cliente_owned)))
+                .execute(&mut connection)?;
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Chamado na transação do pedido: só remove os produtos que entraram no pedido
+    pub(crate) fn remover_itens_on(conn: &mut PgConnection, id_cliente: &str, skus: &[&str]) -> Result<(), ApiError> {
+        diesel::delete(
+            itensCarrinho::table
+                .filter(itensCarrinho::idCliente.eq(id_cliente))
+                .filter(itensCarrinho::skuProduto.eq_any(skus))
+        )
+        .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use diesel::prelude::*;
 use crate::db::DbPool;
-use crate::models::cliente::{Cliente, NewCliente};
+use crate::dal::outbox_dal::OutboxDal;
+use crate::models::cliente::{Cliente, ClienteResponse, NewCliente};
+use crate::models::outbox::TipoEvento;
 use crate::utils::app_message::AppMessage;
 
 pub struct ClienteDal;
@@ -17,10 +19,16 @@ impl ClienteDal {
             let mut connection = pool_clone.get()
                 .map_err(|e| AppMessage::new(&format!("Database connection error: {}", e), 500))?;
 
-            match diesel::insert_into(clientes)
-                .values(&payload_owned)
-                .get_result::<Cliente>(&mut connection)
-            {
+            let resultado = connection.transaction::<Cliente, DieselError, _>(|conn| {
+                let cliente = diesel::insert_into(clientes)
+                    .values(&payload_owned)
+                    .get_result::<Cliente>(conn)?;
+
+                OutboxDal::registrar_on(conn, TipoEvento::ClienteCadastrado, &cliente.id, &ClienteResponse::from(cliente.clone()))?;
+                Ok(cliente)
+            });
+
+            match resultado {
                 Ok(cliente) => Ok(cliente),
                 Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => {
                     let error_message = if let Some(constraint) = info.constraint_name() {
@@ -77,4 +85,15 @@ impl ClienteDal {
         }).await
             .map_err(|e| AppMessage::new(&format!("Task error: {}", e), 500))?
     }
+
+    pub(crate) fn find_on(conn: &mut PgConnection, id_cliente: &str) -> Result<Cliente, AppMessage> {
+        use crate::schema::clientes::dsl::*;
+
+        clientes
+            .filter(id.eq(id_cliente))
+            .first::<Cliente>(conn)
+            .optional()
+            .map_err(|e| AppMessage::new(&format!("Erro ao buscar cliente: {}", e), 500))?
+            .ok_or_else(|| AppMessage::new("Cliente não encontrado", 404))
+    }
 }
\ No newline at end of file
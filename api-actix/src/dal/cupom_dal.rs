@@ -0,0 +1,207 @@
+use crate::db::DbPool;
+use crate::schema::{cupons, cuponsRestricoes, cuponsUtilizados};
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::cupom::{Cupom, CupomAplicado, CupomDetalhe, CupomRestricao};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct CupomDal;
+
+impl CupomDal {
+    pub async fn create(pool: &DbPool, cupom: Cupom, categorias: Vec<String>, skus: Vec<String>) -> Result<CupomDetalhe, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<CupomDetalhe, ApiError, _>(|conn| {
+                let cupom = match diesel::insert_into(cupons::table)
+                    .values(&cupom)
+                    .get_result::<Cupom>(conn)
+                {
+                    Ok(cupom) => cupom,
+                    Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                        return Err(AppMessage::new("Já existe um cupom com este código", 409).into());
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                let restricoes = categorias.iter()
+                    .map(|id_categoria| (Some(id_categoria), None))
+                    .chain(skus.iter().map(|sku| (None, Some(sku))));
+
+                for (id_categoria, sku_produto) in restricoes {
+                    diesel::insert_into(cuponsRestricoes::table)
+                        .values((
+                            cuponsRestricoes::id.eq(Uuid::new_v4().to_string()),
+                            cuponsRestricoes::idCupom.eq(&cupom.id),
+                            cuponsRestricoes::idCategoria.eq(id_categoria),
+                            cuponsRestricoes::skuProduto.eq(sku_produto),
+                            cuponsRestricoes::createdAt.eq(diesel::dsl::now),
+                        ))
+                        .execute(conn)?;
+                }
+
+                Ok(CupomDetalhe { cupom, categorias, skus })
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<CupomDetalhe>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let lst_cupons = cupons::table
+                .select(Cupom::as_select())
+                .order(cupons::createdAt.desc())
+                .load::<Cupom>(&mut connection)?;
+
+            let ids: Vec<&String> = lst_cupons.iter().map(|cupom| &cupom.id).collect();
+            let restricoes = cuponsRestricoes::table
+                .filter(cuponsRestricoes::idCupom.eq_any(ids))
+                .select(CupomRestricao::as_select())
+                .load::<CupomRestricao>(&mut connection)?;
+
+            let mut map_restricoes: HashMap<String, Vec<CupomRestricao>> = HashMap::new();
+            for restricao in restricoes {
+                map_restricoes.entry(restricao.id_cupom.clone()).or_default().push(restricao);
+            }
+
+            Ok(lst_cupons.into_iter()
+                .map(|cupom| {
+                    let restricoes = map_restricoes.remove(&cupom.id).unwrap_or_default();
+                    Self::build_detalhe(cupom, restricoes)
+                })
+                .collect())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_by_codigo(pool: &DbPool, codigo: &str) -> Result<Option<CupomDetalhe>, ApiError> {
+        let pool_clone = pool.clone();
+        let codigo_owned = codigo.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let cupom = cupons::table
+                .filter(cupons::codigo.eq(&codigo_owned))
+                .select(Cupom::as_select())
+                .first::<Cupom>(&mut connection)
+                .optional()?;
+
+            let Some(cupom) = cupom else {
+                return Ok(None);
+            };
+
+            let restricoes = cuponsRestricoes::table
+                .filter(cuponsRestricoes::idCupom.eq(&cupom.id))
+                .select(CupomRestricao::as_select())
+                .load::<CupomRestricao>(&mut connection)?;
+
+            Ok(Some(Self::build_detalhe(cupom, restricoes)))
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Chamado dentro da transação do pedido: o update condicional trava a linha do cupom,
+    // então a contagem por cliente logo em seguida não sofre com pedidos concorrentes
+    pub(crate) fn registrar_utilizacao_on(
+        conn: &mut PgConnection,
+        cupom: &CupomAplicado,
+        id_cliente: &str,
+        id_pedido: &str
+    ) -> Result<(), ApiError> {
+        let agora = Utc::now().naive_utc();
+
+        let atualizados = diesel::update(
+            cupons::table
+                .filter(cupons::id.eq(&cupom.id_cupom))
+                .filter(cupons::ativo.eq(true))
+                .filter(cupons::validoDe.le(agora))
+                .filter(cupons::validoAte.ge(agora))
+                .filter(cupons::limiteUsoTotal.is_null().or(cupons::limiteUsoTotal.gt(cupons::quantidadeUtilizada.nullable())))
+        )
+        .set((
+            cupons::quantidadeUtilizada.eq(cupons::quantidadeUtilizada + 1),
+            cupons::updatedAt.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+
+        if atualizados == 0 {
+            return Err(AppMessage::new(&format!("Cupom {} não está mais disponível", cupom.codigo), 422).into());
+        }
+
+        if let Some(limite) = cupom.limite_uso_cliente {
+            let utilizacoes = cuponsUtilizados::table
+                .filter(cuponsUtilizados::idCupom.eq(&cupom.id_cupom))
+                .filter(cuponsUtilizados::idCliente.eq(id_cliente))
+                .count()
+                .get_result::<i64>(conn)?;
+
+            if utilizacoes >= limite as i64 {
+                return Err(AppMessage::new(&format!("Limite de uso do cupom {} por cliente atingido", cupom.codigo), 422).into());
+            }
+        }
+
+        diesel::insert_into(cuponsUtilizados::table)
+            .values((
+                cuponsUtilizados::id.eq(Uuid::new_v4().to_string()),
+                cuponsUtilizados::idCupom.eq(&cupom.id_cupom),
+                cuponsUtilizados::idCliente.eq(id_cliente),
+                cuponsUtilizados::idPedido.eq(id_pedido),
+                cuponsUtilizados::valorDesconto.eq(&cupom.valor_desconto),
+                cuponsUtilizados::createdAt.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    // Pedido cancelado devolve o uso do cupom, tanto no limite total quanto no limite por cliente
+    pub(crate) fn liberar_utilizacao_on(conn: &mut PgConnection, id_pedido: &str) -> Result<(), ApiError> {
+        let cupons_liberados = diesel::delete(cuponsUtilizados::table.filter(cuponsUtilizados::idPedido.eq(id_pedido)))
+            .returning(cuponsUtilizados::idCupom)
+            .get_results::<String>(conn)?;
+
+        for id_cupom in cupons_liberados {
+            diesel::update(
+                cupons::table
+                    .filter(cupons::id.eq(&id_cupom))
+                    .filter(cupons::quantidadeUtilizada.gt(0))
+            )
+            .set((
+                cupons::quantidadeUtilizada.eq(cupons::quantidadeUtilizada - 1),
+                cupons::updatedAt.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+        }
+
+        Ok(())
+    }
+
+    fn build_detalhe(cupom: Cupom, restricoes: Vec<CupomRestricao>) -> CupomDetalhe {
+        let mut categorias = Vec::new();
+        let mut skus = Vec::new();
+
+        for restricao in restricoes {
+            if let Some(id_categoria) = restricao.id_categoria {
+                categorias.push(id_categoria);
+            }
+            if let Some(sku) = restricao.sku_produto {
+                skus.push(sku);
+            }
+        }
+
+        CupomDetalhe { cupom, categorias, skus }
+    }
+}
@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+use crate::dal::outbox_dal::OutboxDal;
+use crate::dal::pedido_dal::PedidoDal;
+use crate::dal::produto_dal::ProdutoDal;
+use crate::db::DbPool;
+use crate::models::devolucao::{Devolucao, DevolucaoDetalhada, HistoricoStatusDevolucao, ItemDevolucao, MotivoDevolucao, StatusDevolucao, PRAZO_DEVOLUCAO_DIAS};
+use crate::models::outbox::TipoEvento;
+use crate::models::pedido::{FormaPagamento, Pagamento, Pedido, ProdutosPedido, StatusPagamento, StatusPedido};
+use crate::providers::pagamento::{self, SolicitacaoEstorno};
+use crate::schema::{devolucoes, historicoStatusDevolucao, historicoStatusPedido, itensDevolucao, pagamentos, pedidos, produtosPedido};
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::utils::money;
+
+pub struct DevolucaoDal;
+
+// Estorno conferido antes da aprovação, pedido ao provedor fora da transação
+struct EstornoDevolucao {
+    id_pedido: String,
+    forma_pagamento: FormaPagamento,
+    tid: Option<String>,
+    valor: BigDecimal,
+}
+
+const LIMITE_LISTAGEM: i64 = 100;
+
+impl DevolucaoDal {
+    // O pedido fica travado durante a solicitação para que duas devoluções simultâneas não
+    // somem mais unidades do que foram compradas
+    pub async fn create(
+        pool: &DbPool,
+        id_cliente: &str,
+        id_pedido: &str,
+        itens: Vec<(String, BigDecimal, MotivoDevolucao)>,
+        observacao: Option<String>
+    ) -> Result<DevolucaoDetalhada, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let id_pedido_owned = id_pedido.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<DevolucaoDetalhada, ApiError, _>(|conn| {
+                let pedido = Self::travar_pedido(conn, &id_pedido_owned)?;
+                if pedido.id_cliente != id_cliente_owned {
+                    return Err(AppMessage::new("Pedido não encontrado", 404).into());
+                }
+
+                if StatusPedido::from_str(&pedido.status)? != StatusPedido::Entregue {
+                    return Err(AppMessage::new("Só pedidos entregues podem ser devolvidos", 422).into());
+                }
+
+                // Pedidos anteriores ao histórico de status usam a última atualização como data de entrega
+                let entregue_em = historicoStatusPedido::table
+                    .select(historicoStatusPedido::createdAt)
+                    .filter(historicoStatusPedido::idPedido.eq(&id_pedido_owned))
+                    .filter(historicoStatusPedido::statusNovo.eq(StatusPedido::Entregue.as_str()))
+                    .order_by(historicoStatusPedido::createdAt.desc())
+                    .first::<chrono::NaiveDateTime>(conn)
+                    .optional()?
+                    .unwrap_or(pedido.updated_at);
+
+                let prazo = entregue_em + Duration::days(PRAZO_DEVOLUCAO_DIAS);
+                if Utc::now().naive_utc() > prazo {
+                    return Err(AppMessage::new(
+                        &format!("O prazo de {} dias para devolução terminou em {}", PRAZO_DEVOLUCAO_DIAS, prazo.format("%d/%m/%Y")),
+                        422
+                    ).into());
+                }
+
+                let em_analise = devolucoes::table
+                    .select(devolucoes::id)
+                    .filter(devolucoes::idPedido.eq(&id_pedido_owned))
+                    .filter(devolucoes::status.eq(StatusDevolucao::Solicitada.as_str()))
+                    .first::<String>(conn)
+                    .optional()?;
+
+                if em_analise.is_some() {
+                    return Err(AppMessage::new("Pedido já possui uma devolução em análise", 409).into());
+                }
+
+                let itens_pedido: HashMap<String, ProdutosPedido> = produtosPedido::table
+                    .select(ProdutosPedido::as_select())
+                    .filter(produtosPedido::idPedido.eq(&id_pedido_owned))
+                    .load::<ProdutosPedido>(conn)?
+                    .into_iter()
+                    .map(|item| (item.id.clone(), item))
+                    .collect();
+
+                let devolvidos = Self::devolvidos_on(conn, &id_pedido_owned)?;
+                let id_devolucao = Uuid::new_v4().to_string();
+                let mut registros = Vec::with_capacity(itens.len());
+
+                for (id_item, quantidade, motivo) in itens {
+                    let item = itens_pedido.get(&id_item)
+                        .ok_or_else(|| AppMessage::new(&format!("Item {} não pertence ao pedido", id_item), 422))?;
+
+                    let (quantidade_devolvida, valor_reembolsado) = devolvidos.get(&id_item)
+                        .cloned()
+                        .unwrap_or_else(|| (money::zero(), money::zero()));
+                    let quantidade_total = &quantidade_devolvida + &quantidade;
+
+                    if quantidade_total > item.quantidade {
+                        return Err(AppMessage::new(
+                            &format!("Quantidade a devolver do produto {} excede a quantidade comprada", item.sku_produto),
+                            422
+                        ).into());
+                    }
+
+                    // O reembolso inclui a parte do frete rateada no item. Quem devolve o restante
+                    // do item recebe a diferença, para as devoluções parciais somarem o valor pago
+                    let valor_item = &item.valor_liquido + &item.valor_frete;
+                    let valor_reembolso = if quantidade_total == item.quantidade {
+                        &valor_item - &valor_reembolsado
+                    } else {
+                        money::arredondar(&(&valor_item * &quantidade / &item.quantidade))
+                    };
+
+                    registros.push(ItemDevolucao {
+                        id: Uuid::new_v4().to_string(),
+                        id_devolucao: id_devolucao.clone(),
+                        id_produto_pedido: item.id.clone(),
+                        sku_produto: item.sku_produto.clone(),
+                        quantidade,
+                        motivo: motivo.as_str().to_string(),
+                        valor_reembolso,
+                    });
+                }
+
+                let valor_reembolso = registros.iter()
+                    .fold(money::zero(), |total, item| total + &item.valor_reembolso);
+
+                diesel::insert_into(devolucoes::table)
+                    .values((
+                        devolucoes::id.eq(&id_devolucao),
+                        devolucoes::idPedido.eq(&id_pedido_owned),
+                        devolucoes::idCliente.eq(&id_cliente_owned),
+                        devolucoes::status.eq(StatusDevolucao::Solicitada.as_str()),
+                        devolucoes::observacao.eq(&observacao),
+                        devolucoes::valorReembolso.eq(&valor_reembolso),
+                        devolucoes::createdAt.eq(diesel::dsl::now),
+                        devolucoes::updatedAt.eq(diesel::dsl::now),
+                    ))
+                    .execute(conn)?;
+
+                diesel::insert_into(itensDevolucao::table)
+                    .values(&registros)
+                    .execute(conn)?;
+
+                Self::registrar_status(conn, &id_devolucao, None, StatusDevolucao::Solicitada, &id_cliente_owned, Some("Devolução solicitada"))?;
+
+                Self::detalhar_on(conn, &id_devolucao, None)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all_by_pedido(pool: &DbPool, id_cliente: &str, id_pedido: &str) -> Result<Vec<DevolucaoDetalhada>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let id_pedido_owned = id_pedido.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            PedidoDal::find_pedido(&mut connection, &id_pedido_owned, Some(&id_cliente_owned))?;
+
+            let ids = devolucoes::table
+                .select(devolucoes::id)
+                .filter(devolucoes::idPedido.eq(&id_pedido_owned))
+                .order_by(devolucoes::createdAt.asc())
+                .load::<String>(&mut connection)?;
+
+            ids.iter()
+                .map(|id| Self::detalhar_on(&mut connection, id, Some(&id_cliente_owned)))
+                .collect()
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all(pool: &DbPool, status: Option<StatusDevolucao>) -> Result<Vec<Devolucao>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let mut query = devolucoes::table
+                .select(Devolucao::as_select())
+                .order_by(devolucoes::createdAt.asc())
+                .limit(LIMITE_LISTAGEM)
+                .into_boxed();
+
+            if let Some(status) = status {
+                query = query.filter(devolucoes::status.eq(status.as_str()));
+            }
+
+            query
+                .load::<Devolucao>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_by_id(pool: &DbPool, id: &str) -> Result<DevolucaoDetalhada, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            Self::detalhar_on(&mut connection, &id_owned, None)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Aprovar devolve as unidades ao estoque, desfaz a contagem de vendas e pede o estorno do
+    // valor ao provedor. Quando todos os itens do pedido voltaram, o pedido passa a devolvido
+    pub async fn aprovar(pool: &DbPool, id: &str, alterado_por: &str, motivo: Option<String>) -> Result<DevolucaoDetalhada, ApiError> {
+        let estorno = Self::conferir_aprovacao(pool, id).await?;
+
+        // O estorno é pedido fora de transação e só depois a aprovação é gravada. O id da devolução é a
+        // referência idempotente: aprovar de novo depois de uma falha não estorna duas vezes
+        let id_devolucao = id.to_string();
+        let valor_estorno = estorno.valor.clone();
+        tokio::task::spawn_blocking(move || {
+            pagamento::provider_para(estorno.forma_pagamento).estornar(&SolicitacaoEstorno {
+                id_pedido: &estorno.id_pedido,
+                id_devolucao: &id_devolucao,
+                forma_pagamento: estorno.forma_pagamento,
+                tid: estorno.tid.as_deref(),
+                valor: &estorno.valor,
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))??;
+
+        Self::gravar_aprovacao(pool, id, alterado_por, motivo, valor_estorno).await
+    }
+
+    async fn conferir_aprovacao(pool: &DbPool, id: &str) -> Result<EstornoDevolucao, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<EstornoDevolucao, ApiError, _>(|conn| {
+                let (devolucao, pedido, pagamento) = Self::travar_aprovacao(conn, &id_owned)?;
+
+                Ok(EstornoDevolucao {
+                    id_pedido: pedido.id,
+                    forma_pagamento: FormaPagamento::from_str(&pagamento.forma_pagamento)?,
+                    valor: Self::valor_estorno(&pagamento, &devolucao),
+                    tid: pagamento.tid,
+                })
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Refaz a conferência, já que outra operação pode ter mudado a devolução enquanto o provedor respondia
+    async fn gravar_aprovacao(
+        pool: &DbPool,
+        id: &str,
+        alterado_por: &str,
+        motivo: Option<String>,
+        valor_estorno: BigDecimal
+    ) -> Result<DevolucaoDetalhada, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let alterado_por_owned = alterado_por.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<DevolucaoDetalhada, ApiError, _>(|conn| {
+                let (devolucao, pedido, pagamento) = Self::travar_aprovacao(conn, &id_owned)?;
+
+                let itens = itensDevolucao::table
+                    .select(ItemDevolucao::as_select())
+                    .filter(itensDevolucao::idDevolucao.eq(&devolucao.id))
+                    .load::<ItemDevolucao>(conn)?;
+
+                for item in &itens {
+                    ProdutoDal::update_estoque_on(conn, &item.sku_produto, &item.quantidade)?;
+                    ProdutoDal::update_vendas_on(conn, &item.sku_produto, &(-&item.quantidade))?;
+                }
+
+                // Grava o valor que foi de fato pedido ao provedor
+                diesel::update(pagamentos::table)
+                    .filter(pagamentos::id.eq(&pagamento.id))
+                    .set((
+                        pagamentos::status.eq(StatusPagamento::EstornoSolicitado.as_str()),
+                        pagamentos::valorEstornado.eq(&pagamento.valor_estornado + &valor_estorno),
+                        pagamentos::updatedAt.eq(diesel::dsl::now),
+                    ))
+                    .execute(conn)?;
+
+                Self::update_status_on(conn, &devolucao.id, StatusDevolucao::Aprovada)?;
+                Self::registrar_status(conn, &devolucao.id, Some(StatusDevolucao::Solicitada), StatusDevolucao::Aprovada, &alterado_por_owned, motivo.as_deref())?;
+
+                let itens_pedido = produtosPedido::table
+                    .select(ProdutosPedido::as_select())
+                    .filter(produtosPedido::idPedido.eq(&pedido.id))
+                    .load::<ProdutosPedido>(conn)?;
+                let devolvidos = Self::devolvidos_on(conn, &pedido.id)?;
+
+                let pedido_devolvido = itens_pedido.iter().all(|item| {
+                    devolvidos.get(&item.id).is_some_and(|(quantidade, _)| *quantidade >= item.quantidade)
+                });
+
+                if pedido_devolvido {
+                    let motivo_pedido = format!("Devolução {} aprovada", devolucao.id);
+                    PedidoDal::update_status_on(conn, &pedido.id, StatusPedido::Entregue, StatusPedido::Devolvido)?;
+                    let historico = PedidoDal::registrar_status(conn, &pedido.id, Some(StatusPedido::Entregue), StatusPedido::Devolvido, &alterado_por_owned, Some(&motivo_pedido))?;
+                    OutboxDal::registrar_on(conn, TipoEvento::StatusPedidoAlterado, &pedido.id, &historico)?;
+                }
+
+                Self::detalhar_on(conn, &devolucao.id, None)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn recusar(pool: &DbPool, id: &str, alterado_por: &str, motivo: String) -> Result<DevolucaoDetalhada, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let alterado_por_owned = alterado_por.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<DevolucaoDetalhada, ApiError, _>(|conn| {
+                let devolucao = Self::travar_solicitada(conn, &id_owned)?;
+
+                Self::update_status_on(conn, &devolucao.id, StatusDevolucao::Recusada)?;
+                Self::registrar_status(conn, &devolucao.id, Some(StatusDevolucao::Solicitada), StatusDevolucao::Recusada, &alterado_por_owned, Some(&motivo))?;
+
+                Self::detalhar_on(conn, &devolucao.id, None)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Trava devolução, pedido e pagamento e confere se a devolução ainda pode ser aprovada
+    fn travar_aprovacao(conn: &mut PgConnection, id: &str) -> Result<(Devolucao, Pedido, Pagamento), ApiError> {
+        let devolucao = Self::travar_solicitada(conn, id)?;
+        let pedido = Self::travar_pedido(conn, &devolucao.id_pedido)?;
+        let status_pedido = StatusPedido::from_str(&pedido.status)?;
+
+        if status_pedido != StatusPedido::Entregue {
+            return Err(AppMessage::new(
+                &format!("Não é possível aprovar a devolução de um pedido com status {}", status_pedido),
+                422
+            ).into());
+        }
+
+        let pagamento = pagamentos::table
+            .select(Pagamento::as_select())
+            .filter(pagamentos::idPedido.eq(&pedido.id))
+            .for_update()
+            .first::<Pagamento>(conn)
+            .optional()?
+            .ok_or_else(|| AppMessage::new("Pagamento do pedido não encontrado", 404))?;
+
+        let status_pagamento = StatusPagamento::from_str(&pagamento.status)?;
+        if !matches!(status_pagamento, StatusPagamento::Aprovado | StatusPagamento::EstornoSolicitado | StatusPagamento::Estornado) {
+            return Err(AppMessage::new("Pagamento do pedido não permite estorno", 422).into());
+        }
+
+        Ok((devolucao, pedido, pagamento))
+    }
+
+    // O total estornado nunca passa do valor pago
+    fn valor_estorno(pagamento: &Pagamento, devolucao: &Devolucao) -> BigDecimal {
+        let valor_estornado = (&pagamento.valor_estornado + &devolucao.valor_reembolso).min(pagamento.valor_total.clone());
+        valor_estornado - &pagamento.valor_estornado
+    }
+
+    fn travar_pedido(conn: &mut PgConnection, id: &str) -> Result<Pedido, ApiError> {
+        pedidos::table
+            .select(Pedido::as_select())
+            .filter(pedidos::id.eq(id))
+            .for_update()
+            .first::<Pedido>(conn)
+            .optional()?
+            .ok_or_else(|| ApiError::from(AppMessage::new("Pedido não encontrado", 404)))
+    }
+
+    fn travar_solicitada(conn: &mut PgConnection, id: &str) -> Result<Devolucao, ApiError> {
+        let devolucao = devolucoes::table
+            .select(Devolucao::as_select())
+            .filter(devolucoes::id.eq(id))
+            .for_update()
+            .first::<Devolucao>(conn)
+            .optional()?
+            .ok_or_else(|| AppMessage::new("Devolução não encontrada", 404))?;
+
+        let status = StatusDevolucao::from_str(&devolucao.status)?;
+        if status != StatusDevolucao::Solicitada {
+            return Err(AppMessage::new(&format!("Devolução já está {}", status.descricao().to_lowercase()), 409).into());
+        }
+
+        Ok(devolucao)
+    }
+
+    // Quantidade e valor já devolvidos de cada item do pedido, somando só as devoluções aprovadas
+    fn devolvidos_on(conn: &mut PgConnection, id_pedido: &str) -> Result<HashMap<String, (BigDecimal, BigDecimal)>, ApiError> {
+        let itens = itensDevolucao::table
+            .inner_join(devolucoes::table)
+            .select((itensDevolucao::idProdutoPedido, itensDevolucao::quantidade, itensDevolucao::valorReembolso))
+            .filter(devolucoes::idPedido.eq(id_pedido))
+            .filter(devolucoes::status.eq(StatusDevolucao::Aprovada.as_str()))
+            .load::<(String, BigDecimal, BigDecimal)>(conn)?;
+
+        let mut devolvidos: HashMap<String, (BigDecimal, BigDecimal)> = HashMap::new();
+        for (id_item, quantidade, valor) in itens {
+            let (total_quantidade, total_valor) = devolvidos.entry(id_item)
+                .or_insert_with(|| (money::zero(), money::zero()));
+            *total_quantidade += quantidade;
+            *total_valor += valor;
+        }
+
+        Ok(devolvidos)
+    }
+
+    fn detalhar_on(conn: &mut PgConnection, id: &str, id_cliente: Option<&str>) -> Result<DevolucaoDetalhada, ApiError> {
+        let mut query = devolucoes::table
+            .select(Devolucao::as_select())
+            .filter(devolucoes::id.eq(id.to_string()))
+            .into_boxed();
+
+        if let Some(id_cliente) = id_cliente {
+            query = query.filter(devolucoes::idCliente.eq(id_cliente.to_string()));
+        }
+
+        let devolucao = query
+            .first::<Devolucao>(conn)
+            .optional()?
+            .ok_or_else(|| AppMessage::new("Devolução não encontrada", 404))?;
+
+        let itens = itensDevolucao::table
+            .select(ItemDevolucao::as_select())
+            .filter(itensDevolucao::idDevolucao.eq(id))
+            .order_by(itensDevolucao::skuProduto.asc())
+            .load::<ItemDevolucao>(conn)?;
+
+        let historico = historicoStatusDevolucao::table
+            .select(HistoricoStatusDevolucao::as_select())
+            .filter(historicoStatusDevolucao::idDevolucao.eq(id))
+            .order_by(historicoStatusDevolucao::createdAt.asc())
+            .load::<HistoricoStatusDevolucao>(conn)?;
+
+        Ok(DevolucaoDetalhada { devolucao, itens, historico })
+    }
+
+    fn update_status_on(conn: &mut PgConnection, id: &str, status: StatusDevolucao) -> Result<(), ApiError> {
+        diesel::update(devolucoes::table)
+            .filter(devolucoes::id.eq(id))
+            .set((
+                devolucoes::status.eq(status.as_str()),
+                devolucoes::updatedAt.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    fn registrar_status(
+        conn: &mut PgConnection,
+        id_devolucao: &str,
+        status_anterior: Option<StatusDevolucao>,
+        status_novo: StatusDevolucao,
+        alterado_por: &str,
+        motivo: Option<&str>
+    ) -> Result<HistoricoStatusDevolucao, diesel::result::Error> {
+        diesel::insert_into(historicoStatusDevolucao::table)
+            .values((
+                historicoStatusDevolucao::id.eq(Uuid::new_v4().to_string()),
+                historicoStatusDevolucao::idDevolucao.eq(id_devolucao),
+                historicoStatusDevolucao::statusAnterior.eq(status_anterior.map(|s| s.as_str())),
+                historicoStatusDevolucao::statusNovo.eq(status_novo.as_str()),
+                historicoStatusDevolucao::alteradoPor.eq(alterado_por),
+                historicoStatusDevolucao::motivo.eq(motivo),
+                historicoStatusDevolucao::createdAt.eq(diesel::dsl::now),
+            ))
+            .get_result::<HistoricoStatusDevolucao>(conn)
+    }
+}
@@ -0,0 +1,207 @@
+use crate::db::DbPool;
+use crate::schema::{clientes, enderecosCliente};
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::endereco::EnderecoCliente;
+use diesel::prelude::*;
+
+pub struct EnderecoDal;
+
+impl EnderecoDal {
+    pub async fn get_all(pool: &DbPool, id_cliente: &str) -> Result<Vec<EnderecoCliente>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            enderecosCliente::table
+                .select(EnderecoCliente::as_select())
+                .filter(enderecosCliente::idCliente.eq(&id_cliente_owned))
+                .order((enderecosCliente::padrao.desc(), enderecosCliente::createdAt.asc()))
+                .load::<EnderecoCliente>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Sem id devolve o endereço padrão do cliente
+    pub async fn get(pool: &DbPool, id_cliente: &str, id: Option<&str>) -> Result<Option<EnderecoCliente>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let id_owned = id.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let mut query = enderecosCliente::table
+                .select(EnderecoCliente::as_select())
+                .filter(enderecosCliente::idCliente.eq(&id_cliente_owned))
+                .into_boxed();
+
+            query = match &id_owned {
+                Some(id) => query.filter(enderecosCliente::id.eq(id)),
+                None => query.filter(enderecosCliente::padrao.eq(true)),
+            };
+
+            query
+                .first::<EnderecoCliente>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // O primeiro endereço do cliente vira o padrão mesmo que não tenha sido pedido
+    pub async fn create(pool: &DbPool, mut endereco: EnderecoCliente) -> Result<EnderecoCliente, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<EnderecoCliente, ApiError, _>(|conn| {
+                Self::travar_cliente(conn, &endereco.id_cliente)?;
+
+                let possui_padrao = enderecosCliente::table
+                    .filter(enderecosCliente::idCliente.eq(&endereco.id_cliente))
+                    .filter(enderecosCliente::padrao.eq(true))
+                    .count()
+                    .get_result::<i64>(conn)? > 0;
+
+                if !possui_padrao {
+                    endereco.padrao = true;
+                } else if endereco.padrao {
+                    Self::desmarcar_padrao_on(conn, &endereco.id_cliente)?;
+                }
+
+                diesel::insert_into(enderecosCliente::table)
+                    .values(&endereco)
+                    .get_result::<EnderecoCliente>(conn)
+                    .map_err(ApiError::from)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // O padrão só deixa de ser padrão quando outro endereço é escolhido no lugar dele
+    pub async fn update(pool: &DbPool, endereco: EnderecoCliente, tornar_padrao: Option<bool>) -> Result<EnderecoCliente, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<EnderecoCliente, ApiError, _>(|conn| {
+                Self::travar_cliente(conn, &endereco.id_cliente)?;
+
+                let atual = enderecosCliente::table
+                    .select(EnderecoCliente::as_select())
+                    .filter(enderecosCliente::id.eq(&endereco.id))
+                    .filter(enderecosCliente::idCliente.eq(&endereco.id_cliente))
+                    .first::<EnderecoCliente>(conn)
+                    .optional()?
+                    .ok_or_else(|| AppMessage::new("Endereço não encontrado", 404))?;
+
+                let padrao = match tornar_padrao {
+                    Some(true) => {
+                        Self::desmarcar_padrao_on(conn, &endereco.id_cliente)?;
+                        true
+                    }
+                    Some(false) if atual.padrao => {
+                        return Err(AppMessage::new("Escolha outro endereço como padrão antes de desmarcar este", 422).into());
+                    }
+                    _ => atual.padrao,
+                };
+
+                diesel::update(enderecosCliente::table.filter(enderecosCliente::id.eq(&endereco.id)))
+                    .set((
+                        enderecosCliente::apelido.eq(&endereco.apelido),
+                        enderecosCliente::nomeRemetente.eq(&endereco.nome_remetente),
+                        enderecosCliente::cep.eq(&endereco.cep),
+                        enderecosCliente::logradouro.eq(&endereco.logradouro),
+                        enderecosCliente::numero.eq(&endereco.numero),
+                        enderecosCliente::complemento.eq(&endereco.complemento),
+                        enderecosCliente::bairro.eq(&endereco.bairro),
+                        enderecosCliente::cidade.eq(&endereco.cidade),
+                        enderecosCliente::codigoIbgeCidade.eq(&endereco.codigo_ibge_cidade),
+                        enderecosCliente::codigoIbgeUF.eq(&endereco.codigo_ibge_uf),
+                        enderecosCliente::padrao.eq(padrao),
+                        enderecosCliente::updatedAt.eq(diesel::dsl::now),
+                    ))
+                    .get_result::<EnderecoCliente>(conn)
+                    .map_err(ApiError::from)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Remover o padrão promove o endereço alterado mais recentemente
+    pub async fn delete(pool: &DbPool, id_cliente: &str, id: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<(), ApiError, _>(|conn| {
+                Self::travar_cliente(conn, &id_cliente_owned)?;
+
+                let removido = diesel::delete(
+                    enderecosCliente::table
+                        .filter(enderecosCliente::id.eq(&id_owned))
+                        .filter(enderecosCliente::idCliente.eq(&id_cliente_owned))
+                )
+                .get_result::<EnderecoCliente>(conn)
+                .optional()?
+                .ok_or_else(|| AppMessage::new("Endereço não encontrado", 404))?;
+
+                if removido.padrao {
+                    let proximo = enderecosCliente::table
+                        .select(enderecosCliente::id)
+                        .filter(enderecosCliente::idCliente.eq(&id_cliente_owned))
+                        .order(enderecosCliente::updatedAt.desc())
+                        .first::<String>(conn)
+                        .optional()?;
+
+                    if let Some(id_proximo) = proximo {
+                        diesel::update(enderecosCliente::table.filter(enderecosCliente::id.eq(&id_proximo)))
+                            .set(enderecosCliente::padrao.eq(true))
+                            .execute(conn)?;
+                    }
+                }
+
+                Ok(())
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Serializa as alterações de endereço do mesmo cliente, que disputam o único endereço padrão
+    fn travar_cliente(conn: &mut PgConnection, id_cliente: &str) -> Result<(), ApiError> {
+        clientes::table
+            .select(clientes::id)
+            .filter(clientes::id.eq(id_cliente))
+            .for_update()
+            .first::<String>(conn)
+            .optional()?
+            .ok_or_else(|| AppMessage::new("Cliente não encontrado", 404))?;
+
+        Ok(())
+    }
+
+    fn desmarcar_padrao_on(conn: &mut PgConnection, id_cliente: &str) -> Result<(), ApiError> {
+        diesel::update(
+            enderecosCliente::table
+                .filter(enderecosCliente::idCliente.eq(id_cliente))
+                .filter(enderecosCliente::padrao.eq(true))
+        )
+        .set(enderecosCliente::padrao.eq(false))
+        .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::db::DbPool;
+use crate::schema::feriados;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::feriado::Feriado;
+use diesel::prelude::*;
+
+pub struct FeriadoDal;
+
+impl FeriadoDal {
+    pub async fn create(pool: &DbPool, feriado: Feriado) -> Result<Feriado, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::insert_into(feriados::table)
+                .values(&feriado)
+                .get_result::<Feriado>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<Feriado>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            feriados::table
+                .select(Feriado::as_select())
+                .order((feriados::mes.asc(), feriados::dia.asc()))
+                .load::<Feriado>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn delete(pool: &DbPool, id: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let removidos = diesel::delete(feriados::table.filter(feriados::id.eq(&id_owned)))
+                .execute(&mut connection)?;
+
+            if removidos == 0 {
+                return Err(AppMessage::new("Feriado não encontrado", 404).into());
+            }
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Feriados nacionais cadastrados mais os da UF e da cidade de destino
+    // Sem cidade de destino (cotação só pelo CEP) ficam de fora os feriados municipais
+    pub async fn get_aplicaveis(pool: &DbPool, codigo_ibge_uf: &str, codigo_ibge_cidade: Option<&str>, anos: Vec<i16>) -> Result<Vec<Feriado>, ApiError> {
+        let pool_clone = pool.clone();
+        let uf_owned = codigo_ibge_uf.to_string();
+        let cidade_owned = codigo_ibge_cidade.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            feriados::table
+                .select(Feriado::as_select())
+                .filter(
+                    feriados::codigoIbgeUF.is_null()
+                        .or(feriados::codigoIbgeUF.eq(&uf_owned))
+                )
+                .filter(
+                    feriados::codigoIbgeCidade.is_null()
+                        .or(feriados::codigoIbgeCidade.eq(&cidade_owned))
+                )
+                .filter(feriados::ano.is_null().or(feriados::ano.eq_any(anos)))
+                .load::<Feriado>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+}
@@ -0,0 +1,108 @@
+use crate::db::DbPool;
+use crate::schema::regrasFrete;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::frete::RegraFrete;
+use diesel::prelude::*;
+
+pub struct FreteDal;
+
+impl FreteDal {
+    pub async fn create(pool: &DbPool, regra: RegraFrete) -> Result<RegraFrete, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::insert_into(regrasFrete::table)
+                .values(&regra)
+                .get_result::<RegraFrete>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<RegraFrete>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            regrasFrete::table
+                .select(RegraFrete::as_select())
+                .order((regrasFrete::modalidade.asc(), regrasFrete::codigoIbgeUF.asc(), regrasFrete::cepInicial.asc(), regrasFrete::pesoMinimo.asc()))
+                .load::<RegraFrete>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_ativas(pool: &DbPool) -> Result<Vec<RegraFrete>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            regrasFrete::table
+                .select(RegraFrete::as_select())
+                .filter(regrasFrete::ativo.eq(true))
+                .load::<RegraFrete>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Substitui a regra inteira, preservando apenas o id e a data de criação
+    pub async fn update(pool: &DbPool, regra: RegraFrete) -> Result<RegraFrete, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::update(regrasFrete::table.filter(regrasFrete::id.eq(&regra.id)))
+                .set((
+                    regrasFrete::descricao.eq(&regra.descricao),
+                    regrasFrete::codigoIbgeUF.eq(&regra.codigo_ibge_uf),
+                    regrasFrete::cepInicial.eq(&regra.cep_inicial),
+                    regrasFrete::cepFinal.eq(&regra.cep_final),
+                    regrasFrete::pesoMinimo.eq(&regra.peso_minimo),
+                    regrasFrete::pesoMaximo.eq(&regra.peso_maximo),
+                    regrasFrete::valorFrete.eq(&regra.valor_frete),
+                    regrasFrete::valorMinimoFreteGratis.eq(&regra.valor_minimo_frete_gratis),
+                    regrasFrete::prioridade.eq(regra.prioridade),
+                    regrasFrete::ativo.eq(regra.ativo),
+                    regrasFrete::modalidade.eq(&regra.modalidade),
+                    regrasFrete::prazoDiasUteis.eq(regra.prazo_dias_uteis),
+                    regrasFrete::updatedAt.eq(diesel::dsl::now),
+                ))
+                .get_result::<RegraFrete>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)?
+                .ok_or_else(|| ApiError::from(AppMessage::new("Regra de frete não encontrada", 404)))
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn delete(pool: &DbPool, id: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let removidos = diesel::delete(regrasFrete::table.filter(regrasFrete::id.eq(&id_owned)))
+                .execute(&mut connection)?;
+
+            if removidos == 0 {
+                return Err(AppMessage::new("Regra de frete não encontrada", 404).into());
+            }
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+}
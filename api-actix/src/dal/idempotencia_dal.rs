@@ -0,0 +1,149 @@
+use crate::db::DbPool;
+use crate::schema::chavesIdempotencia;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::idempotencia::{ChaveIdempotencia, ReservaIdempotencia, RespostaIdempotente};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use serde_json::Value;
+use uuid::Uuid;
+
+pub struct IdempotenciaDal;
+
+impl IdempotenciaDal {
+    pub async fn reservar(
+        pool: &DbPool,
+        id_cliente: &str,
+        chave: &str,
+        hash_requisicao: &str,
+        validade_horas: i64,
+        reserva_segundos: i64
+    ) -> Result<ReservaIdempotencia, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let chave_owned = chave.to_string();
+        let hash_owned = hash_requisicao.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<ReservaIdempotencia, ApiError, _>(|conn| {
+                let agora = Utc::now().naive_utc();
+
+                // Chaves vencidas do cliente são descartadas antes da reserva
+                diesel::delete(
+                    chavesIdempotencia::table
+                        .filter(chavesIdempotencia::idCliente.eq(&id_cliente_owned))
+                        .filter(chavesIdempotencia::expiraEm.lt(agora))
+                )
+                .execute(conn)?;
+
+                let inseridas = diesel::insert_into(chavesIdempotencia::table)
+                    .values((
+                        chavesIdempotencia::id.eq(Uuid::new_v4().to_string()),
+                        chavesIdempotencia::idCliente.eq(&id_cliente_owned),
+                        chavesIdempotencia::chave.eq(&chave_owned),
+                        chavesIdempotencia::hashRequisicao.eq(&hash_owned),
+                        chavesIdempotencia::createdAt.eq(agora),
+                        chavesIdempotencia::expiraEm.eq(agora + Duration::hours(validade_horas)),
+                    ))
+                    .on_conflict((chavesIdempotencia::idCliente, chavesIdempotencia::chave))
+                    .do_nothing()
+                    .execute(conn)?;
+
+                if inseridas > 0 {
+                    return Ok(ReservaIdempotencia::Nova);
+                }
+
+                let existente = chavesIdempotencia::table
+                    .filter(chavesIdempotencia::idCliente.eq(&id_cliente_owned))
+                    .filter(chavesIdempotencia::chave.eq(&chave_owned))
+                    .select(ChaveIdempotencia::as_select())
+                    .first::<ChaveIdempotencia>(conn)?;
+
+                if existente.hash_requisicao != hash_owned {
+                    return Err(AppMessage::new("Idempotency-Key já utilizada com uma requisição diferente", 422).into());
+                }
+
+                match (existente.status_code, existente.resposta) {
+                    (Some(status_code), Some(resposta)) => {
+                        let corpo = serde_json::from_str::<Value>(&resposta)
+                            .map_err(|e| AppMessage::new(&format!("Resposta armazenada inválida: {}", e), 500))?;
+
+                        Ok(ReservaIdempotencia::Concluida(RespostaIdempotente {
+                            status_code: status_code as u16,
+                            corpo,
+                            repetida: true,
+                        }))
+                    }
+                    _ => {
+                        // Reserva pendente além do prazo ficou de uma requisição que caiu no meio e é retomada;
+                        // o filtro no update garante que só um retry concorrente fique com ela
+                        let retomadas = diesel::update(
+                            chavesIdempotencia::table
+                                .filter(chavesIdempotencia::id.eq(&existente.id))
+                                .filter(chavesIdempotencia::resposta.is_null())
+                                .filter(chavesIdempotencia::createdAt.lt(agora - Duration::seconds(reserva_segundos)))
+                        )
+                        .set((
+                            chavesIdempotencia::createdAt.eq(agora),
+                            chavesIdempotencia::expiraEm.eq(agora + Duration::hours(validade_horas)),
+                        ))
+                        .execute(conn)?;
+
+                        if retomadas > 0 {
+                            return Ok(ReservaIdempotencia::Nova);
+                        }
+
+                        Err(AppMessage::new("Já existe uma requisição em processamento com esta Idempotency-Key", 409).into())
+                    }
+                }
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn liberar(pool: &DbPool, id_cliente: &str, chave: &str) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let chave_owned = chave.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            // Só remove reservas sem resposta; respostas concluídas continuam valendo até expirar
+            diesel::delete(
+                chavesIdempotencia::table
+                    .filter(chavesIdempotencia::idCliente.eq(&id_cliente_owned))
+                    .filter(chavesIdempotencia::chave.eq(&chave_owned))
+                    .filter(chavesIdempotencia::resposta.is_null())
+            )
+            .execute(&mut connection)?;
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub(crate) fn concluir_on(
+        conn: &mut PgConnection,
+        id_cliente: &str,
+        chave: &str,
+        status_code: u16,
+        corpo: &Value
+    ) -> Result<(), diesel::result::Error> {
+        diesel::update(
+            chavesIdempotencia::table
+                .filter(chavesIdempotencia::idCliente.eq(id_cliente))
+                .filter(chavesIdempotencia::chave.eq(chave))
+        )
+        .set((
+            chavesIdempotencia::statusCode.eq(status_code as i32),
+            chavesIdempotencia::resposta.eq(corpo.to_string()),
+        ))
+        .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,14 @@
 pub mod categoria_dal;
 pub mod produto_dal;
 pub mod cliente_dal;
-pub mod pedido_dal;
\ No newline at end of file
+pub mod pedido_dal;
+pub mod idempotencia_dal;
+pub mod cupom_dal;
+pub mod pagamento_dal;
+pub mod carrinho_dal;
+pub mod feriado_dal;
+pub mod frete_dal;
+pub mod endereco_dal;
+pub mod nfe_dal;
+pub mod outbox_dal;
+pub mod devolucao_dal;
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::db::DbPool;
+use crate::schema::{clientes, enderecosEntrega, notasFiscais, pagamentos, produtos, produtosPedido};
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::dal::pedido_dal::PedidoDal;
+use crate::models::cliente::Cliente;
+use crate::models::nfe::{DadosNotaFiscal, ItemNotaFiscal, NotaFiscal};
+use crate::models::pedido::{EnderecosEntrega, Pagamento, ProdutosPedido};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub struct NfeDal;
+
+impl NfeDal {
+    // Com id_cliente, o pedido precisa ser do cliente; senão responde 404 como as demais rotas de pedido
+    pub async fn get_by_pedido(pool: &DbPool, id_pedido: &str, id_cliente: Option<&str>) -> Result<Option<NotaFiscal>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_pedido_owned = id_pedido.to_string();
+        let id_cliente_owned = id_cliente.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            PedidoDal::find_pedido(&mut connection, &id_pedido_owned, id_cliente_owned.as_deref())?;
+
+            notasFiscais::table
+                .select(NotaFiscal::as_select())
+                .filter(notasFiscais::idPedido.eq(&id_pedido_owned))
+                .first::<NotaFiscal>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_dados(pool: &DbPool, id_pedido: &str) -> Result<DadosNotaFiscal, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id_pedido.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let pedido = PedidoDal::find_pedido(&mut connection, &id_owned, None)?;
+
+            let cliente = clientes::table
+                .select(Cliente::as_select())
+                .filter(clientes::id.eq(&pedido.id_cliente))
+                .first::<Cliente>(&mut connection)?;
+
+            let endereco = enderecosEntrega::table
+                .select(EnderecosEntrega::as_select())
+                .filter(enderecosEntrega::idPedido.eq(&id_owned))
+                .first::<EnderecosEntrega>(&mut connection)
+                .optional()?;
+
+            let pagamento = pagamentos::table
+                .select(Pagamento::as_select())
+                .filter(pagamentos::idPedido.eq(&id_owned))
+                .first::<Pagamento>(&mut connection)
+                .optional()?;
+
+            let itens = produtosPedido::table
+                .left_join(produtos::table)
+                .select((ProdutosPedido::as_select(), produtos::nome.nullable(), produtos::ncm.nullable()))
+                .filter(produtosPedido::idPedido.eq(&id_owned))
+                .order_by(produtosPedido::createdAt.asc())
+                .load::<(ProdutosPedido, Option<String>, Option<String>)>(&mut connection)?
+                .into_iter()
+                .map(|(produto, nome, ncm)| ItemNotaFiscal { produto, nome, ncm })
+                .collect();
+
+            Ok(DadosNotaFiscal { pedido, cliente, endereco, pagamento, itens })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Reserva o próximo número da série e grava a nota montada por `gerar` (chave de acesso e XML
+    // assinado) na mesma transação. Se o pedido já tiver nota, devolve a existente com `false`
+    pub async fn create<F>(
+        pool: &DbPool,
+        id_pedido: &str,
+        ambiente: i16,
+        serie: i32,
+        numero_inicial: i32,
+        gerar: F
+    ) -> Result<(NotaFiscal, bool), ApiError>
+    where
+        F: FnOnce(i32) -> Result<(String, String), ApiError> + Send + 'static,
+    {
+        let pool_clone = pool.clone();
+        let id_pedido_owned = id_pedido.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<(NotaFiscal, bool), ApiError, _>(|conn| {
+                // Serializa as emissões para que duas notas nunca disputem o mesmo número;
+                // leituras continuam liberadas
+                diesel::sql_query("LOCK TABLE \"notasFiscais\" IN EXCLUSIVE MODE").execute(conn)?;
+
+                let existente = notasFiscais::table
+                    .select(NotaFiscal::as_select())
+                    .filter(notasFiscais::idPedido.eq(&id_pedido_owned))
+                    .first::<NotaFiscal>(conn)
+                    .optional()?;
+
+                if let Some(nota) = existente {
+                    return Ok((nota, false));
+                }
+
+                let ultimo = notasFiscais::table
+                    .select(diesel::dsl::max(notasFiscais::numero))
+                    .filter(notasFiscais::ambiente.eq(ambiente))
+                    .filter(notasFiscais::serie.eq(serie))
+                    .first::<Option<i32>>(conn)?;
+
+                let numero = ultimo.map_or(numero_inicial, |ultimo| (ultimo + 1).max(numero_inicial));
+                let (chave_acesso, xml) = gerar(numero)?;
+
+                let nota = NotaFiscal {
+                    id: Uuid::new_v4().to_string(),
+                    id_pedido: id_pedido_owned.clone(),
+                    ambiente,
+                    serie,
+                    numero,
+                    chave_acesso,
+                    xml,
+                    created_at: chrono::Utc::now().naive_utc(),
+                };
+
+                diesel::insert_into(notasFiscais::table)
+                    .values(&nota)
+                    .get_result::<NotaFiscal>(conn)
+                    .map(|nota| (nota, true))
+                    .map_err(ApiError::from)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+}
@@ -0,0 +1,205 @@
+use crate::db::DbPool;
+use crate::schema::eventosOutbox;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::models::outbox::{EventoOutbox, StatusEvento, TipoEvento};
+use diesel::dsl::IntervalDsl;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+pub struct OutboxDal;
+
+const LIMITE_LISTAGEM: i64 = 100;
+
+impl OutboxDal {
+    // Roda na transação de quem chama: o evento só existe se a escrita de negócio for confirmada
+    pub(crate) fn registrar_on<T: Serialize>(
+        conn: &mut PgConnection,
+        tipo: TipoEvento,
+        id_agregado: &str,
+        payload: &T
+    ) -> Result<(), diesel::result::Error> {
+        let payload = serde_json::to_string(payload)
+            .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+
+        diesel::insert_into(eventosOutbox::table)
+            .values((
+                eventosOutbox::id.eq(Uuid::new_v4().to_string()),
+                eventosOutbox::tipo.eq(tipo.as_str()),
+                eventosOutbox::idAgregado.eq(id_agregado),
+                eventosOutbox::payload.eq(payload),
+                eventosOutbox::status.eq(StatusEvento::Pendente.as_str()),
+                eventosOutbox::proximaTentativa.eq(diesel::dsl::now),
+                // now() é fixo na transação; o relógio real mantém a ordem de vários eventos gravados juntos
+                eventosOutbox::createdAt.eq(diesel::dsl::sql::<diesel::sql_types::Timestamp>("clock_timestamp()::timestamp")),
+            ))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    // Reserva eventos vencidos empurrando a próxima tentativa para depois do lease: se o worker
+    // cair no meio da entrega o evento volta sozinho para a fila. SKIP LOCKED deixa várias
+    // instâncias do servidor dividirem a fila sem entregar o mesmo evento ao mesmo tempo
+    pub async fn reservar(pool: &DbPool, limite: i64, lease_segundos: i64) -> Result<Vec<EventoOutbox>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<Vec<EventoOutbox>, ApiError, _>(|conn| {
+                let ids = eventosOutbox::table
+                    .select(eventosOutbox::id)
+                    .filter(eventosOutbox::status.eq(StatusEvento::Pendente.as_str()))
+                    .filter(eventosOutbox::proximaTentativa.le(diesel::dsl::now))
+                    .order_by(eventosOutbox::createdAt.asc())
+                    .limit(limite)
+                    .for_update()
+                    .skip_locked()
+                    .load::<String>(conn)?;
+
+                if ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let mut eventos = diesel::update(eventosOutbox::table)
+                    .filter(eventosOutbox::id.eq_any(&ids))
+                    .set(eventosOutbox::proximaTentativa.eq(diesel::dsl::now + lease_segundos.seconds()))
+                    .get_results::<EventoOutbox>(conn)?;
+
+                eventos.sort_by_key(|evento| evento.created_at);
+                Ok(eventos)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn concluir(pool: &DbPool, id: &str, entregue_a: Vec<String>) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            diesel::update(eventosOutbox::table)
+                .filter(eventosOutbox::id.eq(&id_owned))
+                .set((
+                    eventosOutbox::status.eq(StatusEvento::Entregue.as_str()),
+                    eventosOutbox::tentativas.eq(eventosOutbox::tentativas + 1),
+                    eventosOutbox::entregueA.eq(&entregue_a),
+                    eventosOutbox::ultimoErro.eq(None::<String>),
+                    eventosOutbox::processadoEm.eq(diesel::dsl::now.nullable()),
+                ))
+                .execute(&mut connection)?;
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Sem espera o evento esgotou as tentativas e fica como falho até ser reprocessado
+    pub async fn registrar_falha(
+        pool: &DbPool,
+        id: &str,
+        entregue_a: Vec<String>,
+        erro: String,
+        espera_segundos: Option<i64>
+    ) -> Result<(), ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let query = diesel::update(eventosOutbox::table)
+                .filter(eventosOutbox::id.eq(&id_owned));
+            let campos = (
+                eventosOutbox::tentativas.eq(eventosOutbox::tentativas + 1),
+                eventosOutbox::entregueA.eq(&entregue_a),
+                eventosOutbox::ultimoErro.eq(Some(&erro)),
+            );
+
+            match espera_segundos {
+                Some(espera) => query
+                    .set((
+                        campos,
+                        eventosOutbox::proximaTentativa.eq(diesel::dsl::now + espera.seconds()),
+                    ))
+                    .execute(&mut connection)?,
+                None => query
+                    .set((
+                        campos,
+                        eventosOutbox::status.eq(StatusEvento::Falhou.as_str()),
+                        eventosOutbox::processadoEm.eq(diesel::dsl::now.nullable()),
+                    ))
+                    .execute(&mut connection)?,
+            };
+
+            Ok(())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all(pool: &DbPool, status: Option<StatusEvento>) -> Result<Vec<EventoOutbox>, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let mut query = eventosOutbox::table
+                .select(EventoOutbox::as_select())
+                .order_by(eventosOutbox::createdAt.desc())
+                .limit(LIMITE_LISTAGEM)
+                .into_boxed();
+
+            if let Some(status) = status {
+                query = query.filter(eventosOutbox::status.eq(status.as_str()));
+            }
+
+            query
+                .load::<EventoOutbox>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Devolve um evento falho à fila com as tentativas zeradas; os destinos que já receberam
+    // o evento continuam de fora
+    pub async fn reprocessar(pool: &DbPool, id: &str) -> Result<EventoOutbox, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let existe = eventosOutbox::table
+                .select(eventosOutbox::id)
+                .filter(eventosOutbox::id.eq(&id_owned))
+                .first::<String>(&mut connection)
+                .optional()?;
+
+            if existe.is_none() {
+                return Err(AppMessage::new("Evento não encontrado", 404).into());
+            }
+
+            diesel::update(eventosOutbox::table)
+                .filter(eventosOutbox::id.eq(&id_owned))
+                .filter(eventosOutbox::status.eq(StatusEvento::Falhou.as_str()))
+                .set((
+                    eventosOutbox::status.eq(StatusEvento::Pendente.as_str()),
+                    eventosOutbox::tentativas.eq(0),
+                    eventosOutbox::proximaTentativa.eq(diesel::dsl::now),
+                    eventosOutbox::processadoEm.eq(None::<chrono::NaiveDateTime>),
+                ))
+                .get_result::<EventoOutbox>(&mut connection)
+                .optional()?
+                .ok_or_else(|| AppMessage::new("Só eventos com falha podem ser reprocessados", 409).into())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+}
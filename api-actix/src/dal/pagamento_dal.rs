@@ -0,0 +1,162 @@
+use crate::db::DbPool;
+use crate::schema::{eventosPagamento, pagamentos};
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::utils::money;
+use crate::dal::cupom_dal::CupomDal;
+use crate::dal::outbox_dal::OutboxDal;
+use crate::dal::pedido_dal::PedidoDal;
+use crate::models::evento_pagamento::{EventoPagamentoPayload, ResultadoEventoPagamento, TipoEventoPagamento};
+use crate::models::outbox::TipoEvento;
+use crate::models::pedido::{Pagamento, StatusPagamento, StatusPedido};
+use diesel::prelude::*;
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub struct PagamentoDal;
+
+const ALTERADO_POR_WEBHOOK: &str = "webhook";
+
+impl PagamentoDal {
+    // O registro do evento e seus efeitos ficam na mesma transação: se algo falhar o evento
+    // não é gravado e o reenvio do provedor é processado normalmente
+    pub async fn processar_evento(
+        pool: &DbPool,
+        evento: EventoPagamentoPayload,
+        tipo: TipoEventoPagamento,
+        payload: String
+    ) -> Result<ResultadoEventoPagamento, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<ResultadoEventoPagamento, ApiError, _>(|conn| {
+                let pedido = PedidoDal::find_pedido(conn, &evento.id_pedido, None)?;
+
+                // Trava o pagamento para serializar eventos concorrentes do mesmo pedido
+                let pagamento = pagamentos::table
+                    .select(Pagamento::as_select())
+                    .filter(pagamentos::idPedido.eq(&evento.id_pedido))
+                    .for_update()
+                    .first::<Pagamento>(conn)
+                    .optional()?
+                    .ok_or_else(|| AppMessage::new("Pagamento do pedido não encontrado", 404))?;
+
+                let inseridos = diesel::insert_into(eventosPagamento::table)
+                    .values((
+                        eventosPagamento::id.eq(Uuid::new_v4().to_string()),
+                        eventosPagamento::idEvento.eq(&evento.id),
+                        eventosPagamento::idPedido.eq(&evento.id_pedido),
+                        eventosPagamento::tipo.eq(tipo.as_str()),
+                        eventosPagamento::payload.eq(&payload),
+                        eventosPagamento::createdAt.eq(diesel::dsl::now),
+                    ))
+                    .on_conflict(eventosPagamento::idEvento)
+                    .do_nothing()
+                    .execute(conn)?;
+
+                if inseridos == 0 {
+                    return Ok(ResultadoEventoPagamento {
+                        id_evento: evento.id,
+                        duplicado: true,
+                        status_pedido: pedido.status,
+                        status_pagamento: pagamento.status,
+                    });
+                }
+
+                if let (Some(tid_evento), Some(tid_pagamento)) = (&evento.tid, &pagamento.tid)
+                    && tid_evento != tid_pagamento
+                {
+                    return Err(AppMessage::new("TID do evento não confere com o do pagamento", 422).into());
+                }
+
+                let status_pagamento = StatusPagamento::from_str(&pagamento.status)?;
+                let status_pedido = StatusPedido::from_str(&pedido.status)?;
+
+                // Estorno parcial vem de uma devolução de parte dos itens, que já cuida do status do pedido;
+                // o restante do valor continua pago
+                let estorno_parcial = tipo == TipoEventoPagamento::Estornado
+                    && pagamento.valor_estornado > money::zero()
+                    && pagamento.valor_estornado < pagamento.valor_total;
+
+                let status_pagamento_novo = Self::status_pagamento_apos(tipo, status_pagamento)
+                    .map(|status| if estorno_parcial { StatusPagamento::Aprovado } else { status })
+                    .ok_or_else(|| AppMessage::new(
+                        &format!("Evento {} incompatível com o status atual do pagamento", tipo.as_str()),
+                        409
+                    ))?;
+
+                let pagamento_atualizado = diesel::update(pagamentos::table)
+                    .filter(pagamentos::id.eq(&pagamento.id))
+                    .set((
+                        pagamentos::status.eq(status_pagamento_novo.as_str()),
+                        pagamentos::tid.eq(evento.tid.as_ref().or(pagamento.tid.as_ref())),
+                        pagamentos::updatedAt.eq(diesel::dsl::now),
+                    ))
+                    .get_result::<Pagamento>(conn)?;
+
+                if status_pagamento_novo == StatusPagamento::Aprovado && status_pagamento != StatusPagamento::Aprovado && !estorno_parcial {
+                    OutboxDal::registrar_on(conn, TipoEvento::PagamentoConfirmado, &evento.id_pedido, &pagamento_atualizado)?;
+                }
+
+                // Pedido cancelado que recebe aprovação tardia só tem o estorno solicitado
+                let mut status_pedido_final = status_pedido;
+                if status_pagamento_novo != StatusPagamento::EstornoSolicitado
+                    && !estorno_parcial
+                    && let Some(status_pedido_novo) = Self::status_pedido_apos(tipo, status_pedido)
+                {
+                    let motivo = match &evento.motivo {
+                        Some(motivo) => format!("{}: {}", tipo.descricao(), motivo),
+                        None => tipo.descricao().to_string(),
+                    };
+
+                    PedidoDal::update_status_on(conn, &evento.id_pedido, status_pedido, status_pedido_novo)?;
+                    let historico = PedidoDal::registrar_status(conn, &evento.id_pedido, Some(status_pedido), status_pedido_novo, ALTERADO_POR_WEBHOOK, Some(&motivo))?;
+                    OutboxDal::registrar_on(conn, TipoEvento::StatusPedidoAlterado, &evento.id_pedido, &historico)?;
+
+                    if status_pedido_novo == StatusPedido::Cancelado {
+                        PedidoDal::restaurar_estoque_on(conn, &evento.id_pedido)?;
+                        CupomDal::liberar_utilizacao_on(conn, &evento.id_pedido)?;
+                    }
+
+                    status_pedido_final = status_pedido_novo;
+                }
+
+                Ok(ResultadoEventoPagamento {
+                    id_evento: evento.id,
+                    duplicado: false,
+                    status_pedido: status_pedido_final.as_str().to_string(),
+                    status_pagamento: status_pagamento_novo.as_str().to_string(),
+                })
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    fn status_pagamento_apos(tipo: TipoEventoPagamento, atual: StatusPagamento) -> Option<StatusPagamento> {
+        use StatusPagamento::*;
+
+        match (tipo, atual) {
+            (TipoEventoPagamento::Aprovado, Pendente | Aprovado) => Some(Aprovado),
+            (TipoEventoPagamento::Aprovado, Cancelado | EstornoSolicitado) => Some(EstornoSolicitado),
+            (TipoEventoPagamento::Recusado, Pendente | Recusado) => Some(Recusado),
+            (TipoEventoPagamento::Estornado, Aprovado | EstornoSolicitado | Estornado) => Some(Estornado),
+            (TipoEventoPagamento::Chargeback, Aprovado | EstornoSolicitado | Estornado | Chargeback) => Some(Chargeback),
+            _ => None,
+        }
+    }
+
+    // Estorno e chargeback cancelam o pedido ainda não enviado; depois do envio ele passa a devolvido
+    fn status_pedido_apos(tipo: TipoEventoPagamento, atual: StatusPedido) -> Option<StatusPedido> {
+        use StatusPedido::*;
+
+        match (tipo, atual) {
+            (TipoEventoPagamento::Aprovado, Pendente) => Some(Pago),
+            (TipoEventoPagamento::Recusado, Pendente) => Some(Cancelado),
+            (TipoEventoPagamento::Estornado | TipoEventoPagamento::Chargeback, Pendente | Pago | Faturado) => Some(Cancelado),
+            (TipoEventoPagamento::Estornado | TipoEventoPagamento::Chargeback, Enviado | Entregue) => Some(Devolvido),
+            _ => None,
+        }
+    }
+}
@@ -1,73 +1,136 @@
 use crate::db::DbPool;
-use crate::schema::{enderecosEntrega, pagamentos, pedidos, produtosPedido};
+use crate::schema::{cupons, cuponsUtilizados, enderecosEntrega, historicoStatusPedido, pagamentos, pedidos, produtos, produtosPedido};
 use crate::utils::app_message::{ApiError, AppMessage};
+use crate::utils::money;
 use bigdecimal::BigDecimal;
 use diesel::prelude::*;
 use serde_json::Value;
-use std::str::FromStr;
 use uuid::Uuid;
-use crate::models::pedido::{EnderecosEntrega, Pagamento, Pedido, PedidoPayload, ProdutosPedido};
+use crate::dal::carrinho_dal::CarrinhoDal;
+use crate::dal::cupom_dal::CupomDal;
+use crate::providers::pagamento::{PaymentProvider, ResultadoPagamento, SolicitacaoPagamento, StatusTransacao};
+use crate::dal::idempotencia_dal::IdempotenciaDal;
+use crate::dal::outbox_dal::OutboxDal;
+use crate::dal::produto_dal::ProdutoDal;
+use crate::models::outbox::TipoEvento;
+use crate::models::pedido::{ComprovantePedido, EnderecosEntrega, FormaPagamento, HistoricoStatusPedido, Pagamento, Pedido, PedidoDraft, PedidoFiltro, ProdutosPedido, StatusPagamento, StatusPedido};
 
 pub struct PedidoDal;
 
+// Pedido gravado com o estoque reservado e o pagamento pendente, à espera da resposta do provedor
+struct PedidoReservado {
+    pedido: Pedido,
+    endereco: EnderecosEntrega,
+    pagamento: Pagamento,
+    produtos: Vec<ProdutosPedido>,
+    forma_pagamento: FormaPagamento,
+    skus_carrinho: Option<Vec<String>>,
+}
+
 impl PedidoDal {
-    pub async fn create(pool: &DbPool, payload: PedidoPayload) -> Result<Value, ApiError> {
+    // O provedor é chamado fora de transação, entre a gravação do pedido e a do resultado: uma cobrança
+    // nunca some num rollback e o id do pedido, já gravado, é a referência idempotente da cobrança
+    pub async fn create(
+        pool: &DbPool,
+        draft: PedidoDraft,
+        provider: Box<dyn PaymentProvider>,
+        chave_idempotencia: Option<String>
+    ) -> Result<Value, ApiError> {
+        let reservado = Self::gravar_pendente(pool, draft).await?;
+
+        let provedor = provider.nome();
+        let id_pedido = reservado.pedido.id.clone();
+        let id_cliente = reservado.pedido.id_cliente.clone();
+        let forma_pagamento = reservado.forma_pagamento;
+        let numero_parcelas = reservado.pagamento.numero_parcelas;
+        let valor_total = reservado.pagamento.valor_total.clone();
+
+        let resultado = tokio::task::spawn_blocking(move || {
+            provider.processar(&SolicitacaoPagamento {
+                id_pedido: &id_pedido,
+                id_cliente: &id_cliente,
+                forma_pagamento,
+                numero_parcelas,
+                valor_total: &valor_total,
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?;
+
+        Self::registrar_pagamento(pool, reservado, resultado, provedor, chave_idempotencia).await
+    }
+
+    // Reserva o estoque e grava o pedido com o pagamento pendente, ainda sem resposta do provedor
+    async fn gravar_pendente(pool: &DbPool, draft: PedidoDraft) -> Result<PedidoReservado, ApiError> {
         let pool_clone = pool.clone();
-        let payload_owned = payload;
 
         tokio::task::spawn_blocking(move || {
             let mut connection = pool_clone.get()
                 .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
 
-            let PedidoPayload {
-                endereco_entrega,
-                pagamento,
-                produtos,
+            let PedidoDraft {
                 id_cliente,
                 valor_bruto,
                 valor_desconto,
                 valor_frete,
                 valor_liquido,
+                modalidade_frete,
                 data_entrega,
-            } = payload_owned;
-
-            let valor_bruto_bd = BigDecimal::from_str(&valor_bruto.to_string())
-                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-            let valor_desconto_bd = BigDecimal::from_str(&valor_desconto.to_string())
-                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-            let valor_frete_bd = BigDecimal::from_str(&valor_frete.to_string())
-                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+                endereco_entrega,
+                pagamento,
+                itens,
+                cupom,
+                origem_carrinho,
+            } = draft;
 
-            let valor_liquido_bd = BigDecimal::from_str(&valor_liquido.to_string())
-                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+            connection
+                .transaction::<PedidoReservado, ApiError, _>(|conn| {
+                    let id_pedido = Uuid::new_v4().to_string();
 
-            let data_entrega_naive = chrono::NaiveDateTime::parse_from_str(&data_entrega, "%Y-%m-%dT%H:%M:%SZ")
-                .or_else(|_| chrono::NaiveDateTime::parse_from_str(&data_entrega, "%Y-%m-%dT%H:%M:%S%.fZ"))
-                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+                    // Reserva em ordem de SKU para que pedidos concorrentes travem as linhas na mesma ordem
+                    let mut reservas: Vec<(&String, i32)> = itens.iter()
+                        .map(|item| (&item.sku_produto, item.quantidade))
+                        .collect();
+                    reservas.sort_by(|a, b| a.0.cmp(b.0));
 
-            let data_entrega_date = data_entrega_naive.date();
+                    let mut conflitos = Vec::new();
+                    for (sku, quantidade) in reservas {
+                        if let Some(conflito) = ProdutoDal::reservar_estoque_on(conn, sku, quantidade)? {
+                            conflitos.push(conflito);
+                        }
+                    }
 
-            connection
-                .transaction(|conn| {
-                    let id_pedido = Uuid::new_v4().to_string();
+                    if !conflitos.is_empty() {
+                        let skus = conflitos.iter()
+                            .map(|c| c.sku_produto.clone())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        let data = serde_json::to_value(&conflitos)
+                            .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+                        return Err(AppMessage::with_data(&format!("Estoque insuficiente para os produtos: {}", skus), 409, data).into());
+                    }
 
                     let pedido = diesel::insert_into(pedidos::table)
                         .values((
                             pedidos::id.eq(&id_pedido),
                             pedidos::idCliente.eq(&id_cliente),
-                            pedidos::valorBruto.eq(valor_bruto_bd),
-                            pedidos::valorDesconto.eq(valor_desconto_bd),
-                            pedidos::valorFrete.eq(valor_frete_bd),
-                            pedidos::valorLiquido.eq(valor_liquido_bd),
-                            pedidos::dataEntrega.eq(data_entrega_date),
-                            pedidos::status.eq("P"),
+                            pedidos::valorBruto.eq(&valor_bruto),
+                            pedidos::valorDesconto.eq(&valor_desconto),
+                            pedidos::valorFrete.eq(&valor_frete),
+                            pedidos::valorLiquido.eq(&valor_liquido),
+                            pedidos::dataEntrega.eq(data_entrega),
+                            pedidos::modalidadeFrete.eq(modalidade_frete.as_str()),
+                            pedidos::status.eq(StatusPedido::Pendente.as_str()),
                             pedidos::createdAt.eq(diesel::dsl::now),
                             pedidos::updatedAt.eq(diesel::dsl::now),
                         ))
                         .get_result::<Pedido>(conn)?;
 
+                    Self::registrar_status(conn, &id_pedido, None, StatusPedido::Pendente, &id_cliente, Some("Pedido criado"))?;
+
+                    if let Some(cupom) = &cupom {
+                        CupomDal::registrar_utilizacao_on(conn, cupom, &id_cliente, &id_pedido)?;
+                    }
+
                     let endereco = diesel::insert_into(enderecosEntrega::table)
                         .values((
                             enderecosEntrega::id.eq(Uuid::new_v4().to_string()),
@@ -76,8 +139,9 @@ impl PedidoDal {
                             enderecosEntrega::cep.eq(&endereco_entrega.cep),
                             enderecosEntrega::logradouro.eq(&endereco_entrega.logradouro),
                             enderecosEntrega::numero.eq(&endereco_entrega.numero),
-                            enderecosEntrega::complemento.eq(endereco_entrega.complemento),
+                            enderecosEntrega::complemento.eq(&endereco_entrega.complemento),
                             enderecosEntrega::bairro.eq(&endereco_entrega.bairro),
+                            enderecosEntrega::cidade.eq(&endereco_entrega.cidade),
                             enderecosEntrega::codigoIbgeCidade.eq(&endereco_entrega.codigo_ibge_cidade),
                             enderecosEntrega::codigoIbgeUF.eq(&endereco_entrega.codigo_ibge_uf),
                             enderecosEntrega::createdAt.eq(diesel::dsl::now),
@@ -85,63 +149,36 @@ impl PedidoDal {
                         ))
                         .get_result::<EnderecosEntrega>(conn)?;
 
-                    let pagamento_valor_total =
-                        BigDecimal::from_str(&pagamento.valor_total.to_string())
-                            .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                    let pagamento_valor_parcela =
-                        BigDecimal::from_str(&pagamento.valor_parcela.to_string())
-                            .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
                     let pagamento_record = diesel::insert_into(pagamentos::table)
                         .values((
                             pagamentos::id.eq(Uuid::new_v4().to_string()),
                             pagamentos::idPedido.eq(&id_pedido),
-                            pagamentos::formaPagamento.eq(&pagamento.forma_pagamento),
-                            pagamentos::numeroParcelas.eq(pagamento.numero_parcelas as i16),
-                            pagamentos::valorTotal.eq(pagamento_valor_total),
-                            pagamentos::valorParcela.eq(pagamento_valor_parcela),
+                            pagamentos::formaPagamento.eq(pagamento.forma_pagamento.as_str()),
+                            pagamentos::numeroParcelas.eq(pagamento.numero_parcelas),
+                            pagamentos::valorTotal.eq(&pagamento.valor_total),
+                            pagamentos::valorParcela.eq(&pagamento.valor_parcela),
+                            pagamentos::status.eq(StatusPagamento::Pendente.as_str()),
                             pagamentos::createdAt.eq(diesel::dsl::now),
                             pagamentos::updatedAt.eq(diesel::dsl::now),
                         ))
                         .get_result::<Pagamento>(conn)?;
 
-                    let mut produtos_inseridos = Vec::new();
-                    for produto in produtos {
-                        let produto_valor_unitario =
-                            BigDecimal::from_str(&produto.valor_unitario.to_string())
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                        let produto_valor_bruto =
-                            BigDecimal::from_str(&produto.valor_bruto.to_string())
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                        let produto_valor_desconto =
-                            BigDecimal::from_str(&produto.valor_desconto.to_string())
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                        let produto_valor_liquido =
-                            BigDecimal::from_str(&produto.valor_liquido.to_string())
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                        let produto_valor_frete =
-                            BigDecimal::from_str(&produto.valor_frete.to_string())
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                        let quantidade_bd = BigDecimal::from_str(&produto.quantidade.to_string())
-                            .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
+                    let mut produtos_inseridos = Vec::with_capacity(itens.len());
+                    for item in &itens {
                         let produto_pedido = diesel::insert_into(produtosPedido::table)
                             .values((
                                 produtosPedido::id.eq(Uuid::new_v4().to_string()),
                                 produtosPedido::idPedido.eq(&id_pedido),
-                                produtosPedido::skuProduto.eq(&produto.sku_produto),
-                                produtosPedido::quantidade.eq(quantidade_bd),
-                                produtosPedido::valorUnitario.eq(produto_valor_unitario),
-                                produtosPedido::valorBruto.eq(produto_valor_bruto),
-                                produtosPedido::valorDesconto.eq(produto_valor_desconto),
-                                produtosPedido::valorLiquido.eq(produto_valor_liquido),
-                                produtosPedido::valorFrete.eq(produto_valor_frete),
+                                produtosPedido::skuProduto.eq(&item.sku_produto),
+                                produtosPedido::quantidade.eq(BigDecimal::from(item.quantidade)),
+                                produtosPedido::valorUnitario.eq(&item.valor_unitario),
+                                produtosPedido::valorBruto.eq(&item.valor_bruto),
+                                produtosPedido::valorDesconto.eq(&item.valor_desconto),
+                                produtosPedido::valorLiquido.eq(&item.valor_liquido),
+                                produtosPedido::valorFrete.eq(&item.valor_frete),
+                                produtosPedido::valorUnitarioOriginal.eq(&item.valor_unitario_original),
+                                produtosPedido::pctOferta.eq(&item.pct_oferta),
+                                produtosPedido::promocao.eq(&item.promocao),
                                 produtosPedido::createdAt.eq(diesel::dsl::now),
                                 produtosPedido::updatedAt.eq(diesel::dsl::now),
                             ))
@@ -150,33 +187,493 @@ impl PedidoDal {
                         produtos_inseridos.push(produto_pedido);
                     }
 
-                    let mut pedido_json = serde_json::to_value(pedido)
-                        .map_err(|_| diesel::result::Error::RollbackTransaction)?;
-
-                    if let Value::Object(ref mut pedido_obj) = pedido_json {
-                        pedido_obj.insert(
-                            "enderecosEntrega".to_string(),
-                            serde_json::to_value(endereco)
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?,
-                        );
-
-                        pedido_obj.insert(
-                            "pagamentos".to_string(),
-                            serde_json::to_value(pagamento_record)
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?,
-                        );
-
-                        pedido_obj.insert(
-                            "produtos".to_string(),
-                            serde_json::to_value(produtos_inseridos)
-                                .map_err(|_| diesel::result::Error::RollbackTransaction)?,
-                        );
+                    Ok(PedidoReservado {
+                        pedido,
+                        endereco,
+                        pagamento: pagamento_record,
+                        produtos: produtos_inseridos,
+                        forma_pagamento: pagamento.forma_pagamento,
+                        skus_carrinho: origem_carrinho
+                            .then(|| itens.iter().map(|item| item.sku_produto.clone()).collect()),
+                    })
+                })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    // Grava a resposta do provedor. Recusa ou falha cancela o pedido devolvendo estoque e cupom; se a
+    // falha escondia uma cobrança, a aprovação tardia pelo webhook vira pedido de estorno
+    async fn registrar_pagamento(
+        pool: &DbPool,
+        reservado: PedidoReservado,
+        resultado: Result<ResultadoPagamento, AppMessage>,
+        provedor: &'static str,
+        chave_idempotencia: Option<String>
+    ) -> Result<Value, ApiError> {
+        let pool_clone = pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let PedidoReservado { pedido, endereco, pagamento, produtos, skus_carrinho, .. } = reservado;
+
+            connection
+                .transaction::<Result<Value, AppMessage>, ApiError, _>(|conn| {
+                    // Trava o pagamento como o webhook faz: um evento que chegue antes da resposta do
+                    // provedor prevalece e a resposta só completa os dados da cobrança
+                    let pagamento_atual = pagamentos::table
+                        .select(Pagamento::as_select())
+                        .filter(pagamentos::id.eq(&pagamento.id))
+                        .for_update()
+                        .first::<Pagamento>(conn)?;
+                    let pedido_atual = Self::find_pedido(conn, &pedido.id, None)?;
+                    let pendente = pagamento_atual.status == StatusPagamento::Pendente.as_str()
+                        && pedido_atual.status == StatusPedido::Pendente.as_str();
+
+                    let resultado = match resultado {
+                        Ok(resultado) if resultado.status != StatusTransacao::Recusada => resultado,
+                        recusa => {
+                            let (status_pagamento, erro) = match recusa {
+                                Ok(resultado) => {
+                                    let motivo = resultado.motivo.unwrap_or_else(|| "transação não autorizada".to_string());
+                                    (StatusPagamento::Recusado, AppMessage::new(&format!("Pagamento recusado: {}", motivo), 402))
+                                }
+                                Err(erro) => (StatusPagamento::Cancelado, erro),
+                            };
+
+                            if pendente {
+                                Self::update_status_on(conn, &pedido.id, StatusPedido::Pendente, StatusPedido::Cancelado)?;
+                                Self::registrar_status(conn, &pedido.id, Some(StatusPedido::Pendente), StatusPedido::Cancelado, provedor, Some(&erro.message))?;
+                                Self::restaurar_estoque_on(conn, &pedido.id)?;
+                                CupomDal::liberar_utilizacao_on(conn, &pedido.id)?;
+
+                                diesel::update(pagamentos::table)
+                                    .filter(pagamentos::id.eq(&pagamento.id))
+                                    .set((
+                                        pagamentos::status.eq(status_pagamento.as_str()),
+                                        pagamentos::updatedAt.eq(diesel::dsl::now),
+                                    ))
+                                    .execute(conn)?;
+                            }
+
+                            // O cancelamento é gravado mesmo com a resposta de erro
+                            return Ok(Err(erro));
+                        }
+                    };
+
+                    let aprovado = pendente && resultado.status == StatusTransacao::Aprovada;
+                    let status_pagamento = if aprovado { StatusPagamento::Aprovado.as_str() } else { pagamento_atual.status.as_str() };
+
+                    let pagamento_record = diesel::update(pagamentos::table)
+                        .filter(pagamentos::id.eq(&pagamento.id))
+                        .set((
+                            pagamentos::boleto.eq(&resultado.boleto),
+                            pagamentos::pix.eq(&resultado.pix),
+                            pagamentos::tid.eq(pagamento_atual.tid.as_ref().or(resultado.tid.as_ref())),
+                            pagamentos::status.eq(status_pagamento),
+                            pagamentos::updatedAt.eq(diesel::dsl::now),
+                        ))
+                        .get_result::<Pagamento>(conn)?;
+
+                    let mut pedido = pedido_atual;
+                    if aprovado {
+                        pedido = Self::update_status_on(conn, &pedido.id, StatusPedido::Pendente, StatusPedido::Pago)?;
+                        Self::registrar_status(conn, &pedido.id, Some(StatusPedido::Pendente), StatusPedido::Pago, provedor, Some("Pagamento aprovado"))?;
                     }
 
-                    Ok::<Value, diesel::result::Error>(pedido_json)
-                })
+                    if let Some(skus) = &skus_carrinho {
+                        let skus: Vec<&str> = skus.iter().map(String::as_str).collect();
+                        CarrinhoDal::remover_itens_on(conn, &pedido.id_cliente, &skus)?;
+                    }
+
+                    let id_pedido = pedido.id.clone();
+                    let id_cliente = pedido.id_cliente.clone();
+                    let pedido_json = Self::build_pedido_json(pedido, Some(endereco), Some(pagamento_record.clone()), produtos)?;
+
+                    // A transição para pago na criação não gera pedido.status_alterado: o pedido.criado
+                    // já sai com o status final e o pagamento.confirmado vem logo depois
+                    OutboxDal::registrar_on(conn, TipoEvento::PedidoCriado, &id_pedido, &pedido_json)?;
+                    if aprovado {
+                        OutboxDal::registrar_on(conn, TipoEvento::PagamentoConfirmado, &id_pedido, &pagamento_record)?;
+                    }
+
+                    // A resposta é gravada junto com o resultado para que um retry nunca veja um sem o outro
+                    if let Some(chave) = &chave_idempotencia {
+                        IdempotenciaDal::concluir_on(conn, &id_cliente, chave, 201, &pedido_json)?;
+                    }
+
+                    Ok(Ok(pedido_json))
+                })?
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_all_by_cliente(pool: &DbPool, id_cliente: &str, filtro: PedidoFiltro, page: u32, mut page_size: u32) -> Result<Vec<Pedido>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            if page_size > 100 {
+                page_size = 100;
+            }
+            let offset = (page - 1) * page_size;
+
+            let mut query = pedidos::table
+                .select(Pedido::as_select())
+                .filter(pedidos::idCliente.eq(id_cliente_owned))
+                .into_boxed();
+
+            if let Some(status) = filtro.status {
+                query = query.filter(pedidos::status.eq(status));
+            }
+
+            if let Some(data_inicio) = filtro.data_inicio {
+                query = query.filter(pedidos::createdAt.ge(data_inicio.and_hms_opt(0, 0, 0).unwrap()));
+            }
+
+            if let Some(data_fim) = filtro.data_fim {
+                let dia_seguinte = data_fim.succ_opt().unwrap_or(data_fim);
+                query = query.filter(pedidos::createdAt.lt(dia_seguinte.and_hms_opt(0, 0, 0).unwrap()));
+            }
+
+            query
+                .order_by(pedidos::createdAt.desc())
+                .limit(page_size as i64)
+                .offset(offset as i64)
+                .load::<Pedido>(&mut connection)
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_by_id(pool: &DbPool, id_cliente: &str, id: &str) -> Result<Value, ApiError> {
+        let pool_clone = pool.clone();
+        let id_cliente_owned = id_cliente.to_string();
+        let id_owned = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            let pedido = Self::find_pedido(&mut connection, &id_owned, Some(&id_cliente_owned))?;
+
+            let endereco = enderecosEntrega::table
+                .select(EnderecosEntrega::as_select())
+                .filter(enderecosEntrega::idPedido.eq(&id_owned))
+                .first::<EnderecosEntrega>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)?;
+
+            let pagamento = pagamentos::table
+                .select(Pagamento::as_select())
+                .filter(pagamentos::idPedido.eq(&id_owned))
+                .first::<Pagamento>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)?;
+
+            let produtos = produtosPedido::table
+                .select(ProdutosPedido::as_select())
+                .filter(produtosPedido::idPedido.eq(&id_owned))
+                .order_by(produtosPedido::createdAt.asc())
+                .load::<ProdutosPedido>(&mut connection)
+                .map_err(ApiError::from)?;
+
+            Self::build_pedido_json(pedido, endereco, pagamento, produtos)
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("JSON serialization error: {}", e), 500)))
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_comprovante(pool: &DbPool, id: &str, id_cliente: Option<&str>) -> Result<ComprovantePedido, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let id_cliente_owned = id_cliente.map(|c| c.to_string());
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            Self::comprovante_on(&mut connection, &id_owned, id_cliente_owned.as_deref())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_pedido_by_id(pool: &DbPool, id: &str, id_cliente: Option<&str>) -> Result<Pedido, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let id_cliente_owned = id_cliente.map(|c| c.to_string());
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            Self::find_pedido(&mut connection, &id_owned, id_cliente_owned.as_deref())
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn update_status(
+        pool: &DbPool,
+        id: &str,
+        status_atual: StatusPedido,
+        status_novo: StatusPedido,
+        alterado_por: &str,
+        motivo: Option<String>
+    ) -> Result<Pedido, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let alterado_por_owned = alterado_por.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<Pedido, ApiError, _>(|conn| {
+                let pedido = Self::update_status_on(conn, &id_owned, status_atual, status_novo)?;
+                let historico = Self::registrar_status(conn, &id_owned, Some(status_atual), status_novo, &alterado_por_owned, motivo.as_deref())?;
+                OutboxDal::registrar_on(conn, TipoEvento::StatusPedidoAlterado, &id_owned, &historico)?;
+                Ok(pedido)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn cancelar(
+        pool: &DbPool,
+        id: &str,
+        status_atual: StatusPedido,
+        alterado_por: &str,
+        motivo: Option<String>
+    ) -> Result<Pedido, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let alterado_por_owned = alterado_por.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            connection.transaction::<Pedido, ApiError, _>(|conn| {
+                let pedido = Self::update_status_on(conn, &id_owned, status_atual, StatusPedido::Cancelado)?;
+                let historico = Self::registrar_status(conn, &id_owned, Some(status_atual), StatusPedido::Cancelado, &alterado_por_owned, motivo.as_deref())?;
+                OutboxDal::registrar_on(conn, TipoEvento::StatusPedidoAlterado, &id_owned, &historico)?;
+
+                Self::restaurar_estoque_on(conn, &id_owned)?;
+                CupomDal::liberar_utilizacao_on(conn, &id_owned)?;
+
+                // Pagamento só foi capturado se o pedido já estava pago ou faturado
+                let status_pagamento = if matches!(status_atual, StatusPedido::Pago | StatusPedido::Faturado) {
+                    StatusPagamento::EstornoSolicitado
+                } else {
+                    StatusPagamento::Cancelado
+                };
+
+                diesel::update(pagamentos::table)
+                    .filter(pagamentos::idPedido.eq(&id_owned))
+                    .set((
+                        pagamentos::status.eq(status_pagamento.as_str()),
+                        pagamentos::updatedAt.eq(diesel::dsl::now),
+                    ))
+                    .execute(conn)?;
+
+                Ok(pedido)
+            })
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub async fn get_historico(pool: &DbPool, id: &str, id_cliente: &str) -> Result<Vec<HistoricoStatusPedido>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            Self::find_pedido(&mut connection, &id_owned, Some(&id_cliente_owned))?;
+
+            historicoStatusPedido::table
+                .select(HistoricoStatusPedido::as_select())
+                .filter(historicoStatusPedido::idPedido.eq(&id_owned))
+                .order_by(historicoStatusPedido::createdAt.asc())
+                .load::<HistoricoStatusPedido>(&mut connection)
                 .map_err(ApiError::from)
         }).await
             .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
     }
-}
\ No newline at end of file
+
+    pub async fn get_pagamento(pool: &DbPool, id: &str, id_cliente: &str) -> Result<Option<Pagamento>, ApiError> {
+        let pool_clone = pool.clone();
+        let id_owned = id.to_string();
+        let id_cliente_owned = id_cliente.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = pool_clone.get()
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+
+            Self::find_pedido(&mut connection, &id_owned, Some(&id_cliente_owned))?;
+
+            pagamentos::table
+                .select(Pagamento::as_select())
+                .filter(pagamentos::idPedido.eq(&id_owned))
+                .first::<Pagamento>(&mut connection)
+                .optional()
+                .map_err(ApiError::from)
+        }).await
+            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+    }
+
+    pub(crate) fn find_pedido(conn: &mut PgConnection, id: &str, id_cliente: Option<&str>) -> Result<Pedido, ApiError> {
+        let mut query = pedidos::table
+            .select(Pedido::as_select())
+            .filter(pedidos::id.eq(id.to_string()))
+            .into_boxed();
+
+        if let Some(id_cliente) = id_cliente {
+            query = query.filter(pedidos::idCliente.eq(id_cliente.to_string()));
+        }
+
+        query
+            .first::<Pedido>(conn)
+            .optional()
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::from(AppMessage::new("Pedido não encontrado", 404)))
+    }
+
+    // Pedido com tudo o que aparece no comprovante e nas notificações ao cliente
+    pub(crate) fn comprovante_on(conn: &mut PgConnection, id: &str, id_cliente: Option<&str>) -> Result<ComprovantePedido, ApiError> {
+        let pedido = Self::find_pedido(conn, id, id_cliente)?;
+
+        let endereco = enderecosEntrega::table
+            .select(EnderecosEntrega::as_select())
+            .filter(enderecosEntrega::idPedido.eq(id))
+            .first::<EnderecosEntrega>(conn)
+            .optional()?;
+
+        let pagamento = pagamentos::table
+            .select(Pagamento::as_select())
+            .filter(pagamentos::idPedido.eq(id))
+            .first::<Pagamento>(conn)
+            .optional()?;
+
+        let itens = produtosPedido::table
+            .left_join(produtos::table)
+            .select((ProdutosPedido::as_select(), produtos::nome.nullable()))
+            .filter(produtosPedido::idPedido.eq(id))
+            .order_by(produtosPedido::createdAt.asc())
+            .load::<(ProdutosPedido, Option<String>)>(conn)?;
+
+        let codigo_cupom = cuponsUtilizados::table
+            .inner_join(cupons::table)
+            .select(cupons::codigo)
+            .filter(cuponsUtilizados::idPedido.eq(id))
+            .first::<String>(conn)
+            .optional()?;
+
+        Ok(ComprovantePedido { pedido, endereco, pagamento, itens, codigo_cupom })
+    }
+
+    pub(crate) fn update_status_on(
+        conn: &mut PgConnection,
+        id: &str,
+        status_atual: StatusPedido,
+        status_novo: StatusPedido
+    ) -> Result<Pedido, ApiError> {
+        diesel::update(pedidos::table)
+            .filter(pedidos::id.eq(id))
+            .filter(pedidos::status.eq(status_atual.as_str()))
+            .set((
+                pedidos::status.eq(status_novo.as_str()),
+                pedidos::updatedAt.eq(diesel::dsl::now),
+            ))
+            .get_result::<Pedido>(conn)
+            .optional()
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::from(AppMessage::new("O status do pedido foi alterado por outra operação, tente novamente", 409)))
+    }
+
+    // Devolve ao estoque os itens de um pedido cancelado e desfaz a contagem de vendas
+    pub(crate) fn restaurar_estoque_on(conn: &mut PgConnection, id_pedido: &str) -> Result<(), ApiError> {
+        let itens = produtosPedido::table
+            .select(ProdutosPedido::as_select())
+            .filter(produtosPedido::idPedido.eq(id_pedido))
+            .load::<ProdutosPedido>(conn)?;
+
+        for item in &itens {
+            ProdutoDal::update_estoque_on(conn, &item.sku_produto, &item.quantidade)?;
+            ProdutoDal::update_vendas_on(conn, &item.sku_produto, &(-&item.quantidade))?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn registrar_status(
+        conn: &mut PgConnection,
+        id_pedido: &str,
+        status_anterior: Option<StatusPedido>,
+        status_novo: StatusPedido,
+        alterado_por: &str,
+        motivo: Option<&str>
+    ) -> Result<HistoricoStatusPedido, diesel::result::Error> {
+        diesel::insert_into(historicoStatusPedido::table)
+            .values((
+                historicoStatusPedido::id.eq(Uuid::new_v4().to_string()),
+                historicoStatusPedido::idPedido.eq(id_pedido),
+                historicoStatusPedido::statusAnterior.eq(status_anterior.map(|s| s.as_str())),
+                historicoStatusPedido::statusNovo.eq(status_novo.as_str()),
+                historicoStatusPedido::alteradoPor.eq(alterado_por),
+                historicoStatusPedido::motivo.eq(motivo),
+                historicoStatusPedido::createdAt.eq(diesel::dsl::now),
+            ))
+            .get_result::<HistoricoStatusPedido>(conn)
+    }
+
+    fn build_pedido_json(
+        pedido: Pedido,
+        endereco: Option<EnderecosEntrega>,
+        pagamento: Option<Pagamento>,
+        produtos: Vec<ProdutosPedido>
+    ) -> Result<Value, diesel::result::Error> {
+        let mut pedido_json = serde_json::to_value(pedido)
+            .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+
+        if let Value::Object(ref mut pedido_obj) = pedido_json {
+            pedido_obj.insert(
+                "enderecosEntrega".to_string(),
+                serde_json::to_value(endereco)
+                    .map_err(|_| diesel::result::Error::RollbackTransaction)?,
+            );
+
+            // Parcelas com os centavos restantes distribuídos, somando exatamente o valor total
+            let parcelas = pagamento.as_ref()
+                .map(|p| money::ratear(&p.valor_total, p.numero_parcelas.max(1) as usize));
+
+            let mut pagamento_json = serde_json::to_value(pagamento)
+                .map_err(|_| diesel::result::Error::RollbackTransaction)?;
+
+            if let (Value::Object(pagamento_obj), Some(parcelas)) = (&mut pagamento_json, parcelas) {
+                pagamento_obj.insert(
+                    "parcelas".to_string(),
+                    serde_json::to_value(parcelas)
+                        .map_err(|_| diesel::result::Error::RollbackTransaction)?,
+                );
+            }
+
+            pedido_obj.insert("pagamentos".to_string(), pagamento_json);
+
+            pedido_obj.insert(
+                "produtos".to_string(),
+                serde_json::to_value(produtos)
+                    .map_err(|_| diesel::result::Error::RollbackTransaction)?,
+            );
+        }
+
+        Ok(pedido_json)
+    }
+}
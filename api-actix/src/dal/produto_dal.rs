@@ -7,7 +7,7 @@ use diesel::sql_types::Double;
 use crate::schema::{categorias, produtos};
 use crate::db::DbPool;
 use crate::models::categoria::Categoria;
-use crate::models::produto::Produto;
+use crate::models::produto::{ConflitoEstoque, Produto};
 use crate::utils::app_message::{ApiError, AppMessage};
 
 pub struct ProdutoDal;
@@ -214,37 +214,28 @@ impl ProdutoDal {
             .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
     }
 
-    pub async fn update_estoque(pool: &DbPool, sku: &String, quantidade: BigDecimal) -> Result<(), ApiError> {
-        let pool_clone = pool.clone();
-        let sku_owned = sku.clone();
-
-        tokio::task::spawn_blocking(move || {
-            let mut connection = pool_clone.get()
-                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
-
-            let produto = produtos::table
-                .filter(produtos::sku.eq(&sku_owned))
-                .select(produtos::estoque)
-                .first::<BigDecimal>(&mut connection)
-                .optional()
-                .map_err(ApiError::from)?;
+    pub(crate) fn update_estoque_on(conn: &mut PgConnection, sku: &str, quantidade: &BigDecimal) -> Result<(), ApiError> {
+        let produto = produtos::table
+            .filter(produtos::sku.eq(sku))
+            .select(produtos::estoque)
+            .first::<BigDecimal>(conn)
+            .optional()
+            .map_err(ApiError::from)?;
 
-            if produto.is_none() {
-                return Err(ApiError::from(AppMessage::new("Produto não encontrado", 404)));
-            }
+        if produto.is_none() {
+            return Err(ApiError::from(AppMessage::new("Produto não encontrado", 404)));
+        }
 
-            let query = format!(
-                "UPDATE \"produtos\" SET \"estoque\"=\"estoque\" + ({}) WHERE \"sku\" = '{}'",
-                quantidade,
-                sku_owned.replace('\'', "''")
-            );
+        let query = format!(
+            "UPDATE \"produtos\" SET \"estoque\"=\"estoque\" + ({}) WHERE \"sku\" = '{}'",
+            quantidade,
+            sku.replace('\'', "''")
+        );
 
-            sql_query(query)
-                .execute(&mut connection)
-                .map(|_| ())
-                .map_err(ApiError::from)
-        }).await
-            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+        sql_query(query)
+            .execute(conn)
+            .map(|_| ())
+            .map_err(ApiError::from)
     }
 
     pub async fn get_all_ofertas(pool: &DbPool, campos: &str, page: u32, mut page_size: u32) -> Result<Vec<serde_json::Value>, ApiError> {
@@ -302,37 +293,62 @@ impl ProdutoDal {
             .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
     }
 
-    pub async fn update_vendas(pool: &DbPool, sku: &String, quantidade: BigDecimal) -> Result<(), ApiError> {
-        let pool_clone = pool.clone();
-        let sku_owned = sku.clone();
+    pub(crate) fn reservar_estoque_on(conn: &mut PgConnection, sku: &str, quantidade: i32) -> Result<Option<ConflitoEstoque>, ApiError> {
+        let quantidade_bd = BigDecimal::from(quantidade);
+
+        // Baixa condicional: só decrementa se houver saldo, evitando venda acima do estoque
+        let atualizados = diesel::update(produtos::table)
+            .filter(produtos::sku.eq(sku))
+            .filter(produtos::estoque.ge(quantidade_bd.clone()))
+            .set((
+                produtos::estoque.eq(produtos::estoque - quantidade_bd),
+                produtos::qtdvendas.eq(produtos::qtdvendas + quantidade),
+                produtos::updatedAt.eq(diesel::dsl::now),
+            ))
+            .execute(conn)
+            .map_err(ApiError::from)?;
+
+        if atualizados > 0 {
+            return Ok(None);
+        }
 
-        tokio::task::spawn_blocking(move || {
-            let mut connection = pool_clone.get()
-                .map_err(|e| ApiError::from(AppMessage::new(&format!("Database connection error: {}", e), 500)))?;
+        let estoque_disponivel = produtos::table
+            .filter(produtos::sku.eq(sku))
+            .select(produtos::estoque)
+            .first::<BigDecimal>(conn)
+            .optional()
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::from(AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400)))?;
+
+        Ok(Some(ConflitoEstoque {
+            sku_produto: sku.to_string(),
+            quantidade_solicitada: quantidade,
+            estoque_disponivel,
+        }))
+    }
 
-            let produto = produtos::table
-                .filter(produtos::sku.eq(&sku_owned))
-                .select(produtos::estoque)
-                .first::<BigDecimal>(&mut connection)
-                .optional()
-                .map_err(ApiError::from)?;
+    pub(crate) fn update_vendas_on(conn: &mut PgConnection, sku: &str, quantidade: &BigDecimal) -> Result<(), ApiError> {
+        let produto = produtos::table
+            .filter(produtos::sku.eq(sku))
+            .select(produtos::estoque)
+            .first::<BigDecimal>(conn)
+            .optional()
+            .map_err(ApiError::from)?;
 
-            if produto.is_none() {
-                return Err(ApiError::from(AppMessage::new("Produto não encontrado", 404)));
-            }
+        if produto.is_none() {
+            return Err(ApiError::from(AppMessage::new("Produto não encontrado", 404)));
+        }
 
-            let query = format!(
-                "UPDATE \"produtos\" SET \"qtdvendas\"=\"qtdvendas\" + ({}) WHERE \"sku\" = '{}'",
-                quantidade,
-                sku_owned.replace('\'', "''")
-            );
+        let query = format!(
+            "UPDATE \"produtos\" SET \"qtdvendas\"=\"qtdvendas\" + ({}) WHERE \"sku\" = '{}'",
+            quantidade,
+            sku.replace('\'', "''")
+        );
 
-            sql_query(query)
-                .execute(&mut connection)
-                .map(|_| ())
-                .map_err(ApiError::from)
-        }).await
-            .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?
+        sql_query(query)
+            .execute(conn)
+            .map(|_| ())
+            .map_err(ApiError::from)
     }
 
     fn process_produtos_with_categorias(
@@ -2,6 +2,8 @@ use actix_web::{App, HttpServer, middleware::Logger, web};
 use dotenvy::dotenv;
 use std::env;
 use crate::db::{AppState};
+use crate::services::outbox_service::OutboxService;
+use crate::utils::app_message::AppMessage;
 
 mod dal;
 mod services;
@@ -13,6 +15,7 @@ pub mod utils;
 pub mod configs;
 pub mod middlewares;
 pub mod models;
+pub mod providers;
 pub mod validations;
 
 #[actix_web::main]
@@ -32,6 +35,8 @@ async fn main() -> std::io::Result<()> {
         drop(conn);
     }
 
+    OutboxService::iniciar_worker(app_state.db_pool.clone());
+
     let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
     let server_url = format!("{}:{}", host, port);
@@ -41,6 +46,9 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(app_state.clone()))
+            .app_data(web::JsonConfig::default().error_handler(|err, _req| {
+                AppMessage::new(&format!("JSON inválido: {}", err), 400).into()
+            }))
             .wrap(Logger::default())
             .configure(routes::routes)
     })
@@ -0,0 +1,110 @@
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+use crate::configs::auth::AuthConfig;
+use crate::middlewares::is_authenticated::AuthError;
+use crate::utils::app_message::AppMessage;
+
+#[derive(Debug, Clone)]
+pub struct AdminAuth {
+    pub usuario: String,
+}
+
+// Middleware factory para rotas de suporte/backoffice
+pub struct AdminAuthentication;
+
+impl<S, B> Transform<S, ServiceRequest> for AdminAuthentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AdminAuthenticationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AdminAuthenticationMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AdminAuthenticationMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AdminAuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            // Sem ADMIN_TOKEN configurado as rotas administrativas ficam bloqueadas
+            let admin_token = match AuthConfig::new().admin_token {
+                Some(token) => token,
+                None => {
+                    log::warn!("ADMIN_TOKEN não configurado, acesso administrativo negado");
+                    return Err(AuthError(AppMessage::new("Acesso administrativo não configurado.", 401)).into());
+                }
+            };
+
+            let token_informado = req.headers()
+                .get("x-admin-token")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or_default();
+
+            if token_informado.is_empty() {
+                return Err(AuthError(AppMessage::new("Token administrativo não informado.", 401)).into());
+            }
+
+            if !tokens_iguais(token_informado.as_bytes(), admin_token.as_bytes()) {
+                return Err(AuthError(AppMessage::new("Token administrativo inválido.", 401)).into());
+            }
+
+            let usuario = req.headers()
+                .get("x-admin-usuario")
+                .and_then(|h| h.to_str().ok())
+                .map(|u| u.trim().chars().take(60).collect::<String>())
+                .filter(|u| !u.is_empty())
+                .unwrap_or_else(|| "suporte".to_string());
+
+            req.extensions_mut().insert(AdminAuth { usuario });
+
+            service.call(req).await
+        })
+    }
+}
+
+// Comparação em tempo constante para não vazar o token por timing
+fn tokens_iguais(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+pub fn get_admin_from_request(req: &actix_web::HttpRequest) -> Result<AdminAuth, AppMessage> {
+    req.extensions()
+        .get::<AdminAuth>()
+        .cloned()
+        .ok_or_else(|| AppMessage::new("Acesso administrativo não autenticado", 401))
+}
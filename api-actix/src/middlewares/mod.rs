@@ -1 +1,2 @@
-pub mod is_authenticated;
\ No newline at end of file
+pub mod is_authenticated;
+pub mod is_admin;
\ No newline at end of file
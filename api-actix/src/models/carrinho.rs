@@ -0,0 +1,96 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::models::pedido::{EnderecoPayload, PagamentoRequest};
+use crate::validations::pedido_validations::{validate_cep, validate_codigo_ibge_uf};
+use crate::schema::itensCarrinho as item_carrinhos;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCarrinho {
+    pub id: String,
+    #[diesel(column_name = "idCliente")]
+    pub id_cliente: String,
+    #[diesel(column_name = "skuProduto")]
+    pub sku_produto: String,
+    pub quantidade: i32,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct AdicionarItemCarrinhoPayload {
+    #[serde(rename = "skuProduto")]
+    #[validate(length(min = 1, message = "SKU do produto é obrigatório"))]
+    pub sku_produto: String,
+    #[validate(range(min = 1, max = 999, message = "Quantidade deve estar entre 1 e 999"))]
+    pub quantidade: i32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct AtualizarItemCarrinhoPayload {
+    #[validate(range(min = 1, max = 999, message = "Quantidade deve estar entre 1 e 999"))]
+    pub quantidade: i32,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CarrinhoQueryParams {
+    #[serde(rename = "codigoIbgeUF")]
+    #[validate(custom = "validate_codigo_ibge_uf")]
+    pub codigo_ibge_uf: Option<String>,
+    #[validate(custom = "validate_cep")]
+    pub cep: Option<String>,
+    #[serde(rename = "modalidadeFrete")]
+    pub modalidade_frete: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CheckoutCarrinhoRequest {
+    #[serde(rename = "enderecoEntrega")]
+    #[validate]
+    pub endereco_entrega: Option<EnderecoPayload>,
+    #[serde(rename = "idEndereco")]
+    pub id_endereco: Option<String>,
+    #[validate]
+    pub pagamento: PagamentoRequest,
+    #[serde(rename = "codigoCupom")]
+    #[validate(length(min = 1, max = 30, message = "Código do cupom deve ter entre 1 e 30 caracteres"))]
+    pub codigo_cupom: Option<String>,
+    #[serde(rename = "modalidadeFrete")]
+    pub modalidade_frete: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCarrinhoView {
+    pub sku_produto: String,
+    pub nome: String,
+    pub foto: Option<String>,
+    pub quantidade: i32,
+    pub estoque_disponivel: BigDecimal,
+    pub valor_unitario_original: BigDecimal,
+    pub pct_oferta: BigDecimal,
+    pub valor_unitario: BigDecimal,
+    pub valor_bruto: BigDecimal,
+    pub aviso: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarrinhoView {
+    pub itens: Vec<ItemCarrinhoView>,
+    pub quantidade_itens: i32,
+    pub valor_bruto: BigDecimal,
+    pub valor_frete: Option<BigDecimal>,
+    pub valor_total: BigDecimal,
+    pub possui_avisos: bool,
+}
@@ -0,0 +1,16 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsultaCep {
+    pub cep: String,
+    #[serde(rename = "codigoIbgeUF")]
+    pub codigo_ibge_uf: String,
+    #[serde(rename = "siglaUF")]
+    pub sigla_uf: String,
+    #[serde(rename = "nomeUF")]
+    pub nome_uf: String,
+    // Só preenchidos quando o CEP cai numa faixa de localidade da tabela embutida
+    pub codigo_ibge_cidade: Option<String>,
+    pub cidade: Option<String>,
+}
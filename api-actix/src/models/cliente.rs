@@ -6,7 +6,7 @@ use validator::Validate;
 use crate::schema::clientes;
 use crate::validations::cliente_validations::{validate_cpf, validate_cnpj, validate_data_nascimento, validate_ie, validate_senha_forte, validate_telefone, validate_sexo, validate_tipo_pessoa};
 
-#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[derive(Queryable, Debug, Clone, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
 #[diesel(primary_key(id))]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 #[serde(rename_all = "camelCase")]
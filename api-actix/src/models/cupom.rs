@@ -0,0 +1,166 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use validator::Validate;
+use crate::utils::app_message::AppMessage;
+use crate::validations::cupom_validations::{validate_codigo_cupom, validate_tipo_desconto, validate_valor_desconto, validate_valor_minimo_pedido};
+use crate::schema::{cupons, cuponsRestricoes, cuponsUtilizados};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TipoDesconto {
+    #[serde(rename = "P")]
+    Percentual,
+    #[serde(rename = "V")]
+    Valor,
+}
+
+impl TipoDesconto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Percentual => "P",
+            Self::Valor => "V",
+        }
+    }
+}
+
+impl FromStr for TipoDesconto {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "P" => Ok(Self::Percentual),
+            "V" => Ok(Self::Valor),
+            _ => Err(AppMessage::new(&format!("Tipo de desconto \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable, Clone)]
+#[diesel(table_name = cupons)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct Cupom {
+    pub id: String,
+    pub codigo: String,
+    pub descricao: Option<String>,
+    #[diesel(column_name = "tipoDesconto")]
+    pub tipo_desconto: String,
+    #[diesel(column_name = "valorDesconto")]
+    pub valor_desconto: BigDecimal,
+    #[diesel(column_name = "valorMinimoPedido")]
+    pub valor_minimo_pedido: BigDecimal,
+    #[diesel(column_name = "validoDe")]
+    pub valido_de: NaiveDateTime,
+    #[diesel(column_name = "validoAte")]
+    pub valido_ate: NaiveDateTime,
+    #[diesel(column_name = "limiteUsoCliente")]
+    pub limite_uso_cliente: Option<i32>,
+    #[diesel(column_name = "limiteUsoTotal")]
+    pub limite_uso_total: Option<i32>,
+    #[diesel(column_name = "quantidadeUtilizada")]
+    pub quantidade_utilizada: i32,
+    pub ativo: bool,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable, Clone)]
+#[diesel(table_name = cuponsRestricoes)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct CupomRestricao {
+    pub id: String,
+    #[diesel(column_name = "idCupom")]
+    pub id_cupom: String,
+    #[diesel(column_name = "idCategoria")]
+    pub id_categoria: Option<String>,
+    #[diesel(column_name = "skuProduto")]
+    pub sku_produto: Option<String>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(table_name = cuponsUtilizados)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct CupomUtilizado {
+    pub id: String,
+    #[diesel(column_name = "idCupom")]
+    pub id_cupom: String,
+    #[diesel(column_name = "idCliente")]
+    pub id_cliente: String,
+    #[diesel(column_name = "idPedido")]
+    pub id_pedido: String,
+    #[diesel(column_name = "valorDesconto")]
+    pub valor_desconto: BigDecimal,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CupomDetalhe {
+    #[serde(flatten)]
+    pub cupom: Cupom,
+    pub categorias: Vec<String>,
+    pub skus: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateCupomPayload {
+    #[validate(custom = "validate_codigo_cupom")]
+    pub codigo: String,
+    #[validate(length(min = 1, max = 120, message = "Descrição deve ter entre 1 e 120 caracteres"))]
+    pub descricao: Option<String>,
+    #[serde(rename = "tipoDesconto")]
+    #[validate(custom = "validate_tipo_desconto")]
+    pub tipo_desconto: String,
+    #[serde(rename = "valorDesconto")]
+    #[validate(custom = "validate_valor_desconto")]
+    pub valor_desconto: BigDecimal,
+    #[serde(rename = "valorMinimoPedido", default)]
+    #[validate(custom = "validate_valor_minimo_pedido")]
+    pub valor_minimo_pedido: BigDecimal,
+    #[serde(rename = "validoDe")]
+    pub valido_de: NaiveDateTime,
+    #[serde(rename = "validoAte")]
+    pub valido_ate: NaiveDateTime,
+    #[serde(rename = "limiteUsoCliente")]
+    #[validate(range(min = 1, message = "Limite de uso por cliente deve ser maior que zero"))]
+    pub limite_uso_cliente: Option<i32>,
+    #[serde(rename = "limiteUsoTotal")]
+    #[validate(range(min = 1, message = "Limite de uso total deve ser maior que zero"))]
+    pub limite_uso_total: Option<i32>,
+    #[serde(default)]
+    pub categorias: Vec<String>,
+    #[serde(default)]
+    pub skus: Vec<String>,
+}
+
+// Item do pedido do ponto de vista do cupom: o que importa é onde ele se encaixa nas restrições
+pub struct ItemCupom<'a> {
+    pub sku_produto: &'a str,
+    pub id_categoria: &'a str,
+    pub valor_bruto: &'a BigDecimal,
+}
+
+pub struct CupomAplicado {
+    pub id_cupom: String,
+    pub codigo: String,
+    pub limite_uso_cliente: Option<i32>,
+    pub valor_desconto: BigDecimal,
+}
+
+pub struct DescontoCupom {
+    pub cupom: CupomAplicado,
+    pub descontos_itens: Vec<BigDecimal>,
+}
@@ -0,0 +1,194 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use validator::Validate;
+use crate::utils::app_message::AppMessage;
+use crate::validations::devolucao_validations::validate_motivo_devolucao;
+use crate::schema::devolucoes as devolucaos;
+use crate::schema::historicoStatusDevolucao as historico_status_devolucaos;
+use crate::schema::itensDevolucao as item_devolucaos;
+
+// Direito de arrependimento do CDC (art. 49), contado a partir da entrega
+pub const PRAZO_DEVOLUCAO_DIAS: i64 = 7;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct Devolucao {
+    pub id: String,
+    #[diesel(column_name = "idPedido")]
+    pub id_pedido: String,
+    #[diesel(column_name = "idCliente")]
+    pub id_cliente: String,
+    pub status: String,
+    pub observacao: Option<String>,
+    #[diesel(column_name = "valorReembolso")]
+    pub valor_reembolso: BigDecimal,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDevolucao {
+    pub id: String,
+    #[diesel(column_name = "idDevolucao")]
+    pub id_devolucao: String,
+    #[diesel(column_name = "idProdutoPedido")]
+    pub id_produto_pedido: String,
+    #[diesel(column_name = "skuProduto")]
+    pub sku_produto: String,
+    pub quantidade: BigDecimal,
+    pub motivo: String,
+    #[diesel(column_name = "valorReembolso")]
+    pub valor_reembolso: BigDecimal,
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricoStatusDevolucao {
+    pub id: String,
+    #[diesel(column_name = "idDevolucao")]
+    pub id_devolucao: String,
+    #[diesel(column_name = "statusAnterior")]
+    pub status_anterior: Option<String>,
+    #[diesel(column_name = "statusNovo")]
+    pub status_novo: String,
+    #[diesel(column_name = "alteradoPor")]
+    pub alterado_por: String,
+    pub motivo: Option<String>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+// Devolução com os itens e a trilha de status, como aparece para o cliente e para o atendimento
+#[derive(Serialize)]
+pub struct DevolucaoDetalhada {
+    #[serde(flatten)]
+    pub devolucao: Devolucao,
+    pub itens: Vec<ItemDevolucao>,
+    pub historico: Vec<HistoricoStatusDevolucao>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct ItemDevolucaoRequest {
+    #[serde(rename = "idItemPedido")]
+    #[validate(length(min = 1, message = "Item do pedido é obrigatório"))]
+    pub id_item_pedido: String,
+    #[validate(range(min = 1, message = "Quantidade deve ser maior que zero"))]
+    pub quantidade: i32,
+    #[validate(custom = "validate_motivo_devolucao")]
+    pub motivo: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateDevolucaoRequest {
+    #[validate(length(min = 1, message = "Nenhum item informado"))]
+    #[validate]
+    pub itens: Vec<ItemDevolucaoRequest>,
+    #[validate(length(min = 1, max = 500, message = "Observação deve ter entre 1 e 500 caracteres"))]
+    pub observacao: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct DecisaoDevolucaoPayload {
+    #[validate(length(max = 255, message = "Motivo deve ter no máximo 255 caracteres"))]
+    pub motivo: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevolucaoFiltro {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusDevolucao {
+    Solicitada,
+    Aprovada,
+    Recusada,
+}
+
+impl StatusDevolucao {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Solicitada => "S",
+            Self::Aprovada => "A",
+            Self::Recusada => "R",
+        }
+    }
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Self::Solicitada => "Solicitada",
+            Self::Aprovada => "Aprovada",
+            Self::Recusada => "Recusada",
+        }
+    }
+}
+
+impl FromStr for StatusDevolucao {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "S" => Ok(Self::Solicitada),
+            "A" => Ok(Self::Aprovada),
+            "R" => Ok(Self::Recusada),
+            _ => Err(AppMessage::new(&format!("Status de devolução \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+impl fmt::Display for StatusDevolucao {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.descricao())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotivoDevolucao {
+    Arrependimento,
+    Defeito,
+    ProdutoErrado,
+    Avaria,
+    Outro,
+}
+
+impl MotivoDevolucao {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Arrependimento => "arrependimento",
+            Self::Defeito => "defeito",
+            Self::ProdutoErrado => "produto_errado",
+            Self::Avaria => "avaria",
+            Self::Outro => "outro",
+        }
+    }
+}
+
+impl FromStr for MotivoDevolucao {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "arrependimento" => Ok(Self::Arrependimento),
+            "defeito" => Ok(Self::Defeito),
+            "produto_errado" => Ok(Self::ProdutoErrado),
+            "avaria" => Ok(Self::Avaria),
+            "outro" => Ok(Self::Outro),
+            _ => Err(AppMessage::new(&format!("Motivo de devolução \"{}\" inválido", value), 400)),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::models::pedido::EnderecoPayload;
+use crate::validations::pedido_validations::{validate_cep, validate_codigo_ibge_cidade, validate_codigo_ibge_uf};
+use crate::schema::enderecosCliente as endereco_clientes;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct EnderecoCliente {
+    pub id: String,
+    #[diesel(column_name = "idCliente")]
+    pub id_cliente: String,
+    pub apelido: Option<String>,
+    #[diesel(column_name = "nomeRemetente")]
+    pub nome_remetente: String,
+    pub cep: String,
+    pub logradouro: String,
+    pub numero: String,
+    pub complemento: Option<String>,
+    pub bairro: String,
+    pub cidade: Option<String>,
+    #[diesel(column_name = "codigoIbgeCidade")]
+    pub codigo_ibge_cidade: String,
+    #[diesel(column_name = "codigoIbgeUF")]
+    #[serde(rename = "codigoIbgeUF")]
+    pub codigo_ibge_uf: String,
+    pub padrao: bool,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+}
+
+// O pedido guarda uma cópia: alterar ou remover o endereço depois não muda pedidos já feitos
+impl From<EnderecoCliente> for EnderecoPayload {
+    fn from(endereco: EnderecoCliente) -> Self {
+        Self {
+            nome_remetente: endereco.nome_remetente,
+            cep: endereco.cep,
+            logradouro: endereco.logradouro,
+            numero: endereco.numero,
+            complemento: endereco.complemento,
+            bairro: endereco.bairro,
+            cidade: endereco.cidade,
+            codigo_ibge_cidade: endereco.codigo_ibge_cidade,
+            codigo_ibge_uf: endereco.codigo_ibge_uf,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct EnderecoClientePayload {
+    #[validate(length(min = 1, max = 30, message = "Apelido deve ter entre 1 e 30 caracteres"))]
+    pub apelido: Option<String>,
+    #[serde(rename = "nomeRemetente")]
+    #[validate(length(min = 1, max = 120, message = "Nome do remetente deve ter entre 1 e 120 caracteres"))]
+    pub nome_remetente: String,
+    #[validate(custom = "validate_cep")]
+    pub cep: String,
+    #[validate(length(min = 1, max = 60, message = "Logradouro deve ter entre 1 e 60 caracteres"))]
+    pub logradouro: String,
+    #[validate(length(min = 1, max = 10, message = "Número deve ter entre 1 e 10 caracteres"))]
+    pub numero: String,
+    #[validate(length(min = 1, max = 60, message = "Complemento deve ter entre 1 e 60 caracteres"))]
+    pub complemento: Option<String>,
+    #[validate(length(min = 1, max = 60, message = "Bairro deve ter entre 1 e 60 caracteres"))]
+    pub bairro: String,
+    #[validate(length(min = 1, max = 60, message = "Cidade deve ter entre 1 e 60 caracteres"))]
+    pub cidade: Option<String>,
+    #[serde(rename = "codigoIbgeCidade")]
+    #[validate(custom = "validate_codigo_ibge_cidade")]
+    pub codigo_ibge_cidade: String,
+    #[serde(rename = "codigoIbgeUF")]
+    #[validate(custom = "validate_codigo_ibge_uf")]
+    pub codigo_ibge_uf: String,
+    pub padrao: Option<bool>,
+}
@@ -0,0 +1,98 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use validator::Validate;
+use crate::models::pedido::StatusPagamento;
+use crate::schema::eventosPagamento as evento_pagamentos;
+use crate::utils::app_message::AppMessage;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct EventoPagamento {
+    pub id: String,
+    #[diesel(column_name = "idEvento")]
+    pub id_evento: String,
+    #[diesel(column_name = "idPedido")]
+    pub id_pedido: String,
+    pub tipo: String,
+    pub payload: String,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct EventoPagamentoPayload {
+    #[validate(length(min = 1, max = 255, message = "Id do evento deve ter entre 1 e 255 caracteres"))]
+    pub id: String,
+    pub tipo: String,
+    #[validate(length(equal = 36, message = "Id do pedido inválido"))]
+    pub id_pedido: String,
+    #[validate(length(min = 1, max = 255, message = "TID deve ter entre 1 e 255 caracteres"))]
+    pub tid: Option<String>,
+    #[validate(length(max = 255, message = "Motivo deve ter no máximo 255 caracteres"))]
+    pub motivo: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoEventoPagamento {
+    Aprovado,
+    Recusado,
+    Estornado,
+    Chargeback,
+}
+
+impl TipoEventoPagamento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Aprovado => "pagamento.aprovado",
+            Self::Recusado => "pagamento.recusado",
+            Self::Estornado => "pagamento.estornado",
+            Self::Chargeback => "pagamento.chargeback",
+        }
+    }
+
+    pub fn status_pagamento(&self) -> StatusPagamento {
+        match self {
+            Self::Aprovado => StatusPagamento::Aprovado,
+            Self::Recusado => StatusPagamento::Recusado,
+            Self::Estornado => StatusPagamento::Estornado,
+            Self::Chargeback => StatusPagamento::Chargeback,
+        }
+    }
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Self::Aprovado => "Pagamento aprovado",
+            Self::Recusado => "Pagamento recusado",
+            Self::Estornado => "Pagamento estornado",
+            Self::Chargeback => "Chargeback do pagamento",
+        }
+    }
+}
+
+impl FromStr for TipoEventoPagamento {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "pagamento.aprovado" => Ok(Self::Aprovado),
+            "pagamento.recusado" => Ok(Self::Recusado),
+            "pagamento.estornado" => Ok(Self::Estornado),
+            "pagamento.chargeback" => Ok(Self::Chargeback),
+            _ => Err(AppMessage::new(&format!("Tipo de evento \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultadoEventoPagamento {
+    pub id_evento: String,
+    pub duplicado: bool,
+    pub status_pedido: String,
+    pub status_pagamento: String,
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::validations::pedido_validations::{validate_codigo_ibge_cidade, validate_codigo_ibge_uf};
+use crate::schema::feriados;
+
+// Feriado cadastrado: sem ano repete todo ano; sem UF e sem cidade vale para o país inteiro
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(table_name = feriados)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct Feriado {
+    pub id: String,
+    pub descricao: String,
+    pub dia: i16,
+    pub mes: i16,
+    pub ano: Option<i16>,
+    #[diesel(column_name = "codigoIbgeUF")]
+    #[serde(rename = "codigoIbgeUF")]
+    pub codigo_ibge_uf: Option<String>,
+    #[diesel(column_name = "codigoIbgeCidade")]
+    pub codigo_ibge_cidade: Option<String>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreateFeriadoPayload {
+    #[validate(length(min = 1, max = 60, message = "Descrição deve ter entre 1 e 60 caracteres"))]
+    pub descricao: String,
+    #[validate(range(min = 1, max = 31, message = "Dia deve estar entre 1 e 31"))]
+    pub dia: i16,
+    #[validate(range(min = 1, max = 12, message = "Mês deve estar entre 1 e 12"))]
+    pub mes: i16,
+    #[validate(range(min = 2000, max = 2100, message = "Ano deve estar entre 2000 e 2100"))]
+    pub ano: Option<i16>,
+    #[serde(rename = "codigoIbgeUF")]
+    #[validate(custom = "validate_codigo_ibge_uf")]
+    pub codigo_ibge_uf: Option<String>,
+    #[serde(rename = "codigoIbgeCidade")]
+    #[validate(custom = "validate_codigo_ibge_cidade")]
+    pub codigo_ibge_cidade: Option<String>,
+}
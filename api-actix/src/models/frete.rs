@@ -0,0 +1,163 @@
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use validator::Validate;
+use crate::utils::app_message::AppMessage;
+use crate::validations::frete_validations::{validate_peso_maximo, validate_peso_minimo, validate_valor_frete, validate_valor_minimo_frete_gratis};
+use crate::validations::pedido_validations::{validate_cep, validate_codigo_ibge_uf};
+use crate::schema::regrasFrete as regra_fretes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModalidadeFrete {
+    Economico,
+    Expresso,
+    Retirada,
+}
+
+impl ModalidadeFrete {
+    pub const TODAS: [ModalidadeFrete; 3] = [Self::Economico, Self::Expresso, Self::Retirada];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Economico => "economico",
+            Self::Expresso => "expresso",
+            Self::Retirada => "retirada",
+        }
+    }
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Self::Economico => "Entrega econômica",
+            Self::Expresso => "Entrega expressa",
+            Self::Retirada => "Retirada na loja",
+        }
+    }
+}
+
+impl FromStr for ModalidadeFrete {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "economico" => Ok(Self::Economico),
+            "expresso" => Ok(Self::Expresso),
+            "retirada" => Ok(Self::Retirada),
+            _ => Err(AppMessage::new(&format!("Modalidade de frete \"{}\" inválida", value), 400)),
+        }
+    }
+}
+
+// Regra sem faixa de CEP e sem UF vale para o país inteiro; sem peso máximo não tem limite de peso
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable, Clone)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct RegraFrete {
+    pub id: String,
+    pub descricao: String,
+    #[diesel(column_name = "codigoIbgeUF")]
+    #[serde(rename = "codigoIbgeUF")]
+    pub codigo_ibge_uf: Option<String>,
+    #[diesel(column_name = "cepInicial")]
+    pub cep_inicial: Option<String>,
+    #[diesel(column_name = "cepFinal")]
+    pub cep_final: Option<String>,
+    #[diesel(column_name = "pesoMinimo")]
+    pub peso_minimo: BigDecimal,
+    #[diesel(column_name = "pesoMaximo")]
+    pub peso_maximo: Option<BigDecimal>,
+    #[diesel(column_name = "valorFrete")]
+    pub valor_frete: BigDecimal,
+    #[diesel(column_name = "valorMinimoFreteGratis")]
+    pub valor_minimo_frete_gratis: Option<BigDecimal>,
+    pub prioridade: i32,
+    pub ativo: bool,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "updatedAt")]
+    pub updated_at: NaiveDateTime,
+    pub modalidade: String,
+    #[diesel(column_name = "prazoDiasUteis")]
+    pub prazo_dias_uteis: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct RegraFretePayload {
+    #[validate(length(min = 1, max = 60, message = "Descrição deve ter entre 1 e 60 caracteres"))]
+    pub descricao: String,
+    #[serde(rename = "codigoIbgeUF")]
+    #[validate(custom = "validate_codigo_ibge_uf")]
+    pub codigo_ibge_uf: Option<String>,
+    #[serde(rename = "cepInicial")]
+    #[validate(custom = "validate_cep")]
+    pub cep_inicial: Option<String>,
+    #[serde(rename = "cepFinal")]
+    #[validate(custom = "validate_cep")]
+    pub cep_final: Option<String>,
+    #[serde(rename = "pesoMinimo", default)]
+    #[validate(custom = "validate_peso_minimo")]
+    pub peso_minimo: BigDecimal,
+    #[serde(rename = "pesoMaximo")]
+    #[validate(custom = "validate_peso_maximo")]
+    pub peso_maximo: Option<BigDecimal>,
+    #[serde(rename = "valorFrete")]
+    #[validate(custom = "validate_valor_frete")]
+    pub valor_frete: BigDecimal,
+    #[serde(rename = "valorMinimoFreteGratis")]
+    #[validate(custom = "validate_valor_minimo_frete_gratis")]
+    pub valor_minimo_frete_gratis: Option<BigDecimal>,
+    #[serde(default)]
+    pub prioridade: i32,
+    #[serde(default = "ativo_padrao")]
+    pub ativo: bool,
+    pub modalidade: Option<String>,
+    #[serde(rename = "prazoDiasUteis")]
+    #[validate(range(min = 1, max = 60, message = "Prazo deve estar entre 1 e 60 dias úteis"))]
+    pub prazo_dias_uteis: Option<i32>,
+}
+
+fn ativo_padrao() -> bool {
+    true
+}
+
+// O que a regra precisa saber do pedido (ou do carrinho) para ser escolhida
+pub struct CargaFrete<'a> {
+    pub cep: Option<&'a str>,
+    pub codigo_ibge_uf: &'a str,
+    pub peso_taxado: BigDecimal,
+    pub valor_pedido: BigDecimal,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CotacaoFreteQueryParams {
+    #[validate(custom = "validate_cep")]
+    pub cep: String,
+    // SKUs separados por vírgula, com a quantidade opcional depois de dois-pontos: "SKU1:2,SKU2"
+    #[validate(length(min = 1, message = "Nenhum produto informado"))]
+    pub skus: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcaoFrete {
+    pub id: ModalidadeFrete,
+    pub descricao: &'static str,
+    pub valor_frete: BigDecimal,
+    pub prazo_dias_uteis: u32,
+    pub data_entrega: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CotacaoFrete {
+    pub cep: String,
+    #[serde(rename = "codigoIbgeUF")]
+    pub codigo_ibge_uf: String,
+    pub peso_taxado: BigDecimal,
+    pub valor_produtos: BigDecimal,
+    pub opcoes: Vec<OpcaoFrete>,
+}
@@ -0,0 +1,36 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::schema::chavesIdempotencia as chave_idempotencias;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct ChaveIdempotencia {
+    pub id: String,
+    #[diesel(column_name = "idCliente")]
+    pub id_cliente: String,
+    pub chave: String,
+    #[diesel(column_name = "hashRequisicao")]
+    pub hash_requisicao: String,
+    #[diesel(column_name = "statusCode")]
+    pub status_code: Option<i32>,
+    pub resposta: Option<String>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+    #[diesel(column_name = "expiraEm")]
+    pub expira_em: NaiveDateTime,
+}
+
+pub enum ReservaIdempotencia {
+    Nova,
+    Concluida(RespostaIdempotente),
+}
+
+pub struct RespostaIdempotente {
+    pub status_code: u16,
+    pub corpo: Value,
+    pub repetida: bool,
+}
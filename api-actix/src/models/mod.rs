@@ -1,5 +1,16 @@
+pub mod carrinho;
 pub mod categoria;
+pub mod cep;
 pub mod cliente;
+pub mod cupom;
+pub mod devolucao;
+pub mod endereco;
+pub mod evento_pagamento;
+pub mod feriado;
+pub mod frete;
+pub mod idempotencia;
 pub mod login;
+pub mod nfe;
+pub mod outbox;
 pub mod pedido;
 pub mod produto;
\ No newline at end of file
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use crate::models::cliente::Cliente;
+use crate::models::pedido::{EnderecosEntrega, Pagamento, Pedido, ProdutosPedido};
+use crate::schema::notasFiscais as nota_fiscals;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct NotaFiscal {
+    pub id: String,
+    #[diesel(column_name = "idPedido")]
+    pub id_pedido: String,
+    pub ambiente: i16,
+    pub serie: i32,
+    pub numero: i32,
+    #[diesel(column_name = "chaveAcesso")]
+    pub chave_acesso: String,
+    // O XML é baixado pela rota própria, não vai junto dos metadados
+    #[serde(skip_serializing)]
+    pub xml: String,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+pub struct ItemNotaFiscal {
+    pub produto: ProdutosPedido,
+    pub nome: Option<String>,
+    pub ncm: Option<String>,
+}
+
+// Dados do pedido que vão para a NF-e, lidos de uma vez antes de reservar o número da nota
+pub struct DadosNotaFiscal {
+    pub pedido: Pedido,
+    pub cliente: Cliente,
+    pub endereco: Option<EnderecosEntrega>,
+    pub pagamento: Option<Pagamento>,
+    pub itens: Vec<ItemNotaFiscal>,
+}
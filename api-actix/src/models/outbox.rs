@@ -0,0 +1,98 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use crate::schema::eventosOutbox as evento_outboxs;
+use crate::utils::app_message::AppMessage;
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct EventoOutbox {
+    pub id: String,
+    pub tipo: String,
+    #[diesel(column_name = "idAgregado")]
+    pub id_agregado: String,
+    pub payload: String,
+    pub status: String,
+    pub tentativas: i32,
+    #[diesel(column_name = "entregueA")]
+    pub entregue_a: Vec<String>,
+    #[diesel(column_name = "ultimoErro")]
+    pub ultimo_erro: Option<String>,
+    #[diesel(column_name = "proximaTentativa")]
+    pub proxima_tentativa: NaiveDateTime,
+    #[diesel(column_name = "processadoEm")]
+    pub processado_em: Option<NaiveDateTime>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventoOutboxFiltro {
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipoEvento {
+    PedidoCriado,
+    PagamentoConfirmado,
+    StatusPedidoAlterado,
+    ClienteCadastrado,
+}
+
+impl TipoEvento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PedidoCriado => "pedido.criado",
+            Self::PagamentoConfirmado => "pagamento.confirmado",
+            Self::StatusPedidoAlterado => "pedido.status_alterado",
+            Self::ClienteCadastrado => "cliente.cadastrado",
+        }
+    }
+}
+
+impl FromStr for TipoEvento {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "pedido.criado" => Ok(Self::PedidoCriado),
+            "pagamento.confirmado" => Ok(Self::PagamentoConfirmado),
+            "pedido.status_alterado" => Ok(Self::StatusPedidoAlterado),
+            "cliente.cadastrado" => Ok(Self::ClienteCadastrado),
+            _ => Err(AppMessage::new(&format!("Tipo de evento \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusEvento {
+    Pendente,
+    Entregue,
+    Falhou,
+}
+
+impl StatusEvento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pendente => "P",
+            Self::Entregue => "E",
+            Self::Falhou => "F",
+        }
+    }
+}
+
+impl FromStr for StatusEvento {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "P" => Ok(Self::Pendente),
+            "E" => Ok(Self::Entregue),
+            "F" => Ok(Self::Falhou),
+            _ => Err(AppMessage::new(&format!("Status de evento \"{}\" inválido", value), 400)),
+        }
+    }
+}
@@ -2,9 +2,17 @@ use bigdecimal::BigDecimal;
 use chrono::{NaiveDate, NaiveDateTime};
 use diesel::{Identifiable, Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
-use crate::models::produto::ProdutoPayload;
+use std::fmt;
+use std::str::FromStr;
+use validator::Validate;
+use crate::models::cupom::CupomAplicado;
+use crate::models::frete::ModalidadeFrete;
+use crate::utils::app_message::AppMessage;
+use crate::utils::ibge;
+use crate::validations::pedido_validations::{validate_cep, validate_codigo_ibge_cidade, validate_codigo_ibge_uf, validate_forma_pagamento};
 pub(crate) use crate::schema::pedidos;
 use crate::schema::enderecosEntrega as enderecos_entregas;
+use crate::schema::historicoStatusPedido as historico_status_pedidos;
 use crate::schema::pagamentos;
 use crate::schema::produtosPedido as produtos_pedidos;
 
@@ -25,6 +33,7 @@ pub struct EnderecosEntrega {
     pub numero: String,
     pub complemento: Option<String>,
     pub bairro: String,
+    pub cidade: Option<String>,
     #[diesel(column_name = "codigoIbgeCidade")]
     #[serde(rename = "codigoIbgeCidade")]
     pub codigo_ibge_cidade: String,
@@ -39,7 +48,7 @@ pub struct EnderecosEntrega {
     pub updated_at: NaiveDateTime,
 }
 
-#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[derive(Queryable, Debug, Clone, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
 #[diesel(primary_key(id))]
 #[serde(rename_all = "camelCase")]
 pub struct Pagamento {
@@ -67,6 +76,10 @@ pub struct Pagamento {
     #[diesel(column_name = "updatedAt")]
     #[serde(rename = "updatedAt")]
     pub updated_at: NaiveDateTime,
+    pub status: String,
+    #[diesel(column_name = "valorEstornado")]
+    #[serde(rename = "valorEstornado")]
+    pub valor_estornado: BigDecimal,
 }
 
 #[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
@@ -91,6 +104,8 @@ pub struct Pedido {
     pub created_at: NaiveDateTime,
     #[diesel(column_name = "updatedAt")]
     pub updated_at: NaiveDateTime,
+    #[diesel(column_name = "modalidadeFrete")]
+    pub modalidade_frete: String,
 }
 
 #[derive(Queryable, Debug, Serialize, Selectable, Deserialize, Insertable)]
@@ -117,51 +132,384 @@ pub struct ProdutosPedido {
     pub created_at: NaiveDateTime,
     #[diesel(column_name = "updatedAt")]
     pub updated_at: NaiveDateTime,
+    #[diesel(column_name = "valorUnitarioOriginal")]
+    pub valor_unitario_original: BigDecimal,
+    #[diesel(column_name = "pctOferta")]
+    pub pct_oferta: BigDecimal,
+    pub promocao: Option<String>,
 }
 
-#[derive(Deserialize)]
+impl EnderecosEntrega {
+    // Pedidos anteriores à coluna cidade não têm o nome gravado e dependem da tabela embutida
+    pub fn nome_cidade(&self) -> Option<String> {
+        self.cidade.clone()
+            .or_else(|| ibge::municipio(&self.codigo_ibge_cidade).map(|municipio| municipio.nome.to_string()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
 pub struct EnderecoPayload {
     #[serde(rename = "nomeRemetente")]
+    #[validate(length(min = 1, max = 120, message = "Nome do remetente deve ter entre 1 e 120 caracteres"))]
     pub nome_remetente: String,
+    #[validate(custom = "validate_cep")]
     pub cep: String,
+    #[validate(length(min = 1, max = 60, message = "Logradouro deve ter entre 1 e 60 caracteres"))]
     pub logradouro: String,
+    #[validate(length(min = 1, max = 10, message = "Número deve ter entre 1 e 10 caracteres"))]
     pub numero: String,
+    #[validate(length(min = 1, max = 60, message = "Complemento deve ter entre 1 e 60 caracteres"))]
     pub complemento: Option<String>,
+    #[validate(length(min = 1, max = 60, message = "Bairro deve ter entre 1 e 60 caracteres"))]
     pub bairro: String,
+    #[validate(length(min = 1, max = 60, message = "Cidade deve ter entre 1 e 60 caracteres"))]
+    pub cidade: Option<String>,
     #[serde(rename = "codigoIbgeCidade")]
+    #[validate(custom = "validate_codigo_ibge_cidade")]
     pub codigo_ibge_cidade: String,
     #[serde(rename = "codigoIbgeUF")]
+    #[validate(custom = "validate_codigo_ibge_uf")]
     pub codigo_ibge_uf: String,
 }
 
-#[derive(Deserialize)]
-pub struct PagamentoPayload {
+// Sem deny_unknown_fields: os scripts de carga compartilhados com as outras APIs ainda mandam
+// porcentagemDesconto, que aqui é ignorado (o desconto vem de cupons e ofertas)
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct PagamentoRequest {
     #[serde(rename = "formaPagamento")]
+    #[validate(custom = "validate_forma_pagamento")]
     pub forma_pagamento: String,
     #[serde(rename = "numeroParcelas")]
-    pub numero_parcelas: i32,
-    #[serde(rename = "valorTotal")]
-    pub valor_total: f64,
-    #[serde(rename = "valorParcela")]
-    pub valor_parcela: f64,
+    #[validate(range(min = 1, max = 12, message = "Número de parcelas deve estar entre 1 e 12"))]
+    pub numero_parcelas: i16,
 }
 
-#[derive(Deserialize)]
-pub struct PedidoPayload {
-    #[serde(rename = "idCliente")]
-    pub id_cliente: String,
-    #[serde(rename = "valorBruto")]
-    pub valor_bruto: f64,
-    #[serde(rename = "valorDesconto")]
-    pub valor_desconto: f64,
-    #[serde(rename = "valorFrete")]
-    pub valor_frete: f64,
-    #[serde(rename = "valorLiquido")]
-    pub valor_liquido: f64,
-    #[serde(rename = "dataEntrega")]
-    pub data_entrega: String,
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct ProdutoPedidoRequest {
+    #[serde(rename = "skuProduto")]
+    #[validate(length(min = 1, message = "SKU do produto é obrigatório"))]
+    pub sku_produto: String,
+    #[validate(range(min = 1, message = "Quantidade deve ser maior que zero"))]
+    pub quantidade: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePedidoRequest {
+    #[validate(length(min = 1, message = "Nenhum produto informado"))]
+    #[validate]
+    pub produtos: Vec<ProdutoPedidoRequest>,
+    // Endereço completo ou o id de um endereço salvo do cliente; sem nenhum dos dois vale o endereço padrão
     #[serde(rename = "enderecoEntrega")]
+    #[validate]
+    pub endereco_entrega: Option<EnderecoPayload>,
+    #[serde(rename = "idEndereco")]
+    pub id_endereco: Option<String>,
+    #[validate]
+    pub pagamento: PagamentoRequest,
+    #[serde(rename = "codigoCupom")]
+    #[validate(length(min = 1, max = 30, message = "Código do cupom deve ter entre 1 e 30 caracteres"))]
+    pub codigo_cupom: Option<String>,
+    // Id da opção escolhida na cotação de frete; sem ele vale a entrega econômica
+    #[serde(rename = "modalidadeFrete")]
+    pub modalidade_frete: Option<String>,
+}
+
+// Promoção registrada no item quando o pctoferta do produto é aplicado ao preço
+pub const PROMOCAO_OFERTA_PRODUTO: &str = "OFERTA_PRODUTO";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemPedidoDraft {
+    pub sku_produto: String,
+    pub quantidade: i32,
+    pub valor_unitario_original: BigDecimal,
+    pub pct_oferta: BigDecimal,
+    pub promocao: Option<String>,
+    pub valor_unitario: BigDecimal,
+    pub valor_bruto: BigDecimal,
+    pub valor_desconto: BigDecimal,
+    pub valor_liquido: BigDecimal,
+    pub valor_frete: BigDecimal,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagamentoDraft {
+    pub forma_pagamento: FormaPagamento,
+    pub numero_parcelas: i16,
+    pub valor_total: BigDecimal,
+    pub valor_parcela: BigDecimal,
+}
+
+pub struct PedidoDraft {
+    pub id_cliente: String,
+    pub valor_bruto: BigDecimal,
+    pub valor_desconto: BigDecimal,
+    pub valor_frete: BigDecimal,
+    pub valor_liquido: BigDecimal,
+    pub modalidade_frete: ModalidadeFrete,
+    pub data_entrega: NaiveDate,
     pub endereco_entrega: EnderecoPayload,
-    pub pagamento: PagamentoPayload,
-    pub produtos: Vec<ProdutoPayload>,
-}
\ No newline at end of file
+    pub pagamento: PagamentoDraft,
+    pub itens: Vec<ItemPedidoDraft>,
+    pub cupom: Option<CupomAplicado>,
+    pub origem_carrinho: bool,
+}
+
+// Prévia do pedido montada a partir do mesmo PedidoDraft que seria gravado
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulacaoPedido {
+    pub valor_bruto: BigDecimal,
+    pub valor_desconto: BigDecimal,
+    pub valor_frete: BigDecimal,
+    pub valor_liquido: BigDecimal,
+    pub modalidade_frete: ModalidadeFrete,
+    pub data_entrega: NaiveDate,
+    pub codigo_cupom: Option<String>,
+    pub pagamento: PagamentoDraft,
+    pub itens: Vec<ItemPedidoDraft>,
+}
+
+impl From<PedidoDraft> for SimulacaoPedido {
+    fn from(draft: PedidoDraft) -> Self {
+        Self {
+            valor_bruto: draft.valor_bruto,
+            valor_desconto: draft.valor_desconto,
+            valor_frete: draft.valor_frete,
+            valor_liquido: draft.valor_liquido,
+            modalidade_frete: draft.modalidade_frete,
+            data_entrega: draft.data_entrega,
+            codigo_cupom: draft.cupom.map(|cupom| cupom.codigo),
+            pagamento: draft.pagamento,
+            itens: draft.itens,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PedidoQueryParams {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    pub status: Option<String>,
+    pub data_inicio: Option<String>,
+    pub data_fim: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct QrCodeQueryParams {
+    pub formato: Option<String>,
+}
+
+pub struct PedidoFiltro {
+    pub status: Option<String>,
+    pub data_inicio: Option<NaiveDate>,
+    pub data_fim: Option<NaiveDate>,
+}
+
+// Tudo o que o comprovante em PDF exibe; os itens trazem o nome atual do produto quando ele ainda existe
+pub struct ComprovantePedido {
+    pub pedido: Pedido,
+    pub endereco: Option<EnderecosEntrega>,
+    pub pagamento: Option<Pagamento>,
+    pub itens: Vec<(ProdutosPedido, Option<String>)>,
+    pub codigo_cupom: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusPedido {
+    #[serde(rename = "P")]
+    Pendente,
+    #[serde(rename = "A")]
+    Pago,
+    #[serde(rename = "F")]
+    Faturado,
+    #[serde(rename = "E")]
+    Enviado,
+    #[serde(rename = "R")]
+    Entregue,
+    #[serde(rename = "C")]
+    Cancelado,
+    #[serde(rename = "D")]
+    Devolvido,
+}
+
+impl StatusPedido {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pendente => "P",
+            Self::Pago => "A",
+            Self::Faturado => "F",
+            Self::Enviado => "E",
+            Self::Entregue => "R",
+            Self::Cancelado => "C",
+            Self::Devolvido => "D",
+        }
+    }
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Self::Pendente => "Pendente",
+            Self::Pago => "Pago",
+            Self::Faturado => "Faturado",
+            Self::Enviado => "Enviado",
+            Self::Entregue => "Entregue",
+            Self::Cancelado => "Cancelado",
+            Self::Devolvido => "Devolvido",
+        }
+    }
+}
+
+impl FromStr for StatusPedido {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "P" => Ok(Self::Pendente),
+            "A" => Ok(Self::Pago),
+            "F" => Ok(Self::Faturado),
+            "E" => Ok(Self::Enviado),
+            "R" => Ok(Self::Entregue),
+            "C" => Ok(Self::Cancelado),
+            "D" => Ok(Self::Devolvido),
+            _ => Err(AppMessage::new(&format!("Status de pedido \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+impl fmt::Display for StatusPedido {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.descricao())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FormaPagamento {
+    #[serde(rename = "B")]
+    Boleto,
+    #[serde(rename = "P")]
+    Pix,
+    #[serde(rename = "D")]
+    Debito,
+    #[serde(rename = "C")]
+    Credito,
+}
+
+impl FormaPagamento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Boleto => "B",
+            Self::Pix => "P",
+            Self::Debito => "D",
+            Self::Credito => "C",
+        }
+    }
+
+    pub fn descricao(&self) -> &'static str {
+        match self {
+            Self::Boleto => "Boleto bancário",
+            Self::Pix => "PIX",
+            Self::Debito => "Cartão de débito",
+            Self::Credito => "Cartão de crédito",
+        }
+    }
+}
+
+impl FromStr for FormaPagamento {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "B" => Ok(Self::Boleto),
+            "P" => Ok(Self::Pix),
+            "D" => Ok(Self::Debito),
+            "C" => Ok(Self::Credito),
+            _ => Err(AppMessage::new(&format!("Forma de pagamento \"{}\" inválida", value), 400)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusPagamento {
+    #[serde(rename = "P")]
+    Pendente,
+    #[serde(rename = "A")]
+    Aprovado,
+    #[serde(rename = "C")]
+    Cancelado,
+    #[serde(rename = "E")]
+    EstornoSolicitado,
+    #[serde(rename = "R")]
+    Recusado,
+    #[serde(rename = "S")]
+    Estornado,
+    #[serde(rename = "K")]
+    Chargeback,
+}
+
+impl StatusPagamento {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pendente => "P",
+            Self::Aprovado => "A",
+            Self::Cancelado => "C",
+            Self::EstornoSolicitado => "E",
+            Self::Recusado => "R",
+            Self::Estornado => "S",
+            Self::Chargeback => "K",
+        }
+    }
+}
+
+impl FromStr for StatusPagamento {
+    type Err = AppMessage;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_uppercase().as_str() {
+            "P" => Ok(Self::Pendente),
+            "A" => Ok(Self::Aprovado),
+            "C" => Ok(Self::Cancelado),
+            "E" => Ok(Self::EstornoSolicitado),
+            "R" => Ok(Self::Recusado),
+            "S" => Ok(Self::Estornado),
+            "K" => Ok(Self::Chargeback),
+            _ => Err(AppMessage::new(&format!("Status de pagamento \"{}\" inválido", value), 400)),
+        }
+    }
+}
+
+#[derive(Queryable, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricoStatusPedido {
+    pub id: String,
+    #[diesel(column_name = "idPedido")]
+    pub id_pedido: String,
+    #[diesel(column_name = "statusAnterior")]
+    pub status_anterior: Option<String>,
+    #[diesel(column_name = "statusNovo")]
+    pub status_novo: String,
+    #[diesel(column_name = "alteradoPor")]
+    pub alterado_por: String,
+    pub motivo: Option<String>,
+    #[diesel(column_name = "createdAt")]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct AlterarStatusPayload {
+    pub status: String,
+    #[validate(length(max = 255, message = "Motivo deve ter no máximo 255 caracteres"))]
+    pub motivo: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct CancelarPedidoPayload {
+    #[validate(length(max = 255, message = "Motivo deve ter no máximo 255 caracteres"))]
+    pub motivo: Option<String>,
+}
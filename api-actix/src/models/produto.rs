@@ -4,21 +4,12 @@ use diesel::{Identifiable, Insertable, Queryable, QueryableByName, Selectable};
 use serde::{Deserialize, Serialize};
 use crate::schema::produtos;
 
-#[derive(Deserialize)]
-pub struct ProdutoPayload {
-    #[serde(rename = "skuProduto")]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflitoEstoque {
     pub sku_produto: String,
-    pub quantidade: i32,
-    #[serde(rename = "valorUnitario")]
-    pub valor_unitario: f64,
-    #[serde(rename = "valorBruto")]
-    pub valor_bruto: f64,
-    #[serde(rename = "valorDesconto")]
-    pub valor_desconto: f64,
-    #[serde(rename = "valorLiquido")]
-    pub valor_liquido: f64,
-    #[serde(rename = "valorFrete")]
-    pub valor_frete: f64,
+    pub quantidade_solicitada: i32,
+    pub estoque_disponivel: BigDecimal,
 }
 
 #[derive(Queryable, QueryableByName, Debug, Identifiable, Serialize, Selectable, Deserialize, Insertable)]
@@ -42,6 +33,15 @@ pub struct Produto {
     pub created_at: NaiveDateTime,
     #[diesel(column_name = "updatedAt")]
     pub updated_at: NaiveDateTime,
+    #[diesel(column_name = "pesoKg")]
+    pub peso_kg: BigDecimal,
+    #[diesel(column_name = "alturaCm")]
+    pub altura_cm: BigDecimal,
+    #[diesel(column_name = "larguraCm")]
+    pub largura_cm: BigDecimal,
+    #[diesel(column_name = "comprimentoCm")]
+    pub comprimento_cm: BigDecimal,
+    pub ncm: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -0,0 +1,119 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::Address;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use crate::configs::email::{EmailConfig, SegurancaSmtp};
+use crate::dal::cliente_dal::ClienteDal;
+use crate::dal::pedido_dal::PedidoDal;
+use crate::db::DbPool;
+use crate::models::cliente::ClienteResponse;
+use crate::models::outbox::{EventoOutbox, TipoEvento};
+use crate::models::pedido::HistoricoStatusPedido;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::utils::emails::{self, Mensagem};
+use super::EventSink;
+
+// Notificações ao cliente por SMTP. Os dados do pedido são lidos na hora do envio, então uma
+// nova tentativa manda a situação mais recente. Falhas temporárias (banco, servidor SMTP fora do
+// ar) voltam para o outbox; e-mail inválido, payload ilegível ou recusa definitiva do SMTP saem com 4xx
+pub struct EmailSink {
+    pool: Arc<DbPool>,
+    transporte: SmtpTransport,
+    remetente: Mailbox,
+    nome_loja: String,
+}
+
+impl EmailSink {
+    pub fn new(pool: Arc<DbPool>, config: &EmailConfig) -> Result<Self, AppMessage> {
+        let remetente = config.remetente.parse::<Mailbox>()
+            .map_err(|e| AppMessage::new(&format!("EMAIL_REMETENTE inválido: {}", e), 500))?;
+
+        let builder = match config.smtp_seguranca {
+            SegurancaSmtp::Nenhuma => Ok(SmtpTransport::builder_dangerous(&config.smtp_host)),
+            SegurancaSmtp::StartTls => SmtpTransport::starttls_relay(&config.smtp_host),
+            SegurancaSmtp::Tls => SmtpTransport::relay(&config.smtp_host),
+        }
+            .map_err(|e| AppMessage::new(&format!("Configuração SMTP inválida: {}", e), 500))?;
+
+        let mut builder = builder
+            .port(config.smtp_porta)
+            .timeout(Some(Duration::from_secs(config.smtp_timeout_segundos)));
+
+        if let (Some(usuario), Some(senha)) = (&config.smtp_usuario, &config.smtp_senha) {
+            builder = builder.credentials(Credentials::new(usuario.clone(), senha.clone()));
+        }
+
+        Ok(Self {
+            pool,
+            transporte: builder.build(),
+            remetente,
+            nome_loja: config.nome_loja.clone(),
+        })
+    }
+
+    fn montar(&self, tipo: TipoEvento, evento: &EventoOutbox) -> Result<Option<(ClienteResponse, Mensagem)>, AppMessage> {
+        if tipo == TipoEvento::ClienteCadastrado {
+            let cliente: ClienteResponse = serde_json::from_str(&evento.payload)
+                .map_err(|e| AppMessage::new(&format!("Payload do evento inválido: {}", e), 422))?;
+            let mensagem = emails::conta_criada(&self.nome_loja, &cliente);
+            return Ok(Some((cliente, mensagem)));
+        }
+
+        let mut connection = self.pool.get()
+            .map_err(|e| AppMessage::new(&format!("Database connection error: {}", e), 500))?;
+
+        let comprovante = PedidoDal::comprovante_on(&mut connection, &evento.id_agregado, None)
+            .map_err(|e| match e {
+                ApiError::App(mensagem) => mensagem,
+                outro => AppMessage::new(&outro.to_string(), 500),
+            })?;
+        let cliente = ClienteResponse::from(ClienteDal::find_on(&mut connection, &comprovante.pedido.id_cliente)?);
+
+        let mensagem = match tipo {
+            TipoEvento::PedidoCriado => Some(emails::pedido_recebido(&self.nome_loja, &cliente, &comprovante)),
+            TipoEvento::PagamentoConfirmado => Some(emails::pagamento_recebido(&self.nome_loja, &cliente, &comprovante)),
+            TipoEvento::StatusPedidoAlterado => {
+                let historico: HistoricoStatusPedido = serde_json::from_str(&evento.payload)
+                    .map_err(|e| AppMessage::new(&format!("Payload do evento inválido: {}", e), 422))?;
+                emails::status_alterado(&self.nome_loja, &cliente, &comprovante, &historico)
+            }
+            TipoEvento::ClienteCadastrado => None,
+        };
+
+        Ok(mensagem.map(|mensagem| (cliente, mensagem)))
+    }
+}
+
+impl EventSink for EmailSink {
+    fn nome(&self) -> &'static str {
+        "email"
+    }
+
+    fn entregar(&self, evento: &EventoOutbox) -> Result<(), AppMessage> {
+        let tipo = TipoEvento::from_str(&evento.tipo)?;
+        let Some((cliente, mensagem)) = self.montar(tipo, evento)? else {
+            return Ok(());
+        };
+
+        let endereco = cliente.email.parse::<Address>()
+            .map_err(|e| AppMessage::new(&format!("E-mail do cliente inválido: {}", e), 422))?;
+        let destinatario = Mailbox::new(Some(cliente.nome.trim().to_string()), endereco);
+
+        let email = Message::builder()
+            .from(self.remetente.clone())
+            .to(destinatario)
+            .subject(mensagem.assunto)
+            .header(ContentType::TEXT_PLAIN)
+            .body(mensagem.texto)
+            .map_err(|e| AppMessage::new(&format!("Erro ao montar e-mail: {}", e), 422))?;
+
+        // Respostas 5xx do SMTP (caixa inexistente, destinatário recusado) não mudam com novas tentativas
+        self.transporte.send(&email)
+            .map(|_| ())
+            .map_err(|e| AppMessage::new(&format!("Erro ao enviar e-mail: {}", e), if e.is_permanent() { 422 } else { 502 }))
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use crate::configs::email::EmailConfig;
+use crate::configs::outbox::OutboxConfig;
+use crate::db::DbPool;
+use crate::models::outbox::{EventoOutbox, TipoEvento};
+use crate::utils::app_message::AppMessage;
+
+pub mod email;
+pub mod registro;
+
+// Os destinos rodam fora de qualquer transação, em threads bloqueantes do worker do outbox.
+// A entrega é pelo menos uma vez: quem consome usa o id do evento para descartar repetições.
+// Erros 4xx são definitivos e marcam o evento como falho sem esperar as novas tentativas
+pub trait EventSink: Send + Sync {
+    fn nome(&self) -> &'static str;
+
+    fn aceita(&self, _tipo: TipoEvento) -> bool {
+        true
+    }
+
+    fn entregar(&self, evento: &EventoOutbox) -> Result<(), AppMessage>;
+}
+
+// Configuração inválida de um destino derruba a subida do servidor: seguir sem ele marcaria
+// os eventos como entregues sem nunca passar por lá
+pub fn sinks_configurados(config: &OutboxConfig, pool: &Arc<DbPool>) -> Vec<Box<dyn EventSink>> {
+    config.sinks.iter()
+        .filter_map(|nome| match nome.as_str() {
+            "log" => Some(Box::new(registro::LogSink) as Box<dyn EventSink>),
+            "email" => {
+                let sink = email::EmailSink::new(Arc::clone(pool), &EmailConfig::new())
+                    .unwrap_or_else(|e| panic!("Destino de eventos \"email\" mal configurado: {}", e));
+                Some(Box::new(sink) as Box<dyn EventSink>)
+            }
+            _ => {
+                log::warn!("Destino de eventos \"{}\" desconhecido em OUTBOX_SINKS, ignorado", nome);
+                None
+            }
+        })
+        .collect()
+}
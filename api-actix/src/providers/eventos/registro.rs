@@ -0,0 +1,17 @@
+use crate::models::outbox::EventoOutbox;
+use crate::utils::app_message::AppMessage;
+use super::EventSink;
+
+// Escreve os eventos no log da aplicação; útil em desenvolvimento e como trilha mínima
+pub struct LogSink;
+
+impl EventSink for LogSink {
+    fn nome(&self) -> &'static str {
+        "log"
+    }
+
+    fn entregar(&self, evento: &EventoOutbox) -> Result<(), AppMessage> {
+        log::info!("Evento {} ({}) de {}: {}", evento.tipo, evento.id, evento.id_agregado, evento.payload);
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod eventos;
+pub mod pagamento;
\ No newline at end of file
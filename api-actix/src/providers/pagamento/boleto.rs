@@ -0,0 +1,58 @@
+use chrono::{Duration, Utc};
+use crate::configs::pagamento::PagamentoConfig;
+use crate::utils::app_message::AppMessage;
+use crate::utils::boleto::{self, DadosBoleto};
+use super::{PaymentProvider, ResultadoPagamento, SolicitacaoEstorno, SolicitacaoPagamento};
+
+pub struct BoletoProvider;
+
+impl PaymentProvider for BoletoProvider {
+    fn nome(&self) -> &'static str {
+        "boleto"
+    }
+
+    fn processar(&self, solicitacao: &SolicitacaoPagamento) -> Result<ResultadoPagamento, AppMessage> {
+        Ok(ResultadoPagamento {
+            boleto: Some(gerar_linha_digitavel(solicitacao)?),
+            ..ResultadoPagamento::pendente()
+        })
+    }
+
+    // Boleto pago não tem como ser revertido no banco: o valor volta ao cliente por transferência
+    fn estornar(&self, solicitacao: &SolicitacaoEstorno) -> Result<(), AppMessage> {
+        log::info!(
+            "Estorno de {} do pedido {} encaminhado para devolução por transferência (devolução {})",
+            solicitacao.valor, solicitacao.id_pedido, solicitacao.id_devolucao
+        );
+        Ok(())
+    }
+}
+
+pub(crate) fn gerar_linha_digitavel(solicitacao: &SolicitacaoPagamento) -> Result<String, AppMessage> {
+    let config = PagamentoConfig::new();
+
+    let campo_livre = boleto::montar_campo_livre(
+        &config.boleto_agencia,
+        &config.boleto_carteira,
+        &nosso_numero(solicitacao.id_pedido),
+        &config.boleto_conta
+    )?;
+
+    let vencimento = Utc::now().date_naive() + Duration::days(config.boleto_dias_vencimento);
+
+    let gerado = boleto::gerar(&DadosBoleto {
+        banco: &config.boleto_banco,
+        vencimento: Some(vencimento),
+        valor: solicitacao.valor_total,
+        campo_livre: &campo_livre,
+    })?;
+
+    Ok(gerado.linha_digitavel)
+}
+
+// Nosso número de 11 dígitos derivado do uuid do pedido, estável entre reemissões
+fn nosso_numero(id_pedido: &str) -> String {
+    let hex: String = id_pedido.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    let numero = u128::from_str_radix(&hex, 16).unwrap_or(0) % 100_000_000_000;
+    format!("{:011}", numero)
+}
@@ -0,0 +1,28 @@
+use crate::utils::app_message::AppMessage;
+use super::{referencia_pedido, PaymentProvider, ResultadoPagamento, SolicitacaoEstorno, SolicitacaoPagamento};
+
+// Sem adquirente integrado a transação fica pendente com o TID reservado;
+// a confirmação chega depois pelo retorno do gateway
+pub struct CartaoProvider;
+
+impl PaymentProvider for CartaoProvider {
+    fn nome(&self) -> &'static str {
+        "cartao"
+    }
+
+    fn processar(&self, solicitacao: &SolicitacaoPagamento) -> Result<ResultadoPagamento, AppMessage> {
+        Ok(ResultadoPagamento {
+            tid: Some(format!("T{}", referencia_pedido(solicitacao.id_pedido, 19))),
+            ..ResultadoPagamento::pendente()
+        })
+    }
+
+    // O cancelamento total ou parcial é feito sobre o TID da captura
+    fn estornar(&self, solicitacao: &SolicitacaoEstorno) -> Result<(), AppMessage> {
+        let tid = solicitacao.tid
+            .ok_or_else(|| AppMessage::new("Pagamento com cartão sem TID não pode ser estornado", 422))?;
+
+        log::info!("Estorno de {} solicitado ao gateway para o TID {} (devolução {})", solicitacao.valor, tid, solicitacao.id_devolucao);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::models::pedido::FormaPagamento;
+use crate::utils::app_message::AppMessage;
+use crate::utils::money;
+use super::{boleto, pix};
+use super::{referencia_pedido, PaymentProvider, ResultadoPagamento, SolicitacaoEstorno, SolicitacaoPagamento, StatusTransacao};
+
+// Provedor local e determinístico, decidido pelos centavos do valor total:
+// final ,01 recusa, final ,02 fica pendente e qualquer outro valor é aprovado.
+// No estorno vale o mesmo para o valor devolvido: final ,01 é recusado
+pub struct MockProvider;
+
+pub const CENTAVOS_RECUSA: i64 = 1;
+pub const CENTAVOS_PENDENTE: i64 = 2;
+
+impl PaymentProvider for MockProvider {
+    fn nome(&self) -> &'static str {
+        "mock"
+    }
+
+    fn processar(&self, solicitacao: &SolicitacaoPagamento) -> Result<ResultadoPagamento, AppMessage> {
+        let status = match money::centavos(solicitacao.valor_total).rem_euclid(100) {
+            CENTAVOS_RECUSA => StatusTransacao::Recusada,
+            CENTAVOS_PENDENTE => StatusTransacao::Pendente,
+            _ => StatusTransacao::Aprovada,
+        };
+
+        let referencia = referencia_pedido(solicitacao.id_pedido, 16);
+        let (tid, boleto, pix) = match solicitacao.forma_pagamento {
+            FormaPagamento::Debito | FormaPagamento::Credito => (Some(format!("MOCK{}", referencia)), None, None),
+            FormaPagamento::Boleto => (None, Some(boleto::gerar_linha_digitavel(solicitacao)?), None),
+            FormaPagamento::Pix => (None, None, Some(pix::gerar_payload_pix(solicitacao)?)),
+        };
+
+        Ok(ResultadoPagamento {
+            status,
+            tid,
+            boleto,
+            pix,
+            motivo: (status == StatusTransacao::Recusada)
+                .then(|| "Transação recusada pelo emissor (simulação)".to_string()),
+        })
+    }
+
+    fn estornar(&self, solicitacao: &SolicitacaoEstorno) -> Result<(), AppMessage> {
+        if money::centavos(solicitacao.valor).rem_euclid(100) == CENTAVOS_RECUSA {
+            return Err(AppMessage::new("Estorno recusado pelo provedor (simulação)", 422));
+        }
+        Ok(())
+    }
+}
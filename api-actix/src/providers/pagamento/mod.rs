@@ -0,0 +1,87 @@
+use bigdecimal::BigDecimal;
+use crate::configs::pagamento::PagamentoConfig;
+use crate::models::pedido::FormaPagamento;
+use crate::utils::app_message::AppMessage;
+
+pub mod boleto;
+pub mod cartao;
+pub mod mock;
+pub mod pix;
+
+pub struct SolicitacaoPagamento<'a> {
+    pub id_pedido: &'a str,
+    pub id_cliente: &'a str,
+    pub forma_pagamento: FormaPagamento,
+    pub numero_parcelas: i16,
+    pub valor_total: &'a BigDecimal,
+}
+
+// Devolução de parte ou de todo o valor pago, pedida ao aprovar uma devolução de itens
+pub struct SolicitacaoEstorno<'a> {
+    pub id_pedido: &'a str,
+    pub id_devolucao: &'a str,
+    pub forma_pagamento: FormaPagamento,
+    pub tid: Option<&'a str>,
+    pub valor: &'a BigDecimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusTransacao {
+    Aprovada,
+    Recusada,
+    Pendente,
+}
+
+#[derive(Debug)]
+pub struct ResultadoPagamento {
+    pub status: StatusTransacao,
+    pub tid: Option<String>,
+    pub boleto: Option<String>,
+    pub pix: Option<String>,
+    pub motivo: Option<String>,
+}
+
+impl ResultadoPagamento {
+    pub fn pendente() -> Self {
+        Self {
+            status: StatusTransacao::Pendente,
+            tid: None,
+            boleto: None,
+            pix: None,
+            motivo: None,
+        }
+    }
+}
+
+pub trait PaymentProvider: Send + Sync {
+    fn nome(&self) -> &'static str;
+
+    // Chamado fora de transação, com o pedido já gravado e o estoque reservado; uma recusa cancela o pedido.
+    // O id do pedido é a chave de idempotência da cobrança: repetir a chamada não pode cobrar duas vezes
+    fn processar(&self, solicitacao: &SolicitacaoPagamento) -> Result<ResultadoPagamento, AppMessage>;
+
+    // Também roda fora de transação, antes de a aprovação da devolução ser gravada: um erro impede a aprovação.
+    // O id da devolução é a chave de idempotência do estorno, que só é dado como feito quando o webhook Estornado chega
+    fn estornar(&self, solicitacao: &SolicitacaoEstorno) -> Result<(), AppMessage>;
+}
+
+pub fn provider_para(forma_pagamento: FormaPagamento) -> Box<dyn PaymentProvider> {
+    if PagamentoConfig::new().usar_mock {
+        return Box::new(mock::MockProvider);
+    }
+
+    match forma_pagamento {
+        FormaPagamento::Boleto => Box::new(boleto::BoletoProvider),
+        FormaPagamento::Pix => Box::new(pix::PixProvider),
+        FormaPagamento::Debito | FormaPagamento::Credito => Box::new(cartao::CartaoProvider),
+    }
+}
+
+// Referência curta e estável derivada do pedido, usada como nosso número, txid e TID
+pub(crate) fn referencia_pedido(id_pedido: &str, tamanho: usize) -> String {
+    id_pedido.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .take(tamanho)
+        .collect()
+}
@@ -0,0 +1,43 @@
+use crate::configs::pagamento::PagamentoConfig;
+use crate::utils::app_message::AppMessage;
+use crate::utils::pix::{self, DadosPix};
+use super::{referencia_pedido, PaymentProvider, ResultadoPagamento, SolicitacaoEstorno, SolicitacaoPagamento};
+
+pub struct PixProvider;
+
+impl PaymentProvider for PixProvider {
+    fn nome(&self) -> &'static str {
+        "pix"
+    }
+
+    fn processar(&self, solicitacao: &SolicitacaoPagamento) -> Result<ResultadoPagamento, AppMessage> {
+        Ok(ResultadoPagamento {
+            pix: Some(gerar_payload_pix(solicitacao)?),
+            ..ResultadoPagamento::pendente()
+        })
+    }
+
+    // Devolução Pix pedida ao PSP sobre o txid da cobrança
+    fn estornar(&self, solicitacao: &SolicitacaoEstorno) -> Result<(), AppMessage> {
+        log::info!(
+            "Devolução Pix de {} solicitada ao PSP para o txid {} (devolução {})",
+            solicitacao.valor, referencia_pedido(solicitacao.id_pedido, 25), solicitacao.id_devolucao
+        );
+        Ok(())
+    }
+}
+
+// BR Code "copia e cola" com o valor do pedido; o txid do BR Code estático aceita
+// até 25 caracteres, então usamos o início do uuid do pedido sem hífens
+pub(crate) fn gerar_payload_pix(solicitacao: &SolicitacaoPagamento) -> Result<String, AppMessage> {
+    let config = PagamentoConfig::new();
+    let txid = referencia_pedido(solicitacao.id_pedido, 25);
+
+    pix::gerar_payload(&DadosPix {
+        chave: &config.pix_chave,
+        nome_recebedor: &config.pix_nome_recebedor,
+        cidade: &config.pix_cidade,
+        valor: Some(solicitacao.valor_total),
+        txid: &txid,
+    })
+}
@@ -0,0 +1,29 @@
+use actix_web::web;
+use crate::controllers::{cupom_controller, devolucao_controller, feriado_controller, frete_controller, nfe_controller, outbox_controller, pedido_controller};
+use crate::middlewares::is_admin::AdminAuthentication;
+
+pub fn admin_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(AdminAuthentication)
+            .service(pedido_controller::alterar_status)
+            .service(pedido_controller::get_comprovante_admin)
+            .service(nfe_controller::emitir)
+            .service(nfe_controller::get_xml_admin)
+            .service(cupom_controller::create)
+            .service(cupom_controller::get_all)
+            .service(feriado_controller::create)
+            .service(feriado_controller::get_all)
+            .service(feriado_controller::delete)
+            .service(frete_controller::create)
+            .service(frete_controller::get_all)
+            .service(frete_controller::update)
+            .service(frete_controller::delete)
+            .service(outbox_controller::get_all)
+            .service(outbox_controller::reprocessar)
+            .service(devolucao_controller::get_all)
+            .service(devolucao_controller::get_by_id)
+            .service(devolucao_controller::aprovar)
+            .service(devolucao_controller::recusar)
+    );
+}
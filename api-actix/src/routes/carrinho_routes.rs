@@ -0,0 +1,16 @@
+use actix_web::web;
+use crate::controllers::carrinho_controller;
+use crate::middlewares::is_authenticated::Authentication;
+
+pub fn carrinho_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("")
+            .wrap(Authentication)
+            .service(carrinho_controller::get)
+            .service(carrinho_controller::limpar)
+            .service(carrinho_controller::adicionar_item)
+            .service(carrinho_controller::atualizar_item)
+            .service(carrinho_controller::remover_item)
+            .service(carrinho_controller::checkout)
+    );
+}
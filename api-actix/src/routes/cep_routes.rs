@@ -0,0 +1,6 @@
+use actix_web::web;
+use crate::controllers::cep_controller;
+
+pub fn cep_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(cep_controller::consultar);
+}
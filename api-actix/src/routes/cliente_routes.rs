@@ -1,7 +1,17 @@
 use actix_web::web;
-use crate::controllers::cliente_controller;
+use crate::controllers::{cliente_controller, endereco_controller};
+use crate::middlewares::is_authenticated::Authentication;
 
 pub fn cliente_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(cliente_controller::create)
-        .service(cliente_controller::login);
+        .service(cliente_controller::login)
+        .service(
+            web::scope("/me/enderecos")
+                .wrap(Authentication)
+                .service(endereco_controller::get_all)
+                .service(endereco_controller::create)
+                .service(endereco_controller::get_by_id)
+                .service(endereco_controller::update)
+                .service(endereco_controller::delete)
+        );
 }
\ No newline at end of file
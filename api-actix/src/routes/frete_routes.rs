@@ -0,0 +1,6 @@
+use actix_web::web;
+use crate::controllers::frete_controller;
+
+pub fn frete_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(frete_controller::cotacao);
+}
@@ -5,6 +5,11 @@ pub mod produto_routes;
 pub mod home_routes;
 mod cliente_routes;
 mod pedido_routes;
+mod pagamento_routes;
+mod carrinho_routes;
+mod cep_routes;
+mod frete_routes;
+mod admin_routes;
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -22,5 +27,20 @@ pub fn routes(cfg: &mut web::ServiceConfig) {
     ).service(
         web::scope("/pedidos")
             .configure(pedido_routes::pedido_routes)
+    ).service(
+        web::scope("/carrinho")
+            .configure(carrinho_routes::carrinho_routes)
+    ).service(
+        web::scope("/ceps")
+            .configure(cep_routes::cep_routes)
+    ).service(
+        web::scope("/frete")
+            .configure(frete_routes::frete_routes)
+    ).service(
+        web::scope("/pagamentos")
+            .configure(pagamento_routes::pagamento_routes)
+    ).service(
+        web::scope("/admin")
+            .configure(admin_routes::admin_routes)
     );
 }
\ No newline at end of file
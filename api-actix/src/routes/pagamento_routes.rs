@@ -0,0 +1,7 @@
+use actix_web::web;
+use crate::controllers::pagamento_controller;
+
+// Sem autenticação de cliente: o webhook é autenticado pela assinatura HMAC
+pub fn pagamento_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(pagamento_controller::webhook);
+}
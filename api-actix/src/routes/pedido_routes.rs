@@ -1,5 +1,5 @@
 use actix_web::web;
-use crate::controllers::pedido_controller;
+use crate::controllers::{devolucao_controller, nfe_controller, pedido_controller};
 use crate::middlewares::is_authenticated::Authentication;
 
 pub fn pedido_routes(cfg: &mut web::ServiceConfig) {
@@ -7,5 +7,16 @@ pub fn pedido_routes(cfg: &mut web::ServiceConfig) {
         web::scope("")
             .wrap(Authentication)
             .service(pedido_controller::create)
+            .service(pedido_controller::simular)
+            .service(pedido_controller::get_all)
+            .service(pedido_controller::get_by_id)
+            .service(pedido_controller::get_historico)
+            .service(pedido_controller::get_boleto)
+            .service(pedido_controller::get_pix_qrcode)
+            .service(pedido_controller::get_comprovante)
+            .service(nfe_controller::get_xml)
+            .service(pedido_controller::cancelar)
+            .service(devolucao_controller::create)
+            .service(devolucao_controller::get_by_pedido)
     );
 }
\ No newline at end of file
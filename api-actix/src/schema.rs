@@ -11,6 +11,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chavesIdempotencia (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idCliente -> Varchar,
+        #[max_length = 255]
+        chave -> Varchar,
+        #[max_length = 64]
+        hashRequisicao -> Bpchar,
+        statusCode -> Nullable<Int4>,
+        resposta -> Nullable<Text>,
+        createdAt -> Timestamp,
+        expiraEm -> Timestamp,
+    }
+}
+
 diesel::table! {
     clientes (id) {
         #[max_length = 36]
@@ -39,6 +56,107 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    cupons (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 30]
+        codigo -> Varchar,
+        #[max_length = 120]
+        descricao -> Nullable<Varchar>,
+        #[max_length = 1]
+        tipoDesconto -> Bpchar,
+        valorDesconto -> Numeric,
+        valorMinimoPedido -> Numeric,
+        validoDe -> Timestamp,
+        validoAte -> Timestamp,
+        limiteUsoCliente -> Nullable<Int4>,
+        limiteUsoTotal -> Nullable<Int4>,
+        quantidadeUtilizada -> Int4,
+        ativo -> Bool,
+        createdAt -> Timestamp,
+        updatedAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    cuponsRestricoes (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idCupom -> Varchar,
+        #[max_length = 36]
+        idCategoria -> Nullable<Varchar>,
+        skuProduto -> Nullable<Text>,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    cuponsUtilizados (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idCupom -> Varchar,
+        #[max_length = 36]
+        idCliente -> Varchar,
+        #[max_length = 36]
+        idPedido -> Varchar,
+        valorDesconto -> Numeric,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    devolucoes (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idPedido -> Varchar,
+        #[max_length = 36]
+        idCliente -> Varchar,
+        #[max_length = 1]
+        status -> Bpchar,
+        #[max_length = 500]
+        observacao -> Nullable<Varchar>,
+        valorReembolso -> Numeric,
+        createdAt -> Timestamp,
+        updatedAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    enderecosCliente (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idCliente -> Varchar,
+        #[max_length = 30]
+        apelido -> Nullable<Varchar>,
+        #[max_length = 120]
+        nomeRemetente -> Varchar,
+        #[max_length = 8]
+        cep -> Bpchar,
+        #[max_length = 60]
+        logradouro -> Varchar,
+        #[max_length = 10]
+        numero -> Varchar,
+        #[max_length = 60]
+        complemento -> Nullable<Varchar>,
+        #[max_length = 60]
+        bairro -> Varchar,
+        #[max_length = 60]
+        cidade -> Nullable<Varchar>,
+        #[max_length = 7]
+        codigoIbgeCidade -> Bpchar,
+        #[max_length = 2]
+        codigoIbgeUF -> Bpchar,
+        padrao -> Bool,
+        createdAt -> Timestamp,
+        updatedAt -> Timestamp,
+    }
+}
+
 diesel::table! {
     enderecosEntrega (id) {
         #[max_length = 36]
@@ -57,6 +175,8 @@ diesel::table! {
         complemento -> Nullable<Varchar>,
         #[max_length = 60]
         bairro -> Varchar,
+        #[max_length = 60]
+        cidade -> Nullable<Varchar>,
         #[max_length = 7]
         codigoIbgeCidade -> Bpchar,
         #[max_length = 2]
@@ -66,6 +186,139 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    eventosOutbox (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 40]
+        tipo -> Varchar,
+        #[max_length = 36]
+        idAgregado -> Varchar,
+        payload -> Text,
+        #[max_length = 1]
+        status -> Bpchar,
+        tentativas -> Int4,
+        entregueA -> Array<Text>,
+        ultimoErro -> Nullable<Text>,
+        proximaTentativa -> Timestamp,
+        processadoEm -> Nullable<Timestamp>,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    eventosPagamento (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 255]
+        idEvento -> Varchar,
+        #[max_length = 36]
+        idPedido -> Varchar,
+        #[max_length = 20]
+        tipo -> Varchar,
+        payload -> Text,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    feriados (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 60]
+        descricao -> Varchar,
+        dia -> Int2,
+        mes -> Int2,
+        ano -> Nullable<Int2>,
+        #[max_length = 2]
+        codigoIbgeUF -> Nullable<Varchar>,
+        #[max_length = 7]
+        codigoIbgeCidade -> Nullable<Varchar>,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    historicoStatusDevolucao (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idDevolucao -> Varchar,
+        #[max_length = 1]
+        statusAnterior -> Nullable<Bpchar>,
+        #[max_length = 1]
+        statusNovo -> Bpchar,
+        #[max_length = 60]
+        alteradoPor -> Varchar,
+        #[max_length = 255]
+        motivo -> Nullable<Varchar>,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    historicoStatusPedido (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idPedido -> Varchar,
+        #[max_length = 1]
+        statusAnterior -> Nullable<Bpchar>,
+        #[max_length = 1]
+        statusNovo -> Bpchar,
+        #[max_length = 60]
+        alteradoPor -> Varchar,
+        #[max_length = 255]
+        motivo -> Nullable<Varchar>,
+        createdAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    itensCarrinho (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idCliente -> Varchar,
+        skuProduto -> Text,
+        quantidade -> Int4,
+        createdAt -> Timestamp,
+        updatedAt -> Timestamp,
+    }
+}
+
+diesel::table! {
+    itensDevolucao (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idDevolucao -> Varchar,
+        #[max_length = 36]
+        idProdutoPedido -> Varchar,
+        skuProduto -> Text,
+        quantidade -> Numeric,
+        #[max_length = 30]
+        motivo -> Varchar,
+        valorReembolso -> Numeric,
+    }
+}
+
+diesel::table! {
+    notasFiscais (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 36]
+        idPedido -> Varchar,
+        ambiente -> Int2,
+        serie -> Int4,
+        numero -> Int4,
+        #[max_length = 44]
+        chaveAcesso -> Bpchar,
+        xml -> Text,
+        createdAt -> Timestamp,
+    }
+}
+
 diesel::table! {
     pagamentos (id) {
         id -> Text,
@@ -82,6 +335,9 @@ diesel::table! {
         tid -> Nullable<Text>,
         createdAt -> Timestamp,
         updatedAt -> Timestamp,
+        #[max_length = 1]
+        status -> Bpchar,
+        valorEstornado -> Numeric,
     }
 }
 
@@ -100,6 +356,8 @@ diesel::table! {
         dataEntrega -> Date,
         createdAt -> Timestamp,
         updatedAt -> Timestamp,
+        #[max_length = 20]
+        modalidadeFrete -> Varchar,
     }
 }
 
@@ -119,6 +377,12 @@ diesel::table! {
         qtdvendas -> Int4,
         createdAt -> Timestamp,
         updatedAt -> Timestamp,
+        pesoKg -> Numeric,
+        alturaCm -> Numeric,
+        larguraCm -> Numeric,
+        comprimentoCm -> Numeric,
+        #[max_length = 8]
+        ncm -> Nullable<Bpchar>,
     }
 }
 
@@ -137,10 +401,58 @@ diesel::table! {
         valorLiquido -> Numeric,
         createdAt -> Timestamp,
         updatedAt -> Timestamp,
+        valorUnitarioOriginal -> Numeric,
+        pctOferta -> Numeric,
+        #[max_length = 30]
+        promocao -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    regrasFrete (id) {
+        #[max_length = 36]
+        id -> Varchar,
+        #[max_length = 60]
+        descricao -> Varchar,
+        #[max_length = 2]
+        codigoIbgeUF -> Nullable<Varchar>,
+        #[max_length = 8]
+        cepInicial -> Nullable<Bpchar>,
+        #[max_length = 8]
+        cepFinal -> Nullable<Bpchar>,
+        pesoMinimo -> Numeric,
+        pesoMaximo -> Nullable<Numeric>,
+        valorFrete -> Numeric,
+        valorMinimoFreteGratis -> Nullable<Numeric>,
+        prioridade -> Int4,
+        ativo -> Bool,
+        createdAt -> Timestamp,
+        updatedAt -> Timestamp,
+        #[max_length = 20]
+        modalidade -> Varchar,
+        prazoDiasUteis -> Nullable<Int4>,
     }
 }
 
+diesel::joinable!(chavesIdempotencia -> clientes (idCliente));
+diesel::joinable!(cuponsRestricoes -> categorias (idCategoria));
+diesel::joinable!(cuponsRestricoes -> cupons (idCupom));
+diesel::joinable!(cuponsRestricoes -> produtos (skuProduto));
+diesel::joinable!(cuponsUtilizados -> clientes (idCliente));
+diesel::joinable!(cuponsUtilizados -> cupons (idCupom));
+diesel::joinable!(cuponsUtilizados -> pedidos (idPedido));
+diesel::joinable!(devolucoes -> clientes (idCliente));
+diesel::joinable!(devolucoes -> pedidos (idPedido));
+diesel::joinable!(enderecosCliente -> clientes (idCliente));
 diesel::joinable!(enderecosEntrega -> pedidos (idPedido));
+diesel::joinable!(eventosPagamento -> pedidos (idPedido));
+diesel::joinable!(historicoStatusDevolucao -> devolucoes (idDevolucao));
+diesel::joinable!(historicoStatusPedido -> pedidos (idPedido));
+diesel::joinable!(itensCarrinho -> clientes (idCliente));
+diesel::joinable!(itensCarrinho -> produtos (skuProduto));
+diesel::joinable!(itensDevolucao -> devolucoes (idDevolucao));
+diesel::joinable!(itensDevolucao -> produtosPedido (idProdutoPedido));
+diesel::joinable!(notasFiscais -> pedidos (idPedido));
 diesel::joinable!(pagamentos -> pedidos (idPedido));
 diesel::joinable!(pedidos -> clientes (idCliente));
 diesel::joinable!(produtos -> categorias (idCategoria));
@@ -149,10 +461,25 @@ diesel::joinable!(produtosPedido -> produtos (skuProduto));
 
 diesel::allow_tables_to_appear_in_same_query!(
     categorias,
+    chavesIdempotencia,
     clientes,
+    cupons,
+    cuponsRestricoes,
+    cuponsUtilizados,
+    devolucoes,
+    enderecosCliente,
     enderecosEntrega,
+    eventosOutbox,
+    eventosPagamento,
+    feriados,
+    historicoStatusDevolucao,
+    historicoStatusPedido,
+    itensCarrinho,
+    itensDevolucao,
+    notasFiscais,
     pagamentos,
     pedidos,
     produtos,
     produtosPedido,
+    regrasFrete,
 );
@@ -0,0 +1,195 @@
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use validator::Validate;
+use crate::dal::{carrinho_dal::CarrinhoDal, produto_dal::ProdutoDal};
+use crate::services::frete_service::FreteService;
+use crate::services::pedido_service::PedidoService;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::money;
+use crate::db::DbPool;
+use crate::models::frete::{CargaFrete, ModalidadeFrete};
+use crate::models::carrinho::{AdicionarItemCarrinhoPayload, AtualizarItemCarrinhoPayload, CarrinhoQueryParams, CarrinhoView, CheckoutCarrinhoRequest, ItemCarrinhoView};
+use crate::models::idempotencia::RespostaIdempotente;
+use crate::models::pedido::{CreatePedidoRequest, PedidoDraft, ProdutoPedidoRequest};
+
+pub struct CarrinhoService;
+
+impl CarrinhoService {
+    // Preços, ofertas e estoque são sempre os atuais do produto, nunca os do momento em que o item entrou
+    // Sem cupom aplicado, o frete grátis por valor considera o valor bruto do carrinho
+    pub async fn get(pool: &DbPool, id_cliente: &str, params: CarrinhoQueryParams) -> Result<CarrinhoView, ApiError> {
+        params.validate().map_err(ValidationError::from)?;
+
+        let (mut carrinho, peso_taxado) = Self::montar_com_peso(pool, id_cliente).await?;
+
+        if carrinho.itens.is_empty() {
+            return Ok(carrinho);
+        }
+
+        if let Some(codigo_ibge_uf) = &params.codigo_ibge_uf {
+            let modalidade = match &params.modalidade_frete {
+                Some(modalidade) => ModalidadeFrete::from_str(modalidade)?,
+                None => ModalidadeFrete::Economico,
+            };
+
+            let opcao = FreteService::opcao(pool, &CargaFrete {
+                cep: params.cep.as_deref(),
+                codigo_ibge_uf,
+                peso_taxado,
+                valor_pedido: carrinho.valor_bruto.clone(),
+            }, None, modalidade).await?;
+
+            carrinho.valor_total = &carrinho.valor_bruto + &opcao.valor_frete;
+            carrinho.valor_frete = Some(opcao.valor_frete);
+        }
+
+        Ok(carrinho)
+    }
+
+    pub async fn adicionar_item(pool: &DbPool, id_cliente: &str, payload: AdicionarItemCarrinhoPayload) -> Result<CarrinhoView, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let produtos = ProdutoDal::get_by_skus(pool, vec![payload.sku_produto.clone()], "").await?;
+        if produtos.is_empty() {
+            return Err(AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", payload.sku_produto), 404).into());
+        }
+
+        CarrinhoDal::adicionar_item(pool, id_cliente, &payload.sku_produto, payload.quantidade).await?;
+        Self::montar(pool, id_cliente).await
+    }
+
+    pub async fn atualizar_item(pool: &DbPool, id_cliente: &str, sku_produto: &str, payload: AtualizarItemCarrinhoPayload) -> Result<CarrinhoView, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        CarrinhoDal::atualizar_item(pool, id_cliente, sku_produto, payload.quantidade).await?;
+        Self::montar(pool, id_cliente).await
+    }
+
+    pub async fn remover_item(pool: &DbPool, id_cliente: &str, sku_produto: &str) -> Result<CarrinhoView, ApiError> {
+        CarrinhoDal::remover_item(pool, id_cliente, sku_produto).await?;
+        Self::montar(pool, id_cliente).await
+    }
+
+    pub async fn limpar(pool: &DbPool, id_cliente: &str) -> Result<CarrinhoView, ApiError> {
+        CarrinhoDal::limpar(pool, id_cliente).await?;
+        Self::montar(pool, id_cliente).await
+    }
+
+    pub async fn checkout(pool: &DbPool, id_cliente: &str, payload: CheckoutCarrinhoRequest) -> Result<Value, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let draft = Self::build_draft(pool, id_cliente, payload).await?;
+        PedidoService::registrar(pool, draft, None).await
+    }
+
+    // O hash é do payload do checkout: depois de concluído o carrinho fica vazio, e um retry
+    // com a mesma chave precisa reconhecer a requisição original para devolver o pedido gravado
+    pub async fn checkout_idempotente(
+        pool: &DbPool,
+        id_cliente: &str,
+        chave: &str,
+        payload: CheckoutCarrinhoRequest
+    ) -> Result<RespostaIdempotente, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let hash_requisicao = PedidoService::hash_requisicao(&payload)?;
+
+        PedidoService::executar_idempotente(pool, id_cliente, chave, &hash_requisicao, async {
+            let draft = Self::build_draft(pool, id_cliente, payload).await?;
+            PedidoService::registrar(pool, draft, Some(chave.to_string())).await
+        }).await
+    }
+
+    async fn build_draft(pool: &DbPool, id_cliente: &str, payload: CheckoutCarrinhoRequest) -> Result<PedidoDraft, ApiError> {
+        let itens = CarrinhoDal::get_itens(pool, id_cliente).await?;
+        if itens.is_empty() {
+            return Err(AppMessage::new("Carrinho está vazio", 422).into());
+        }
+
+        let CheckoutCarrinhoRequest { endereco_entrega, id_endereco, pagamento, codigo_cupom, modalidade_frete } = payload;
+
+        let request = CreatePedidoRequest {
+            produtos: itens.into_iter()
+                .map(|item| ProdutoPedidoRequest {
+                    sku_produto: item.sku_produto,
+                    quantidade: item.quantidade,
+                })
+                .collect(),
+            endereco_entrega,
+            id_endereco,
+            pagamento,
+            codigo_cupom,
+            modalidade_frete,
+        };
+
+        let mut draft = PedidoService::build_draft(pool, id_cliente, request).await?;
+        draft.origem_carrinho = true;
+        Ok(draft)
+    }
+
+    async fn montar(pool: &DbPool, id_cliente: &str) -> Result<CarrinhoView, ApiError> {
+        let (carrinho, _) = Self::montar_com_peso(pool, id_cliente).await?;
+        Ok(carrinho)
+    }
+
+    async fn montar_com_peso(pool: &DbPool, id_cliente: &str) -> Result<(CarrinhoView, BigDecimal), ApiError> {
+        let itens = CarrinhoDal::get_itens(pool, id_cliente).await?;
+
+        let skus: Vec<String> = itens.iter().map(|item| item.sku_produto.clone()).collect();
+        let map_produtos: HashMap<String, _> = ProdutoDal::get_by_skus(pool, skus, "").await?
+            .into_iter()
+            .map(|produto| (produto.sku.clone(), produto))
+            .collect();
+
+        let mut itens_view = Vec::with_capacity(itens.len());
+        let mut peso_taxado = BigDecimal::from(0);
+        for item in itens {
+            // A chave estrangeira remove o item junto com o produto, então ele sempre existe
+            let Some(produto) = map_produtos.get(&item.sku_produto) else {
+                continue;
+            };
+
+            peso_taxado += FreteService::peso_taxado(produto, item.quantidade);
+
+            let (valor_unitario_original, pct_oferta, valor_unitario) = PedidoService::precificar(&produto.preco, &produto.pctoferta);
+            let quantidade = BigDecimal::from(item.quantidade);
+            let valor_bruto = money::arredondar(&(&valor_unitario * &quantidade));
+
+            let aviso = if produto.estoque <= money::zero() {
+                Some("Produto sem estoque".to_string())
+            } else if quantidade > produto.estoque {
+                Some(format!("Estoque insuficiente: apenas {} disponível", produto.estoque.normalized()))
+            } else {
+                None
+            };
+
+            itens_view.push(ItemCarrinhoView {
+                sku_produto: item.sku_produto,
+                nome: produto.nome.clone(),
+                foto: produto.foto.clone(),
+                quantidade: item.quantidade,
+                estoque_disponivel: produto.estoque.clone(),
+                valor_unitario_original,
+                pct_oferta: pct_oferta.unwrap_or_else(money::zero),
+                valor_unitario,
+                valor_bruto,
+                aviso,
+            });
+        }
+
+        let valor_bruto: BigDecimal = itens_view.iter().map(|item| &item.valor_bruto).sum();
+
+        let carrinho = CarrinhoView {
+            quantidade_itens: itens_view.iter().map(|item| item.quantidade).sum(),
+            possui_avisos: itens_view.iter().any(|item| item.aviso.is_some()),
+            valor_total: valor_bruto.clone(),
+            valor_frete: None,
+            valor_bruto,
+            itens: itens_view,
+        };
+
+        Ok((carrinho, peso_taxado))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::validations::pedido_validations::validate_cep;
+use crate::utils::app_message::AppMessage;
+use crate::utils::{faixas_cep, ibge};
+use crate::models::cep::ConsultaCep;
+
+pub struct CepService;
+
+impl CepService {
+    pub fn consultar(cep: &str) -> Result<ConsultaCep, AppMessage> {
+        validate_cep(cep).map_err(|e| AppMessage::new(&e.code, 400))?;
+
+        let codigo_ibge_uf = faixas_cep::codigo_ibge_uf_por_cep(cep)
+            .ok_or_else(|| AppMessage::new(&format!("CEP {} não encontrado", cep), 404))?;
+
+        let (sigla_uf, nome_uf) = ibge::uf(codigo_ibge_uf)
+            .ok_or_else(|| AppMessage::new(&format!("Código IBGE UF {} não existe", codigo_ibge_uf), 500))?;
+
+        let municipio = ibge::municipio_por_cep(cep);
+
+        Ok(ConsultaCep {
+            cep: cep.to_string(),
+            codigo_ibge_uf: codigo_ibge_uf.to_string(),
+            sigla_uf: sigla_uf.to_string(),
+            nome_uf: nome_uf.to_string(),
+            codigo_ibge_cidade: municipio.map(|municipio| municipio.codigo.to_string()),
+            cidade: municipio.map(|municipio| municipio.nome.to_string()),
+        })
+    }
+
+    // O nome da cidade é gravado junto com o endereço: vale o da tabela do IBGE quando ela conhece
+    // o município, senão o informado pelo cliente
+    pub fn nome_cidade(codigo_ibge_cidade: &str, informado: Option<String>) -> Option<String> {
+        ibge::municipio(codigo_ibge_cidade)
+            .map(|municipio| municipio.nome.to_string())
+            .or_else(|| informado.map(|nome| nome.trim().to_string()).filter(|nome| !nome.is_empty()))
+    }
+
+    // Os formatos já foram validados no payload; aqui se confere a coerência entre CEP, cidade e UF
+    pub fn validar_endereco(cep: &str, codigo_ibge_cidade: &str, codigo_ibge_uf: &str) -> Result<(), AppMessage> {
+        if !codigo_ibge_cidade.starts_with(codigo_ibge_uf) {
+            return Err(AppMessage::new(&format!("Cidade {} não pertence à UF {}", codigo_ibge_cidade, codigo_ibge_uf), 400));
+        }
+
+        // Dígito verificador correto não basta: o código precisa existir na tabela de municípios do IBGE
+        if ibge::municipio(codigo_ibge_cidade).is_none() {
+            return Err(AppMessage::new(&format!("Cidade {} não existe na tabela do IBGE", codigo_ibge_cidade), 400));
+        }
+
+        if faixas_cep::codigo_ibge_uf_por_cep(cep) != Some(codigo_ibge_uf) {
+            return Err(AppMessage::new(&format!("CEP {} não pertence à UF {}", cep, codigo_ibge_uf), 400));
+        }
+
+        if let Some(municipio) = ibge::municipio_por_cep(cep)
+            && municipio.codigo != codigo_ibge_cidade {
+            return Err(AppMessage::new(
+                &format!("CEP {} pertence a {}, não à cidade {}", cep, municipio.nome, codigo_ibge_cidade),
+                400
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aceita_endereco_coerente() {
+        assert!(CepService::validar_endereco("01310100", "3550308", "35").is_ok());
+    }
+
+    #[test]
+    fn recusa_codigo_com_digito_valido_que_nao_e_municipio() {
+        assert!(ibge::codigo_municipio_valido("3599990"));
+
+        let erro = CepService::validar_endereco("15000000", "3599990", "35").unwrap_err();
+        assert_eq!(erro.status_code, 400);
+    }
+}
@@ -0,0 +1,152 @@
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDateTime, Utc};
+use std::collections::HashSet;
+use std::str::FromStr;
+use uuid::Uuid;
+use validator::Validate;
+use crate::dal::{categoria_dal::CategoriaDal, cupom_dal::CupomDal, produto_dal::ProdutoDal};
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::money;
+use crate::db::DbPool;
+use crate::models::cupom::{CreateCupomPayload, Cupom, CupomAplicado, CupomDetalhe, DescontoCupom, ItemCupom, TipoDesconto};
+
+pub struct CupomService;
+
+impl CupomService {
+    pub async fn create(pool: &DbPool, payload: CreateCupomPayload) -> Result<CupomDetalhe, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let tipo_desconto = TipoDesconto::from_str(&payload.tipo_desconto)?;
+
+        let valor_desconto = money::arredondar(&payload.valor_desconto);
+
+        let cem = BigDecimal::from(100);
+        if tipo_desconto == TipoDesconto::Percentual && valor_desconto > cem {
+            return Err(AppMessage::new("Desconto percentual deve ser de no máximo 100", 400).into());
+        }
+
+        let valor_minimo_pedido = money::arredondar(&payload.valor_minimo_pedido);
+
+        if payload.valido_de >= payload.valido_ate {
+            return Err(AppMessage::new("validoDe deve ser anterior a validoAte", 400).into());
+        }
+
+        let categorias = Self::sem_repetidos(payload.categorias);
+        let skus = Self::sem_repetidos(payload.skus);
+
+        if !categorias.is_empty() {
+            let existentes: HashSet<String> = CategoriaDal::get_all(pool).await?
+                .into_iter()
+                .map(|categoria| categoria.id)
+                .collect();
+
+            if let Some(id) = categorias.iter().find(|id| !existentes.contains(*id)) {
+                return Err(AppMessage::new(&format!("Categoria \"{}\" não existe", id), 400).into());
+            }
+        }
+
+        if !skus.is_empty() {
+            let existentes: HashSet<String> = ProdutoDal::get_by_skus(pool, skus.clone(), "").await?
+                .into_iter()
+                .map(|produto| produto.sku)
+                .collect();
+
+            if let Some(sku) = skus.iter().find(|sku| !existentes.contains(*sku)) {
+                return Err(AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400).into());
+            }
+        }
+
+        let agora = Utc::now().naive_utc();
+        let cupom = Cupom {
+            id: Uuid::new_v4().to_string(),
+            codigo: payload.codigo.to_uppercase(),
+            descricao: payload.descricao,
+            tipo_desconto: tipo_desconto.as_str().to_string(),
+            valor_desconto,
+            valor_minimo_pedido,
+            valido_de: payload.valido_de,
+            valido_ate: payload.valido_ate,
+            limite_uso_cliente: payload.limite_uso_cliente,
+            limite_uso_total: payload.limite_uso_total,
+            quantidade_utilizada: 0,
+            ativo: true,
+            created_at: agora,
+            updated_at: agora,
+        };
+
+        CupomDal::create(pool, cupom, categorias, skus).await
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<CupomDetalhe>, ApiError> {
+        CupomDal::get_all(pool).await
+    }
+
+    // Os limites de uso não são conferidos aqui: isso acontece na transação que grava o pedido
+    pub async fn calcular_desconto(pool: &DbPool, codigo: &str, itens: &[ItemCupom<'_>]) -> Result<DescontoCupom, ApiError> {
+        let codigo = codigo.trim().to_uppercase();
+
+        let detalhe = CupomDal::get_by_codigo(pool, &codigo).await?
+            .ok_or_else(|| AppMessage::new(&format!("Cupom {} não encontrado", codigo), 404))?;
+
+        Ok(Self::aplicar(&detalhe, itens, Utc::now().naive_utc())?)
+    }
+
+    fn aplicar(detalhe: &CupomDetalhe, itens: &[ItemCupom<'_>], agora: NaiveDateTime) -> Result<DescontoCupom, AppMessage> {
+        let cupom = &detalhe.cupom;
+
+        if !cupom.ativo {
+            return Err(AppMessage::new(&format!("Cupom {} está inativo", cupom.codigo), 422));
+        }
+
+        if agora < cupom.valido_de || agora > cupom.valido_ate {
+            return Err(AppMessage::new(&format!("Cupom {} fora do período de validade", cupom.codigo), 422));
+        }
+
+        let valor_bruto_total: BigDecimal = itens.iter().map(|item| item.valor_bruto).sum();
+        if valor_bruto_total < cupom.valor_minimo_pedido {
+            return Err(AppMessage::new(
+                &format!("Cupom {} exige pedido mínimo de R$ {}", cupom.codigo, cupom.valor_minimo_pedido),
+                422
+            ));
+        }
+
+        let sem_restricoes = detalhe.categorias.is_empty() && detalhe.skus.is_empty();
+        let pesos: Vec<BigDecimal> = itens.iter()
+            .map(|item| {
+                let elegivel = sem_restricoes
+                    || detalhe.skus.iter().any(|sku| sku == item.sku_produto)
+                    || detalhe.categorias.iter().any(|id| id == item.id_categoria);
+
+                if elegivel { item.valor_bruto.clone() } else { money::zero() }
+            })
+            .collect();
+
+        let base_desconto: BigDecimal = pesos.iter().sum();
+        if base_desconto <= money::zero() {
+            return Err(AppMessage::new(&format!("Cupom {} não se aplica aos produtos do pedido", cupom.codigo), 422));
+        }
+
+        let tipo_desconto = TipoDesconto::from_str(&cupom.tipo_desconto)?;
+        let valor_desconto = match tipo_desconto {
+            TipoDesconto::Percentual => money::arredondar(&(&base_desconto * &cupom.valor_desconto / BigDecimal::from(100))),
+            TipoDesconto::Valor => cupom.valor_desconto.clone().min(base_desconto),
+        };
+
+        Ok(DescontoCupom {
+            descontos_itens: money::ratear_proporcional(&valor_desconto, &pesos),
+            cupom: CupomAplicado {
+                id_cupom: cupom.id.clone(),
+                codigo: cupom.codigo.clone(),
+                limite_uso_cliente: cupom.limite_uso_cliente,
+                valor_desconto,
+            },
+        })
+    }
+
+    fn sem_repetidos(valores: Vec<String>) -> Vec<String> {
+        let mut vistos = HashSet::new();
+        valores.into_iter()
+            .filter(|valor| vistos.insert(valor.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use validator::Validate;
+use crate::dal::devolucao_dal::DevolucaoDal;
+use crate::db::DbPool;
+use crate::models::devolucao::{CreateDevolucaoRequest, DecisaoDevolucaoPayload, Devolucao, DevolucaoDetalhada, DevolucaoFiltro, MotivoDevolucao, StatusDevolucao};
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+
+pub struct DevolucaoService;
+
+impl DevolucaoService {
+    pub async fn create(pool: &DbPool, id_cliente: &str, id_pedido: &str, payload: CreateDevolucaoRequest) -> Result<DevolucaoDetalhada, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let mut vistos = HashSet::new();
+        let mut itens = Vec::with_capacity(payload.itens.len());
+
+        for item in payload.itens {
+            if !vistos.insert(item.id_item_pedido.clone()) {
+                return Err(AppMessage::new(&format!("Item {} informado mais de uma vez", item.id_item_pedido), 400).into());
+            }
+
+            let motivo = MotivoDevolucao::from_str(&item.motivo)?;
+            if motivo == MotivoDevolucao::Outro && payload.observacao.is_none() {
+                return Err(AppMessage::new("Descreva o motivo da devolução na observação", 400).into());
+            }
+
+            itens.push((item.id_item_pedido, BigDecimal::from(item.quantidade), motivo));
+        }
+
+        DevolucaoDal::create(pool, id_cliente, id_pedido, itens, payload.observacao).await
+    }
+
+    pub async fn get_all_by_pedido(pool: &DbPool, id_cliente: &str, id_pedido: &str) -> Result<Vec<DevolucaoDetalhada>, ApiError> {
+        DevolucaoDal::get_all_by_pedido(pool, id_cliente, id_pedido).await
+    }
+
+    pub async fn get_all(pool: &DbPool, filtro: DevolucaoFiltro) -> Result<Vec<Devolucao>, ApiError> {
+        let status = filtro.status.as_deref()
+            .map(StatusDevolucao::from_str)
+            .transpose()?;
+
+        DevolucaoDal::get_all(pool, status).await
+    }
+
+    pub async fn get_by_id(pool: &DbPool, id: &str) -> Result<DevolucaoDetalhada, ApiError> {
+        DevolucaoDal::get_by_id(pool, id).await
+    }
+
+    pub async fn aprovar(pool: &DbPool, id: &str, payload: DecisaoDevolucaoPayload, alterado_por: &str) -> Result<DevolucaoDetalhada, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        DevolucaoDal::aprovar(pool, id, alterado_por, payload.motivo).await
+    }
+
+    // O cliente precisa saber por que a devolução não foi aceita
+    pub async fn recusar(pool: &DbPool, id: &str, payload: DecisaoDevolucaoPayload, alterado_por: &str) -> Result<DevolucaoDetalhada, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let motivo = payload.motivo
+            .filter(|motivo| !motivo.trim().is_empty())
+            .ok_or_else(|| AppMessage::new("Motivo é obrigatório para recusar a devolução", 400))?;
+
+        DevolucaoDal::recusar(pool, id, alterado_por, motivo).await
+    }
+}
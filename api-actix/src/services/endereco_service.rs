@@ -0,0 +1,79 @@
+use chrono::Utc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::dal::endereco_dal::EnderecoDal;
+use crate::services::cep_service::CepService;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::db::DbPool;
+use crate::models::endereco::{EnderecoCliente, EnderecoClientePayload};
+use crate::models::pedido::EnderecoPayload;
+
+pub struct EnderecoService;
+
+impl EnderecoService {
+    pub async fn get_all(pool: &DbPool, id_cliente: &str) -> Result<Vec<EnderecoCliente>, ApiError> {
+        EnderecoDal::get_all(pool, id_cliente).await
+    }
+
+    pub async fn get_by_id(pool: &DbPool, id_cliente: &str, id: &str) -> Result<EnderecoCliente, ApiError> {
+        EnderecoDal::get(pool, id_cliente, Some(id)).await?
+            .ok_or_else(|| AppMessage::new("Endereço não encontrado", 404).into())
+    }
+
+    pub async fn create(pool: &DbPool, id_cliente: &str, payload: EnderecoClientePayload) -> Result<EnderecoCliente, ApiError> {
+        let endereco = Self::build_endereco(Uuid::new_v4().to_string(), id_cliente, payload)?;
+        EnderecoDal::create(pool, endereco).await
+    }
+
+    pub async fn update(pool: &DbPool, id_cliente: &str, id: &str, payload: EnderecoClientePayload) -> Result<EnderecoCliente, ApiError> {
+        let tornar_padrao = payload.padrao;
+        let endereco = Self::build_endereco(id.to_string(), id_cliente, payload)?;
+        EnderecoDal::update(pool, endereco, tornar_padrao).await
+    }
+
+    pub async fn delete(pool: &DbPool, id_cliente: &str, id: &str) -> Result<(), ApiError> {
+        EnderecoDal::delete(pool, id_cliente, id).await
+    }
+
+    // Endereço informado no pedido, o endereço salvo indicado por idEndereco ou, sem nenhum dos dois, o padrão
+    pub async fn resolver_entrega(
+        pool: &DbPool,
+        id_cliente: &str,
+        endereco_entrega: Option<EnderecoPayload>,
+        id_endereco: Option<String>
+    ) -> Result<EnderecoPayload, ApiError> {
+        match (endereco_entrega, id_endereco) {
+            (Some(_), Some(_)) => Err(AppMessage::new("Informe enderecoEntrega ou idEndereco, não os dois", 400).into()),
+            (Some(endereco), None) => Ok(endereco),
+            (None, Some(id)) => Ok(Self::get_by_id(pool, id_cliente, &id).await?.into()),
+            (None, None) => EnderecoDal::get(pool, id_cliente, None).await?
+                .map(EnderecoPayload::from)
+                .ok_or_else(|| AppMessage::new("Informe enderecoEntrega ou idEndereco: o cliente não tem endereço padrão", 400).into()),
+        }
+    }
+
+    fn build_endereco(id: String, id_cliente: &str, payload: EnderecoClientePayload) -> Result<EnderecoCliente, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        CepService::validar_endereco(&payload.cep, &payload.codigo_ibge_cidade, &payload.codigo_ibge_uf)?;
+
+        let agora = Utc::now().naive_utc();
+        Ok(EnderecoCliente {
+            id,
+            id_cliente: id_cliente.to_string(),
+            apelido: payload.apelido,
+            nome_remetente: payload.nome_remetente,
+            cep: payload.cep,
+            logradouro: payload.logradouro,
+            numero: payload.numero,
+            complemento: payload.complemento,
+            bairro: payload.bairro,
+            cidade: CepService::nome_cidade(&payload.codigo_ibge_cidade, payload.cidade),
+            codigo_ibge_cidade: payload.codigo_ibge_cidade,
+            codigo_ibge_uf: payload.codigo_ibge_uf,
+            padrao: payload.padrao.unwrap_or(false),
+            created_at: agora,
+            updated_at: agora,
+        })
+    }
+}
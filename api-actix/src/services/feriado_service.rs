@@ -0,0 +1,94 @@
+use chrono::{Datelike, FixedOffset, NaiveDate, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+use crate::dal::feriado_dal::FeriadoDal;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::calendario;
+use crate::db::DbPool;
+use crate::models::feriado::{CreateFeriadoPayload, Feriado};
+
+pub struct FeriadoService;
+
+// Pedidos são datados no horário de Brasília (UTC-3)
+pub(crate) const OFFSET_BRASILIA_SEGUNDOS: i32 = -3 * 3600;
+
+impl FeriadoService {
+    pub async fn create(pool: &DbPool, payload: CreateFeriadoPayload) -> Result<Feriado, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        // 2024 é bissexto, então 29/02 sem ano é aceito
+        let ano_referencia = payload.ano.unwrap_or(2024);
+        if NaiveDate::from_ymd_opt(ano_referencia as i32, payload.mes as u32, payload.dia as u32).is_none() {
+            return Err(AppMessage::new("Data do feriado inválida", 400).into());
+        }
+
+        if let (Some(uf), Some(cidade)) = (&payload.codigo_ibge_uf, &payload.codigo_ibge_cidade)
+            && !cidade.starts_with(uf.as_str()) {
+            return Err(AppMessage::new("Cidade do feriado não pertence à UF informada", 400).into());
+        }
+
+        let feriado = Feriado {
+            id: Uuid::new_v4().to_string(),
+            descricao: payload.descricao,
+            dia: payload.dia,
+            mes: payload.mes,
+            ano: payload.ano,
+            // O código IBGE da cidade já começa com o da UF
+            codigo_ibge_uf: payload.codigo_ibge_uf
+                .or_else(|| payload.codigo_ibge_cidade.as_ref().map(|cidade| cidade[0..2].to_string())),
+            codigo_ibge_cidade: payload.codigo_ibge_cidade,
+            created_at: Utc::now().naive_utc(),
+        };
+
+        FeriadoDal::create(pool, feriado).await
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<Feriado>, ApiError> {
+        FeriadoDal::get_all(pool).await
+    }
+
+    pub async fn delete(pool: &DbPool, id: &str) -> Result<(), ApiError> {
+        FeriadoDal::delete(pool, id).await
+    }
+
+    // Cada prazo é contado em dias úteis a partir de hoje, pulando fins de semana,
+    // feriados nacionais (inclusive os móveis) e os feriados cadastrados para o destino
+    pub async fn datas_entrega(
+        pool: &DbPool,
+        codigo_ibge_uf: &str,
+        codigo_ibge_cidade: Option<&str>,
+        prazos: &[u32]
+    ) -> Result<Vec<NaiveDate>, ApiError> {
+        let offset = FixedOffset::east_opt(OFFSET_BRASILIA_SEGUNDOS).expect("offset de Brasília válido");
+        let hoje = Utc::now().with_timezone(&offset).date_naive();
+
+        // Prazos longos que atravessam a virada do ano precisam do calendário seguinte
+        let anos = [hoje.year(), hoje.year() + 1];
+
+        let cadastrados = FeriadoDal::get_aplicaveis(
+            pool,
+            codigo_ibge_uf,
+            codigo_ibge_cidade,
+            anos.iter().map(|ano| *ano as i16).collect()
+        ).await?;
+
+        let mut feriados: HashSet<NaiveDate> = HashSet::new();
+        for ano in anos {
+            feriados.extend(calendario::feriados_nacionais(ano).into_iter().map(|(data, _)| data));
+
+            for feriado in &cadastrados {
+                if feriado.ano.is_some_and(|ano_feriado| ano_feriado as i32 != ano) {
+                    continue;
+                }
+                if let Some(data) = NaiveDate::from_ymd_opt(ano, feriado.mes as u32, feriado.dia as u32) {
+                    feriados.insert(data);
+                }
+            }
+        }
+
+        Ok(prazos.iter()
+            .map(|prazo| calendario::adicionar_dias_uteis(hoje, *prazo, &feriados))
+            .collect())
+    }
+}
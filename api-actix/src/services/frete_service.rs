@@ -0,0 +1,430 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::Utc;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use validator::Validate;
+use crate::configs::frete::FreteConfig;
+use crate::dal::{frete_dal::FreteDal, produto_dal::ProdutoDal};
+use crate::services::feriado_service::FeriadoService;
+use crate::services::pedido_service::PedidoService;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::{faixas_cep, money, prazo_entrega};
+use crate::db::DbPool;
+use crate::models::frete::{CargaFrete, CotacaoFrete, CotacaoFreteQueryParams, ModalidadeFrete, OpcaoFrete, RegraFrete, RegraFretePayload};
+use crate::models::produto::Produto;
+
+pub struct FreteService;
+
+lazy_static! {
+    // Regras ativas carregadas do banco; as alterações feitas por esta instância invalidam o cache na hora,
+    // as feitas por outras instâncias passam a valer quando ele expira
+    static ref CACHE_REGRAS: RwLock<Option<(Instant, Arc<Vec<RegraFrete>>)>> = RwLock::new(None);
+}
+
+impl FreteService {
+    pub async fn create(pool: &DbPool, payload: RegraFretePayload) -> Result<RegraFrete, ApiError> {
+        let regra = Self::build_regra(Uuid::new_v4().to_string(), payload)?;
+        let regra = FreteDal::create(pool, regra).await?;
+
+        Self::invalidar_cache();
+        Ok(regra)
+    }
+
+    pub async fn get_all(pool: &DbPool) -> Result<Vec<RegraFrete>, ApiError> {
+        FreteDal::get_all(pool).await
+    }
+
+    pub async fn update(pool: &DbPool, id: &str, payload: RegraFretePayload) -> Result<RegraFrete, ApiError> {
+        let regra = Self::build_regra(id.to_string(), payload)?;
+        let regra = FreteDal::update(pool, regra).await?;
+
+        Self::invalidar_cache();
+        Ok(regra)
+    }
+
+    pub async fn delete(pool: &DbPool, id: &str) -> Result<(), ApiError> {
+        FreteDal::delete(pool, id).await?;
+
+        Self::invalidar_cache();
+        Ok(())
+    }
+
+    pub async fn cotacao(pool: &DbPool, params: CotacaoFreteQueryParams) -> Result<CotacaoFrete, ApiError> {
+        params.validate().map_err(ValidationError::from)?;
+
+        let codigo_ibge_uf = faixas_cep::codigo_ibge_uf_por_cep(&params.cep)
+            .ok_or_else(|| AppMessage::new(&format!("CEP {} não pertence a nenhuma UF", params.cep), 400))?;
+
+        let quantidades = Self::parse_skus(&params.skus)?;
+
+        let skus: Vec<String> = quantidades.iter().map(|(sku, _)| sku.clone()).collect();
+        let map_produtos: HashMap<String, Produto> = ProdutoDal::get_by_skus(pool, skus, "").await?
+            .into_iter()
+            .map(|produto| (produto.sku.clone(), produto))
+            .collect();
+
+        let mut peso_taxado = BigDecimal::from(0);
+        let mut valor_produtos = money::zero();
+        for (sku, quantidade) in &quantidades {
+            let produto = map_produtos.get(sku)
+                .ok_or_else(|| AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400))?;
+
+            let (_, _, valor_unitario) = PedidoService::precificar(&produto.preco, &produto.pctoferta);
+            valor_produtos += money::arredondar(&(&valor_unitario * BigDecimal::from(*quantidade)));
+            peso_taxado += Self::peso_taxado(produto, *quantidade);
+        }
+
+        let opcoes = Self::opcoes(pool, &CargaFrete {
+            cep: Some(&params.cep),
+            codigo_ibge_uf,
+            peso_taxado: peso_taxado.clone(),
+            valor_pedido: valor_produtos.clone(),
+        }, None).await?;
+
+        Ok(CotacaoFrete {
+            cep: params.cep,
+            codigo_ibge_uf: codigo_ibge_uf.to_string(),
+            peso_taxado,
+            valor_produtos,
+            opcoes,
+        })
+    }
+
+    // Todas as modalidades que atendem a carga; a retirada na loja está sempre disponível
+    pub async fn opcoes(pool: &DbPool, carga: &CargaFrete<'_>, codigo_ibge_cidade: Option<&str>) -> Result<Vec<OpcaoFrete>, ApiError> {
+        let regras = Self::regras_ativas(pool).await?;
+
+        let cotadas: Vec<(ModalidadeFrete, BigDecimal, u32)> = ModalidadeFrete::TODAS.iter()
+            .filter_map(|modalidade| Self::cotar(&regras, carga, *modalidade)
+                .map(|(valor_frete, prazo)| (*modalidade, valor_frete, prazo)))
+            .collect();
+
+        let prazos: Vec<u32> = cotadas.iter().map(|(_, _, prazo)| *prazo).collect();
+        let datas = FeriadoService::datas_entrega(pool, carga.codigo_ibge_uf, codigo_ibge_cidade, &prazos).await?;
+
+        Ok(cotadas.into_iter()
+            .zip(datas)
+            .map(|((modalidade, valor_frete, prazo_dias_uteis), data_entrega)| OpcaoFrete {
+                id: modalidade,
+                descricao: modalidade.descricao(),
+                valor_frete,
+                prazo_dias_uteis,
+                data_entrega,
+            })
+            .collect())
+    }
+
+    pub async fn opcao(
+        pool: &DbPool,
+        carga: &CargaFrete<'_>,
+        codigo_ibge_cidade: Option<&str>,
+        modalidade: ModalidadeFrete
+    ) -> Result<OpcaoFrete, ApiError> {
+        let regras = Self::regras_ativas(pool).await?;
+
+        let (valor_frete, prazo_dias_uteis) = Self::cotar(&regras, carga, modalidade)
+            .ok_or_else(|| AppMessage::new(&format!("{} não disponível para o destino", modalidade.descricao()), 400))?;
+
+        let data_entrega = FeriadoService::datas_entrega(pool, carga.codigo_ibge_uf, codigo_ibge_cidade, &[prazo_dias_uteis]).await?
+            .remove(0);
+
+        Ok(OpcaoFrete {
+            id: modalidade,
+            descricao: modalidade.descricao(),
+            valor_frete,
+            prazo_dias_uteis,
+            data_entrega,
+        })
+    }
+
+    // Vale a regra da modalidade mais específica que atende o destino e o peso: faixa de CEP, depois UF,
+    // depois nacional; no empate decide a maior prioridade. Sem prazo na regra vale o prazo da tabela de entrega
+    fn cotar(regras: &[RegraFrete], carga: &CargaFrete<'_>, modalidade: ModalidadeFrete) -> Option<(BigDecimal, u32)> {
+        if modalidade == ModalidadeFrete::Retirada {
+            return Some((money::zero(), FreteConfig::new().retirada_prazo_dias_uteis));
+        }
+
+        let regra = regras.iter()
+            .filter(|regra| regra.modalidade == modalidade.as_str() && Self::atende(regra, carga))
+            .max_by_key(|regra| (Self::especificidade(regra), regra.prioridade))?;
+
+        let prazo = match regra.prazo_dias_uteis {
+            Some(prazo) => prazo as u32,
+            None => {
+                let codigo_ibge_uf = carga.codigo_ibge_uf.parse::<i64>().ok()?;
+                let prazo = prazo_entrega::prazo_dias_uteis(codigo_ibge_uf, carga.cep.unwrap_or_default())?;
+
+                // O expresso leva metade do prazo padrão, arredondando para cima
+                if modalidade == ModalidadeFrete::Expresso { prazo.div_ceil(2) } else { prazo }
+            }
+        };
+
+        let valor_frete = match &regra.valor_minimo_frete_gratis {
+            Some(valor_minimo) if carga.valor_pedido >= *valor_minimo => money::zero(),
+            _ => money::arredondar(&regra.valor_frete),
+        };
+
+        Some((valor_frete, prazo))
+    }
+
+    // Cobra-se o maior entre o peso real e o peso cúbico (A x L x C / fator de cubagem)
+    pub fn peso_taxado(produto: &Produto, quantidade: i32) -> BigDecimal {
+        let fator_cubagem = BigDecimal::from(FreteConfig::new().fator_cubagem);
+        let peso_cubico = &produto.altura_cm * &produto.largura_cm * &produto.comprimento_cm / fator_cubagem;
+
+        produto.peso_kg.clone().max(peso_cubico) * BigDecimal::from(quantidade)
+    }
+
+    fn atende(regra: &RegraFrete, carga: &CargaFrete<'_>) -> bool {
+        if let Some(uf) = &regra.codigo_ibge_uf
+            && uf != carga.codigo_ibge_uf {
+            return false;
+        }
+
+        if let (Some(cep_inicial), Some(cep_final)) = (&regra.cep_inicial, &regra.cep_final) {
+            let Some(cep) = carga.cep else {
+                return false;
+            };
+            if cep < cep_inicial.as_str() || cep > cep_final.as_str() {
+                return false;
+            }
+        }
+
+        if carga.peso_taxado < regra.peso_minimo {
+            return false;
+        }
+
+        regra.peso_maximo.as_ref().is_none_or(|peso_maximo| carga.peso_taxado <= *peso_maximo)
+    }
+
+    fn parse_skus(skus: &str) -> Result<Vec<(String, i32)>, AppMessage> {
+        let mut quantidades: Vec<(String, i32)> = Vec::new();
+
+        for item in skus.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (sku, quantidade) = match item.split_once(':') {
+                Some((sku, quantidade)) => {
+                    let quantidade = quantidade.trim().parse::<i32>()
+                        .ok()
+                        .filter(|quantidade| (1..=999).contains(quantidade))
+                        .ok_or_else(|| AppMessage::new(&format!("Quantidade do produto \"{}\" deve estar entre 1 e 999", sku.trim()), 400))?;
+                    (sku.trim(), quantidade)
+                }
+                None => (item, 1),
+            };
+
+            match quantidades.iter_mut().find(|(existente, _)| existente == sku) {
+                Some((_, total)) => *total += quantidade,
+                None => quantidades.push((sku.to_string(), quantidade)),
+            }
+        }
+
+        if quantidades.is_empty() {
+            return Err(AppMessage::new("Nenhum produto informado", 400));
+        }
+
+        Ok(quantidades)
+    }
+
+    fn especificidade(regra: &RegraFrete) -> u8 {
+        if regra.cep_inicial.is_some() {
+            2
+        } else if regra.codigo_ibge_uf.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    async fn regras_ativas(pool: &DbPool) -> Result<Arc<Vec<RegraFrete>>, ApiError> {
+        let validade = Duration::from_secs(FreteConfig::new().cache_segundos);
+
+        if let Ok(cache) = CACHE_REGRAS.read()
+            && let Some((carregado_em, regras)) = cache.as_ref()
+            && carregado_em.elapsed() < validade {
+            return Ok(regras.clone());
+        }
+
+        let regras = Arc::new(FreteDal::get_ativas(pool).await?);
+
+        if let Ok(mut cache) = CACHE_REGRAS.write() {
+            *cache = Some((Instant::now(), regras.clone()));
+        }
+
+        Ok(regras)
+    }
+
+    fn invalidar_cache() {
+        if let Ok(mut cache) = CACHE_REGRAS.write() {
+            *cache = None;
+        }
+    }
+
+    // Pesos vão para Decimal(11, 3): números do JSON chegam com a expansão binária do f64
+    fn arredondar_peso(peso: &BigDecimal) -> BigDecimal {
+        peso.with_scale_round(3, RoundingMode::HalfUp)
+    }
+
+    fn build_regra(id: String, payload: RegraFretePayload) -> Result<RegraFrete, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        match (&payload.cep_inicial, &payload.cep_final) {
+            (Some(cep_inicial), Some(cep_final)) if cep_inicial > cep_final => {
+                return Err(AppMessage::new("cepInicial deve ser menor ou igual a cepFinal", 400).into());
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(AppMessage::new("Faixa de CEP exige cepInicial e cepFinal", 400).into());
+            }
+            _ => {}
+        }
+
+        let peso_minimo = Self::arredondar_peso(&payload.peso_minimo);
+        let peso_maximo = payload.peso_maximo.as_ref().map(Self::arredondar_peso);
+
+        if let Some(peso_maximo) = &peso_maximo
+            && peso_minimo > *peso_maximo {
+            return Err(AppMessage::new("pesoMinimo deve ser menor ou igual a pesoMaximo", 400).into());
+        }
+
+        let valor_frete = money::arredondar(&payload.valor_frete);
+        let valor_minimo_frete_gratis = payload.valor_minimo_frete_gratis.as_ref().map(money::arredondar);
+
+        let modalidade = match &payload.modalidade {
+            Some(modalidade) => ModalidadeFrete::from_str(modalidade)?,
+            None => ModalidadeFrete::Economico,
+        };
+
+        // A retirada na loja não tem custo nem depende de regra
+        if modalidade == ModalidadeFrete::Retirada {
+            return Err(AppMessage::new("Regras de frete não se aplicam à retirada na loja", 400).into());
+        }
+
+        let agora = Utc::now().naive_utc();
+        Ok(RegraFrete {
+            id,
+            descricao: payload.descricao,
+            codigo_ibge_uf: payload.codigo_ibge_uf,
+            cep_inicial: payload.cep_inicial,
+            cep_final: payload.cep_final,
+            peso_minimo,
+            peso_maximo,
+            valor_frete,
+            valor_minimo_frete_gratis,
+            prioridade: payload.prioridade,
+            ativo: payload.ativo,
+            created_at: agora,
+            updated_at: agora,
+            modalidade: modalidade.as_str().to_string(),
+            prazo_dias_uteis: payload.prazo_dias_uteis,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valor(texto: &str) -> BigDecimal {
+        BigDecimal::from_str(texto).unwrap()
+    }
+
+    fn regra(descricao: &str, codigo_ibge_uf: Option<&str>, faixa_cep: Option<(&str, &str)>, prioridade: i32, valor_frete: &str) -> RegraFrete {
+        let agora = Utc::now().naive_utc();
+
+        RegraFrete {
+            id: descricao.to_string(),
+            descricao: descricao.to_string(),
+            codigo_ibge_uf: codigo_ibge_uf.map(str::to_string),
+            cep_inicial: faixa_cep.map(|(inicio, _)| inicio.to_string()),
+            cep_final: faixa_cep.map(|(_, fim)| fim.to_string()),
+            peso_minimo: valor("0"),
+            peso_maximo: None,
+            valor_frete: valor(valor_frete),
+            valor_minimo_frete_gratis: None,
+            prioridade,
+            ativo: true,
+            created_at: agora,
+            updated_at: agora,
+            modalidade: ModalidadeFrete::Economico.as_str().to_string(),
+            prazo_dias_uteis: Some(5),
+        }
+    }
+
+    fn carga<'a>(cep: &'a str, codigo_ibge_uf: &'a str, peso_taxado: &str, valor_pedido: &str) -> CargaFrete<'a> {
+        CargaFrete {
+            cep: Some(cep),
+            codigo_ibge_uf,
+            peso_taxado: valor(peso_taxado),
+            valor_pedido: valor(valor_pedido),
+        }
+    }
+
+    fn regras_sp() -> Vec<RegraFrete> {
+        vec![
+            regra("nacional", None, None, 10, "40.00"),
+            regra("sp", Some("35"), None, 0, "25.00"),
+            regra("capital sp", Some("35"), Some(("01000000", "05999999")), 0, "15.00"),
+        ]
+    }
+
+    #[test]
+    fn faixa_de_cep_vence_uf_que_vence_nacional() {
+        let regras = regras_sp();
+
+        assert_eq!(FreteService::cotar(&regras, &carga("01310100", "35", "1", "100"), ModalidadeFrete::Economico), Some((valor("15.00"), 5)));
+        assert_eq!(FreteService::cotar(&regras, &carga("13010000", "35", "1", "100"), ModalidadeFrete::Economico), Some((valor("25.00"), 5)));
+        assert_eq!(FreteService::cotar(&regras, &carga("20040020", "33", "1", "100"), ModalidadeFrete::Economico), Some((valor("40.00"), 5)));
+    }
+
+    #[test]
+    fn prioridade_desempata_regras_de_mesma_especificidade() {
+        let mut regras = regras_sp();
+        regras.push(regra("sp promocional", Some("35"), None, 5, "19.90"));
+
+        assert_eq!(FreteService::cotar(&regras, &carga("13010000", "35", "1", "100"), ModalidadeFrete::Economico), Some((valor("19.90"), 5)));
+        // A prioridade não passa na frente de uma regra mais específica
+        assert_eq!(FreteService::cotar(&regras, &carga("01310100", "35", "1", "100"), ModalidadeFrete::Economico), Some((valor("15.00"), 5)));
+    }
+
+    #[test]
+    fn faixa_de_peso_limita_a_regra() {
+        let mut regras = regras_sp();
+        regras[2].peso_maximo = Some(valor("5"));
+
+        assert_eq!(FreteService::cotar(&regras, &carga("01310100", "35", "5", "100"), ModalidadeFrete::Economico), Some((valor("15.00"), 5)));
+        assert_eq!(FreteService::cotar(&regras, &carga("01310100", "35", "5.001", "100"), ModalidadeFrete::Economico), Some((valor("25.00"), 5)));
+    }
+
+    #[test]
+    fn frete_gratis_a_partir_do_valor_minimo() {
+        let mut regras = regras_sp();
+        regras[1].valor_minimo_frete_gratis = Some(valor("199.90"));
+
+        assert_eq!(FreteService::cotar(&regras, &carga("13010000", "35", "1", "199.89"), ModalidadeFrete::Economico), Some((valor("25.00"), 5)));
+        assert_eq!(FreteService::cotar(&regras, &carga("13010000", "35", "1", "199.90"), ModalidadeFrete::Economico), Some((money::zero(), 5)));
+    }
+
+    #[test]
+    fn sem_regra_para_o_destino() {
+        let regras = vec![regra("sp", Some("35"), None, 0, "25.00")];
+
+        assert_eq!(FreteService::cotar(&regras, &carga("20040020", "33", "1", "100"), ModalidadeFrete::Economico), None);
+    }
+
+    #[test]
+    fn cada_modalidade_usa_as_proprias_regras() {
+        let mut regras = regras_sp();
+        let mut expresso = regra("sp expresso", Some("35"), None, 0, "49.90");
+        expresso.modalidade = ModalidadeFrete::Expresso.as_str().to_string();
+        expresso.prazo_dias_uteis = Some(2);
+        regras.push(expresso);
+
+        let interior = carga("13010000", "35", "1", "100");
+        assert_eq!(FreteService::cotar(&regras, &interior, ModalidadeFrete::Economico), Some((valor("25.00"), 5)));
+        assert_eq!(FreteService::cotar(&regras, &interior, ModalidadeFrete::Expresso), Some((valor("49.90"), 2)));
+        assert_eq!(FreteService::cotar(&regras, &carga("20040020", "33", "1", "100"), ModalidadeFrete::Expresso), None);
+    }
+}
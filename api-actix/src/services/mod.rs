@@ -2,4 +2,14 @@ pub mod categoria_service;
 pub mod produto_service;
 pub mod home_service;
 pub mod pedido_service;
-pub mod cliente_service;
\ No newline at end of file
+pub mod cliente_service;
+pub mod cupom_service;
+pub mod pagamento_service;
+pub mod carrinho_service;
+pub mod feriado_service;
+pub mod frete_service;
+pub mod cep_service;
+pub mod endereco_service;
+pub mod nfe_service;
+pub mod outbox_service;
+pub mod devolucao_service;
\ No newline at end of file
@@ -0,0 +1,112 @@
+use chrono::{FixedOffset, Utc};
+use rand::Rng;
+use std::str::FromStr;
+use crate::configs::nfe::NfeConfig;
+use crate::dal::nfe_dal::NfeDal;
+use crate::db::DbPool;
+use crate::models::nfe::NotaFiscal;
+use crate::models::pedido::StatusPedido;
+use crate::services::feriado_service::OFFSET_BRASILIA_SEGUNDOS;
+use crate::utils::app_message::{ApiError, AppMessage};
+use crate::utils::{ibge, nfe, xmldsig};
+use crate::validations::cliente_validations::validate_cnpj;
+
+// Só pedidos com pagamento confirmado são faturados
+const STATUS_FATURAVEIS: [StatusPedido; 4] = [StatusPedido::Pago, StatusPedido::Faturado, StatusPedido::Enviado, StatusPedido::Entregue];
+
+pub struct NfeService;
+
+impl NfeService {
+    // Devolve a nota e se ela foi emitida agora; um pedido nunca recebe mais de uma NF-e
+    pub async fn emitir(pool: &DbPool, id_pedido: &str) -> Result<(NotaFiscal, bool), ApiError> {
+        if let Some(nota) = NfeDal::get_by_pedido(pool, id_pedido, None).await? {
+            return Ok((nota, false));
+        }
+
+        let config = NfeConfig::new();
+        Self::validar_emitente(&config)?;
+        let certificado = xmldsig::Certificado::carregar(&config.certificado, &config.chave_privada)?;
+
+        let dados = NfeDal::get_dados(pool, id_pedido).await?;
+        let status = StatusPedido::from_str(&dados.pedido.status)?;
+        if !STATUS_FATURAVEIS.contains(&status) {
+            return Err(AppMessage::new(&format!("Pedido com status {} não pode ser faturado", status), 422).into());
+        }
+
+        let offset = FixedOffset::east_opt(OFFSET_BRASILIA_SEGUNDOS).expect("offset de Brasília válido");
+        let emissao = Utc::now().with_timezone(&offset);
+
+        NfeDal::create(
+            pool,
+            id_pedido,
+            config.ambiente as i16,
+            config.serie as i32,
+            config.numero_inicial as i32,
+            move |numero| {
+                let emitente = nfe::Emitente {
+                    cnpj: &config.cnpj,
+                    ie: &config.ie,
+                    razao_social: &config.razao_social,
+                    nome_fantasia: config.nome_fantasia.as_deref(),
+                    logradouro: &config.logradouro,
+                    numero: &config.numero,
+                    complemento: config.complemento.as_deref(),
+                    bairro: &config.bairro,
+                    codigo_municipio: &config.codigo_municipio,
+                    municipio: &config.municipio,
+                    cep: &config.cep,
+                    telefone: config.telefone.as_deref(),
+                    crt: config.crt,
+                };
+
+                let identificacao = nfe::Identificacao {
+                    ambiente: config.ambiente,
+                    serie: config.serie,
+                    numero: numero as u32,
+                    codigo_numerico: Self::codigo_numerico(numero as u32),
+                    emissao,
+                    natureza_operacao: &config.natureza_operacao,
+                };
+
+                let inf_nfe = nfe::gerar(&dados, &emitente, &identificacao)?;
+                let assinatura = xmldsig::assinar(&inf_nfe.canonico(), &inf_nfe.id(), &certificado);
+                let xml = inf_nfe.documento(&assinatura);
+
+                Ok((inf_nfe.chave_acesso, xml))
+            }
+        ).await
+    }
+
+    pub async fn get_xml(pool: &DbPool, id_pedido: &str, id_cliente: Option<&str>) -> Result<NotaFiscal, ApiError> {
+        NfeDal::get_by_pedido(pool, id_pedido, id_cliente).await?
+            .ok_or_else(|| AppMessage::new("Pedido não possui NF-e emitida", 404).into())
+    }
+
+    fn validar_emitente(config: &NfeConfig) -> Result<(), AppMessage> {
+        if validate_cnpj(&config.cnpj).is_err() || config.cnpj.len() != 14 {
+            return Err(AppMessage::new("NFE_CNPJ do emitente ausente ou inválido", 500));
+        }
+        if !config.ie.chars().any(|c| c.is_ascii_digit()) {
+            return Err(AppMessage::new("NFE_IE do emitente não configurada", 500));
+        }
+        if !ibge::codigo_municipio_valido(&config.codigo_municipio) {
+            return Err(AppMessage::new("NFE_CODIGO_MUNICIPIO do emitente inválido", 500));
+        }
+        if config.cep.len() != 8 || !config.cep.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AppMessage::new("NFE_CEP do emitente deve ter 8 dígitos", 500));
+        }
+
+        Ok(())
+    }
+
+    // cNF aleatório de 8 dígitos, que a SEFAZ não aceita igual ao número da nota
+    fn codigo_numerico(numero: u32) -> u32 {
+        let mut rng = rand::rng();
+        loop {
+            let codigo = rng.random_range(0..100_000_000);
+            if codigo != numero {
+                return codigo;
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::configs::outbox::OutboxConfig;
+use crate::dal::outbox_dal::OutboxDal;
+use crate::db::DbPool;
+use crate::models::outbox::{EventoOutbox, EventoOutboxFiltro, StatusEvento, TipoEvento};
+use crate::providers::eventos::{self, EventSink};
+use crate::utils::app_message::{ApiError, AppMessage};
+
+pub struct OutboxService;
+
+type Sinks = Arc<Vec<Box<dyn EventSink>>>;
+
+impl OutboxService {
+    // Sobe o worker que entrega os eventos gravados pelas transações de negócio. Roda dentro
+    // do processo do servidor; com mais de uma instância, cada uma pega lotes diferentes
+    pub fn iniciar_worker(pool: Arc<DbPool>) {
+        let config = OutboxConfig::new();
+        if !config.worker_habilitado {
+            log::info!("Worker do outbox desabilitado por OUTBOX_WORKER");
+            return;
+        }
+
+        let sinks: Sinks = Arc::new(eventos::sinks_configurados(&config, &pool));
+        let nomes: Vec<&str> = sinks.iter().map(|sink| sink.nome()).collect();
+        log::info!("Worker do outbox iniciado com os destinos: {}", nomes.join(", "));
+
+        actix_web::rt::spawn(async move {
+            loop {
+                let processados = Self::processar_lote(&pool, &sinks, &config).await
+                    .unwrap_or_else(|e| {
+                        log::error!("Erro ao processar eventos do outbox: {}", e);
+                        0
+                    });
+
+                // Lote cheio indica fila acumulada: segue sem esperar
+                if processados < config.tamanho_lote as usize {
+                    tokio::time::sleep(Duration::from_millis(config.intervalo_ms)).await;
+                }
+            }
+        });
+    }
+
+    pub async fn get_all(pool: &DbPool, filtro: EventoOutboxFiltro) -> Result<Vec<EventoOutbox>, ApiError> {
+        let status = filtro.status.as_deref()
+            .map(StatusEvento::from_str)
+            .transpose()?;
+
+        OutboxDal::get_all(pool, status).await
+    }
+
+    pub async fn reprocessar(pool: &DbPool, id: &str) -> Result<EventoOutbox, ApiError> {
+        OutboxDal::reprocessar(pool, id).await
+    }
+
+    async fn processar_lote(pool: &DbPool, sinks: &Sinks, config: &OutboxConfig) -> Result<usize, ApiError> {
+        let lote = OutboxDal::reservar(pool, config.tamanho_lote, config.lease_segundos).await?;
+        let processados = lote.len();
+
+        for evento in lote {
+            let id = evento.id.clone();
+            let tentativa = evento.tentativas + 1;
+            let sinks_clone = Arc::clone(sinks);
+
+            let (entregue_a, erros) = tokio::task::spawn_blocking(move || Self::entregar(&sinks_clone, evento)).await
+                .map_err(|e| ApiError::from(AppMessage::new(&format!("Task error: {}", e), 500)))?;
+
+            if erros.is_empty() {
+                OutboxDal::concluir(pool, &id, entregue_a).await?;
+                continue;
+            }
+
+            // Só desiste na hora se nenhum destino tiver falhado por motivo temporário
+            let definitivo = erros.iter().all(|erro| (400..500).contains(&erro.status_code));
+            let erro = erros.iter().map(|erro| erro.message.as_str()).collect::<Vec<_>>().join("; ");
+            let espera = (!definitivo && tentativa < config.max_tentativas)
+                .then(|| Self::backoff_segundos(tentativa, config));
+
+            match espera {
+                Some(segundos) => log::warn!("Falha na tentativa {} do evento {}, nova tentativa em {}s: {}", tentativa, id, segundos, erro),
+                None if definitivo => log::error!("Evento {} marcado como falho sem novas tentativas: {}", id, erro),
+                None => log::error!("Evento {} marcado como falho após {} tentativas: {}", id, tentativa, erro),
+            }
+
+            OutboxDal::registrar_falha(pool, &id, entregue_a, erro, espera).await?;
+        }
+
+        Ok(processados)
+    }
+
+    // Entrega aos destinos que ainda não receberam o evento. Devolve a lista atualizada de
+    // destinos atendidos, para que uma nova tentativa não repita quem já recebeu
+    fn entregar(sinks: &[Box<dyn EventSink>], evento: EventoOutbox) -> (Vec<String>, Vec<AppMessage>) {
+        let mut entregue_a = evento.entregue_a.clone();
+
+        let tipo = match TipoEvento::from_str(&evento.tipo) {
+            Ok(tipo) => tipo,
+            Err(e) => return (entregue_a, vec![e]),
+        };
+
+        let mut erros = Vec::new();
+        for sink in sinks.iter().filter(|sink| sink.aceita(tipo)) {
+            if entregue_a.iter().any(|nome| nome == sink.nome()) {
+                continue;
+            }
+
+            match sink.entregar(&evento) {
+                Ok(()) => entregue_a.push(sink.nome().to_string()),
+                Err(e) => erros.push(AppMessage::new(&format!("{}: {}", sink.nome(), e.message), e.status_code)),
+            }
+        }
+
+        (entregue_a, erros)
+    }
+
+    // Exponencial a partir da base, limitado ao máximo configurado
+    fn backoff_segundos(tentativa: i32, config: &OutboxConfig) -> i64 {
+        let expoente = (tentativa - 1).clamp(0, 30) as u32;
+        config.backoff_base_segundos
+            .saturating_mul(1i64 << expoente)
+            .min(config.backoff_max_segundos)
+    }
+}
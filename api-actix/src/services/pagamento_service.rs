@@ -0,0 +1,48 @@
+use chrono::Utc;
+use std::str::FromStr;
+use validator::Validate;
+use crate::configs::pagamento::PagamentoConfig;
+use crate::dal::pagamento_dal::PagamentoDal;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::webhook;
+use crate::db::DbPool;
+use crate::models::evento_pagamento::{EventoPagamentoPayload, ResultadoEventoPagamento, TipoEventoPagamento};
+
+pub struct PagamentoService;
+
+impl PagamentoService {
+    // A assinatura é conferida sobre o corpo bruto, antes de qualquer parse do JSON
+    pub async fn processar_webhook(
+        pool: &DbPool,
+        timestamp: Option<&str>,
+        assinatura: Option<&str>,
+        corpo: &[u8]
+    ) -> Result<ResultadoEventoPagamento, ApiError> {
+        let config = PagamentoConfig::new();
+
+        // Sem segredo configurado nenhum evento é aceito, já que não há como conferir a origem
+        let Some(segredo) = config.webhook_secret.as_deref() else {
+            log::warn!("PAGAMENTO_WEBHOOK_SECRET não configurado, webhook de pagamento recusado");
+            return Err(AppMessage::new("Webhook de pagamento não configurado", 401).into());
+        };
+
+        webhook::verificar_assinatura(
+            segredo,
+            timestamp,
+            assinatura,
+            corpo,
+            Utc::now().timestamp(),
+            config.webhook_tolerancia_segundos
+        )?;
+
+        let evento: EventoPagamentoPayload = serde_json::from_slice(corpo)
+            .map_err(|e| AppMessage::new(&format!("JSON inválido: {}", e), 400))?;
+
+        evento.validate().map_err(ValidationError::from)?;
+
+        let tipo = TipoEventoPagamento::from_str(&evento.tipo)?;
+        let payload = String::from_utf8_lossy(corpo).into_owned();
+
+        PagamentoDal::processar_evento(pool, evento, tipo, payload).await
+    }
+}
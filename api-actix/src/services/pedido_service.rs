@@ -1,287 +1,456 @@
-use chrono::{Utc, Duration};
+use chrono::{Utc, NaiveDate};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::str::FromStr;
-use bigdecimal::{BigDecimal, ToPrimitive};
-use crate::dal::{pedido_dal::PedidoDal, produto_dal::ProdutoDal};
-use crate::utils::app_message::AppMessage;
-use crate::utils::tabela_frete::MAPA_FRETE;
+use bigdecimal::BigDecimal;
+use validator::Validate;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::configs::idempotencia::IdempotenciaConfig;
+use crate::dal::{idempotencia_dal::IdempotenciaDal, pedido_dal::PedidoDal, produto_dal::ProdutoDal};
+use crate::services::cep_service::CepService;
+use crate::services::cupom_service::CupomService;
+use crate::services::endereco_service::EnderecoService;
+use crate::services::frete_service::FreteService;
+use crate::utils::app_message::{ApiError, AppMessage, ValidationError};
+use crate::utils::{boleto, comprovante, money, pix};
 use crate::db::DbPool;
-use crate::models::pedido::{EnderecoPayload, PagamentoPayload, PedidoPayload};
-use crate::models::produto::ProdutoPayload;
+use crate::models::cupom::ItemCupom;
+use crate::models::frete::{CargaFrete, ModalidadeFrete};
+use crate::models::idempotencia::{ReservaIdempotencia, RespostaIdempotente};
+use crate::providers::pagamento;
+use crate::models::pedido::{AlterarStatusPayload, CancelarPedidoPayload, CreatePedidoRequest, FormaPagamento, HistoricoStatusPedido, ItemPedidoDraft, PagamentoDraft, Pedido, PedidoDraft, PedidoFiltro, PedidoQueryParams, SimulacaoPedido, StatusPedido, PROMOCAO_OFERTA_PRODUTO};
 
 pub struct PedidoService;
 
 impl PedidoService {
-    pub async fn create(pool: &DbPool, mut payload: Value) -> Result<Value, AppMessage> {
-        let produtos = payload["produtos"].as_array().ok_or_else(||
-            AppMessage::new("Formato inválido para produtos", 400)
-        )?;
+    pub async fn create(pool: &DbPool, id_cliente: &str, payload: CreatePedidoRequest) -> Result<Value, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
 
-        if produtos.is_empty() {
-            return Err(AppMessage::new("Nenhum produto informado", 400));
+        let draft = Self::build_draft(pool, id_cliente, payload).await?;
+        Self::registrar(pool, draft, None).await
+    }
+
+    pub async fn create_idempotente(
+        pool: &DbPool,
+        id_cliente: &str,
+        chave: &str,
+        payload: CreatePedidoRequest
+    ) -> Result<RespostaIdempotente, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let hash_requisicao = Self::hash_requisicao(&payload)?;
+
+        Self::executar_idempotente(pool, id_cliente, chave, &hash_requisicao, async {
+            let draft = Self::build_draft(pool, id_cliente, payload).await?;
+            Self::registrar(pool, draft, Some(chave.to_string())).await
+        }).await
+    }
+
+    // Mesmo caminho de precificação do create, mas sem reserva de estoque, pagamento ou gravação
+    pub async fn simular(pool: &DbPool, id_cliente: &str, payload: CreatePedidoRequest) -> Result<SimulacaoPedido, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
+
+        let draft = Self::build_draft(pool, id_cliente, payload).await?;
+        Ok(SimulacaoPedido::from(draft))
+    }
+
+    pub(crate) async fn registrar(pool: &DbPool, draft: PedidoDraft, chave_idempotencia: Option<String>) -> Result<Value, ApiError> {
+        let provider = pagamento::provider_para(draft.pagamento.forma_pagamento);
+        PedidoDal::create(pool, draft, provider, chave_idempotencia).await
+    }
+
+    // O pedido só é criado se a chave for reservada agora; uma chave já concluída devolve a resposta gravada
+    pub(crate) async fn executar_idempotente(
+        pool: &DbPool,
+        id_cliente: &str,
+        chave: &str,
+        hash_requisicao: &str,
+        criar_pedido: impl Future<Output = Result<Value, ApiError>>
+    ) -> Result<RespostaIdempotente, ApiError> {
+        if chave.trim().is_empty() || chave.len() > 255 {
+            return Err(AppMessage::new("Idempotency-Key deve ter entre 1 e 255 caracteres", 400).into());
         }
 
-        let mut sku_produtos = Vec::new();
-        for produto in produtos {
-            if let Some(sku) = produto["skuProduto"].as_str() {
-                sku_produtos.push(sku.to_string());
-            }
+        let config = IdempotenciaConfig::new();
+
+        if let ReservaIdempotencia::Concluida(resposta) =
+            IdempotenciaDal::reservar(pool, id_cliente, chave, hash_requisicao, config.validade_horas, config.reserva_segundos).await? {
+            return Ok(resposta);
         }
 
-        if let Some(obj) = payload.as_object_mut() {
-            obj.insert("valorBruto".to_string(), Value::Number(serde_json::Number::from_f64(0.0).unwrap()));
-            obj.insert("valorDesconto".to_string(), Value::Number(serde_json::Number::from_f64(0.0).unwrap()));
+        match criar_pedido.await {
+            Ok(pedido) => Ok(RespostaIdempotente {
+                status_code: 201,
+                corpo: pedido,
+                repetida: false,
+            }),
+            Err(e) => {
+                // Falhas não ficam gravadas: o cliente pode tentar de novo com a mesma chave
+                IdempotenciaDal::liberar(pool, id_cliente, chave).await?;
+                Err(e)
+            }
         }
+    }
+
+    pub(crate) fn hash_requisicao<T: Serialize>(payload: &T) -> Result<String, ApiError> {
+        let corpo = serde_json::to_vec(payload)
+            .map_err(|e| AppMessage::new(&format!("Erro ao serializar requisição: {}", e), 500))?;
+
+        Ok(format!("{:x}", Sha256::digest(&corpo)))
+    }
+
+    pub(crate) async fn build_draft(pool: &DbPool, id_cliente: &str, payload: CreatePedidoRequest) -> Result<PedidoDraft, ApiError> {
+        let CreatePedidoRequest { produtos, endereco_entrega, id_endereco, pagamento, codigo_cupom, modalidade_frete } = payload;
+
+        let mut endereco_entrega = EnderecoService::resolver_entrega(pool, id_cliente, endereco_entrega, id_endereco).await?;
+        CepService::validar_endereco(&endereco_entrega.cep, &endereco_entrega.codigo_ibge_cidade, &endereco_entrega.codigo_ibge_uf)?;
+        endereco_entrega.cidade = CepService::nome_cidade(&endereco_entrega.codigo_ibge_cidade, endereco_entrega.cidade.take());
+
+        let modalidade_frete = match modalidade_frete {
+            Some(modalidade) => ModalidadeFrete::from_str(&modalidade)?,
+            None => ModalidadeFrete::Economico,
+        };
 
-        let lst_produtos = ProdutoDal::get_by_skus(pool, sku_produtos.clone(), "").await
-            .map_err(|e| AppMessage::new(&format!("Erro ao buscar produtos: {}", e), 500))?;
+        let sku_produtos: Vec<String> = produtos.iter()
+            .map(|produto| produto.sku_produto.clone())
+            .collect();
+
+        let lst_produtos = ProdutoDal::get_by_skus(pool, sku_produtos.clone(), "").await?;
 
         let mut map_produtos = HashMap::new();
-        for produto in &lst_produtos {
-            map_produtos.insert(produto.sku.clone(), produto.clone());
+        for produto in lst_produtos {
+            map_produtos.insert(produto.sku.clone(), produto);
+        }
+
+        for sku in &sku_produtos {
+            if !map_produtos.contains_key(sku) {
+                return Err(AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400).into());
+            }
+        }
+
+        let mut itens = Vec::with_capacity(produtos.len());
+        let mut peso_taxado = BigDecimal::from(0);
+
+        for produto in produtos {
+            let produto_db = &map_produtos[&produto.sku_produto];
+            peso_taxado += FreteService::peso_taxado(produto_db, produto.quantidade);
+
+            let (valor_unitario_original, pct_oferta, valor_unitario) = Self::precificar(&produto_db.preco, &produto_db.pctoferta);
+            let valor_bruto = money::arredondar(&(&valor_unitario * BigDecimal::from(produto.quantidade)));
+
+            itens.push(ItemPedidoDraft {
+                sku_produto: produto.sku_produto,
+                quantidade: produto.quantidade,
+                valor_unitario_original,
+                promocao: pct_oferta.as_ref().map(|_| PROMOCAO_OFERTA_PRODUTO.to_string()),
+                pct_oferta: pct_oferta.unwrap_or_else(money::zero),
+                valor_unitario,
+                valor_liquido: valor_bruto.clone(),
+                valor_bruto,
+                valor_desconto: money::zero(),
+                valor_frete: money::zero(),
+            });
         }
 
-        if sku_produtos.len() != lst_produtos.len() {
-            for sku in sku_produtos {
-                if !map_produtos.contains_key(&sku) {
-                    return Err(AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400));
+        let cupom = match codigo_cupom {
+            Some(codigo) => {
+                let desconto = {
+                    let itens_cupom: Vec<ItemCupom> = itens.iter()
+                        .map(|item| ItemCupom {
+                            sku_produto: &item.sku_produto,
+                            id_categoria: &map_produtos[&item.sku_produto].id_categoria,
+                            valor_bruto: &item.valor_bruto,
+                        })
+                        .collect();
+
+                    CupomService::calcular_desconto(pool, &codigo, &itens_cupom).await?
+                };
+
+                for (item, valor_desconto) in itens.iter_mut().zip(desconto.descontos_itens) {
+                    item.valor_liquido = &item.valor_bruto - &valor_desconto;
+                    item.valor_desconto = valor_desconto;
                 }
+
+                Some(desconto.cupom)
             }
+            None => None,
+        };
+
+        let valor_bruto_total: BigDecimal = itens.iter().map(|item| &item.valor_bruto).sum();
+        let valor_desconto_total: BigDecimal = itens.iter().map(|item| &item.valor_desconto).sum();
+
+        // O frete grátis por valor considera o pedido já com o desconto do cupom
+        let opcao_frete = FreteService::opcao(pool, &CargaFrete {
+            cep: Some(&endereco_entrega.cep),
+            codigo_ibge_uf: &endereco_entrega.codigo_ibge_uf,
+            peso_taxado,
+            valor_pedido: &valor_bruto_total - &valor_desconto_total,
+        }, Some(&endereco_entrega.codigo_ibge_cidade), modalidade_frete).await?;
+        let valor_frete = opcao_frete.valor_frete;
+
+        let fretes_itens = money::ratear(&valor_frete, itens.len());
+        for (item, valor_frete_item) in itens.iter_mut().zip(fretes_itens) {
+            item.valor_frete = valor_frete_item;
         }
-        let codigo_ibge_uf = match &payload["enderecoEntrega"]["codigoIbgeUF"] {
-            Value::String(s) => s.parse::<i64>()
-                .map_err(|_| AppMessage::new(&format!("Código IBGE UF '{}' deve ser um número válido", s), 400))?,
-            Value::Number(n) => n.as_i64()
-                .ok_or_else(|| AppMessage::new("Código IBGE UF inválido", 400))?,
-            _ => return Err(AppMessage::new("Código IBGE UF não informado ou tipo inválido", 400))
+
+        let valor_liquido_total = &valor_bruto_total - &valor_desconto_total + &valor_frete;
+        let parcelas = money::ratear(&valor_liquido_total, pagamento.numero_parcelas as usize);
+        let valor_parcela = parcelas.first().cloned().unwrap_or_else(money::zero);
+
+        Ok(PedidoDraft {
+            id_cliente: id_cliente.to_string(),
+            valor_bruto: valor_bruto_total,
+            valor_desconto: valor_desconto_total,
+            valor_frete,
+            valor_liquido: valor_liquido_total.clone(),
+            modalidade_frete,
+            data_entrega: opcao_frete.data_entrega,
+            endereco_entrega,
+            pagamento: PagamentoDraft {
+                forma_pagamento: FormaPagamento::from_str(&pagamento.forma_pagamento)?,
+                numero_parcelas: pagamento.numero_parcelas,
+                valor_total: valor_liquido_total,
+                valor_parcela,
+            },
+            itens,
+            cupom,
+            origem_carrinho: false,
+        })
+    }
+
+    // Retorna o preço de tabela, a oferta vigente (se houver) e o preço unitário já com a oferta
+    pub(crate) fn precificar(preco: &BigDecimal, pctoferta: &BigDecimal) -> (BigDecimal, Option<BigDecimal>, BigDecimal) {
+        let valor_unitario_original = money::arredondar(preco);
+        let pct_oferta = Self::pct_oferta_ativa(pctoferta);
+        let valor_unitario = match &pct_oferta {
+            Some(pct) => money::arredondar(&(&valor_unitario_original * (BigDecimal::from(100) - pct) / BigDecimal::from(100))),
+            None => valor_unitario_original.clone(),
         };
 
-        let valor_frete = MAPA_FRETE.get(&codigo_ibge_uf)
-            .ok_or_else(|| AppMessage::new(&format!("Código IBGE UF {} não encontrado na tabela de fretes", codigo_ibge_uf), 400))?;
+        (valor_unitario_original, pct_oferta, valor_unitario)
+    }
 
-        let mut valor_bruto_total = 0.0;
-        let mut valor_desconto_total = 0.0;
+    // Oferta vigente é qualquer pctoferta positivo, limitado a 100%
+    fn pct_oferta_ativa(pctoferta: &BigDecimal) -> Option<BigDecimal> {
+        if *pctoferta <= money::zero() {
+            return None;
+        }
+        Some(money::arredondar(pctoferta).min(BigDecimal::from(100)))
+    }
 
-        let porcentagem_desconto = payload["pagamento"]["porcentagemDesconto"].as_f64().unwrap_or(0.0);
+    pub async fn get_all(pool: &DbPool, id_cliente: &str, params: PedidoQueryParams) -> Result<Vec<Pedido>, ApiError> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(20);
 
-        if let Some(produtos_array) = payload["produtos"].as_array_mut() {
-            let num_produtos = produtos_array.len() as f64;
+        let filtro = PedidoFiltro {
+            status: params.status
+                .map(|s| StatusPedido::from_str(&s).map(|status| status.as_str().to_string()))
+                .transpose()?,
+            data_inicio: Self::parse_data_filtro(params.data_inicio, "dataInicio")?,
+            data_fim: Self::parse_data_filtro(params.data_fim, "dataFim")?,
+        };
 
-            for produto in &mut *produtos_array {
-                if let (Some(sku), Some(quantidade)) = (
-                    produto["skuProduto"].as_str(),
-                    produto["quantidade"].as_i64(),
-                ) {
-                    let produto_db = map_produtos.get(sku)
-                        .ok_or_else(|| AppMessage::new(&format!("Produto com SKU igual a \"{}\" não existe", sku), 400))?;
+        if let (Some(inicio), Some(fim)) = (filtro.data_inicio, filtro.data_fim)
+            && inicio > fim {
+            return Err(AppMessage::new("dataInicio deve ser anterior ou igual a dataFim", 400).into());
+        }
 
-                    let estoque = produto_db.estoque.to_f64()
-                        .ok_or_else(|| AppMessage::new(&format!("Estoque inválido para produto {}", sku), 500))?;
+        PedidoDal::get_all_by_cliente(pool, id_cliente, filtro, page, page_size).await
+    }
 
-                    if estoque < quantidade as f64 {
-                        return Err(AppMessage::new(&format!("Produto com SKU igual a \"{}\" está com estoque em falta", sku), 400));
-                    }
+    pub async fn get_by_id(pool: &DbPool, id_cliente: &str, id: &str) -> Result<Value, ApiError> {
+        PedidoDal::get_by_id(pool, id_cliente, id).await
+    }
 
-                    let valor_unitario = produto_db.preco.to_f64()
-                        .ok_or_else(|| AppMessage::new(&format!("Preço inválido para produto {}", sku), 500))?;
+    pub async fn get_boleto_svg(pool: &DbPool, id_cliente: &str, id: &str) -> Result<String, ApiError> {
+        let pagamento = PedidoDal::get_pagamento(pool, id, id_cliente).await?;
 
-                    let valor_bruto = valor_unitario * quantidade as f64;
+        let linha_digitavel = pagamento
+            .filter(|p| p.forma_pagamento == FormaPagamento::Boleto.as_str())
+            .and_then(|p| p.boleto)
+            .ok_or_else(|| AppMessage::new("Pedido não possui boleto", 404))?;
 
-                    let valor_desconto = if porcentagem_desconto > 0.0 {
-                        (valor_bruto * porcentagem_desconto * 100.0).round() / 100.0
-                    } else {
-                        0.0
-                    };
+        let decodificado = boleto::decodificar(&linha_digitavel, Utc::now().date_naive())?;
+        Ok(boleto::codigo_barras_svg(&decodificado.codigo_barras)?)
+    }
 
-                    let valor_liquido = valor_bruto - valor_desconto;
-                    let valor_frete_produto = ((valor_frete / num_produtos) * 100.0).round() / 100.0;
+    // Retorna os bytes da imagem junto com o content type correspondente
+    pub async fn get_pix_qrcode(pool: &DbPool, id_cliente: &str, id: &str, formato: Option<&str>) -> Result<(Vec<u8>, &'static str), ApiError> {
+        let formato = formato.unwrap_or("png").to_lowercase();
+        if formato != "png" && formato != "svg" {
+            return Err(AppMessage::new("Formato deve ser png ou svg", 400).into());
+        }
 
-                    if let Some(obj) = produto.as_object_mut() {
-                        obj.insert("valorUnitario".to_string(), Value::Number(serde_json::Number::from_f64(valor_unitario).unwrap()));
-                        obj.insert("valorBruto".to_string(), Value::Number(serde_json::Number::from_f64(valor_bruto).unwrap()));
-                        obj.insert("valorDesconto".to_string(), Value::Number(serde_json::Number::from_f64(valor_desconto).unwrap()));
-                        obj.insert("valorLiquido".to_string(), Value::Number(serde_json::Number::from_f64(valor_liquido).unwrap()));
-                        obj.insert("valorFrete".to_string(), Value::Number(serde_json::Number::from_f64(valor_frete_produto).unwrap()));
-                    }
+        let pagamento = PedidoDal::get_pagamento(pool, id, id_cliente).await?;
 
-                    valor_bruto_total += valor_bruto;
-                    valor_desconto_total += valor_desconto;
-                }
-            }
+        let payload = pagamento
+            .filter(|p| p.forma_pagamento == FormaPagamento::Pix.as_str())
+            .and_then(|p| p.pix)
+            .ok_or_else(|| AppMessage::new("Pedido não possui PIX", 404))?;
+
+        if !pix::validar_payload(&payload) {
+            return Err(AppMessage::new("Código PIX do pedido é inválido", 422).into());
         }
 
-        let data_atual = Utc::now().naive_utc();
-        let data_entrega = data_atual + Duration::days(3);
-        let data_entrega_iso = data_entrega.format("%Y-%m-%dT%H:%M:%S%.fZ").to_string();
+        if formato == "svg" {
+            Ok((pix::qr_code_svg(&payload)?.into_bytes(), "image/svg+xml"))
+        } else {
+            Ok((pix::qr_code_png(&payload, 8)?, "image/png"))
+        }
+    }
 
-        if let Some(obj) = payload.as_object_mut() {
-            obj.insert("dataEntrega".to_string(), Value::String(data_entrega_iso));
-            obj.insert("valorBruto".to_string(), Value::Number(serde_json::Number::from_f64(valor_bruto_total).unwrap()));
-            obj.insert("valorDesconto".to_string(), Value::Number(serde_json::Number::from_f64(valor_desconto_total).unwrap()));
-            obj.insert("valorFrete".to_string(), Value::Number(serde_json::Number::from_f64(*valor_frete).unwrap()));
+    pub async fn get_comprovante(pool: &DbPool, id: &str, id_cliente: Option<&str>) -> Result<Vec<u8>, ApiError> {
+        let comprovante = PedidoDal::get_comprovante(pool, id, id_cliente).await?;
+        Ok(comprovante::gerar(&comprovante, Utc::now().naive_utc())?)
+    }
 
-            let valor_liquido_total = valor_bruto_total - valor_desconto_total + valor_frete;
-            obj.insert("valorLiquido".to_string(), Value::Number(serde_json::Number::from_f64(valor_liquido_total).unwrap()));
-            obj.insert("status".to_string(), Value::String("P".to_string()));
+    pub async fn get_historico(pool: &DbPool, id_cliente: &str, id: &str) -> Result<Vec<HistoricoStatusPedido>, ApiError> {
+        PedidoDal::get_historico(pool, id, id_cliente).await
+    }
 
-            if let Some(pagamento) = obj.get_mut("pagamento").and_then(|p| p.as_object_mut()) {
-                pagamento.remove("porcentagemDesconto");
-                pagamento.insert("valorTotal".to_string(), Value::Number(serde_json::Number::from_f64(valor_liquido_total).unwrap()));
+    pub async fn alterar_status(pool: &DbPool, id: &str, payload: AlterarStatusPayload, alterado_por: &str) -> Result<Pedido, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
 
-                if let Some(num_parcelas) = pagamento.get("numeroParcelas").and_then(|n| n.as_i64()) {
-                    let valor_parcela = ((valor_liquido_total / num_parcelas as f64) * 100.0).round() / 100.0;
-                    pagamento.insert("valorParcela".to_string(), Value::Number(serde_json::Number::from_f64(valor_parcela).unwrap()));
-                }
-            }
+        let status_novo = StatusPedido::from_str(&payload.status)?;
+        Self::transicionar_status(pool, id, status_novo, alterado_por, payload.motivo).await
+    }
 
-            if let Some(endereco) = obj.get_mut("enderecoEntrega").and_then(|e| e.as_object_mut()) {
-                if let Some(uf_value) = endereco.get("codigoIbgeUF") {
-                    let uf_string = match uf_value {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        _ => uf_value.to_string()
-                    };
-                    endereco.insert("codigoIbgeUF".to_string(), Value::String(uf_string));
-                }
+    pub async fn cancelar(pool: &DbPool, id_cliente: &str, id: &str, payload: CancelarPedidoPayload) -> Result<Pedido, ApiError> {
+        payload.validate().map_err(ValidationError::from)?;
 
-                if let Some(cidade_value) = endereco.get("codigoIbgeCidade") {
-                    let cidade_string = match cidade_value {
-                        Value::String(s) => s.clone(),
-                        Value::Number(n) => n.to_string(),
-                        _ => cidade_value.to_string()
-                    };
-                    endereco.insert("codigoIbgeCidade".to_string(), Value::String(cidade_string));
-                }
+        let pedido = PedidoDal::get_pedido_by_id(pool, id, Some(id_cliente)).await?;
+        let status_atual = StatusPedido::from_str(&pedido.status)?;
+
+        match status_atual {
+            StatusPedido::Pendente | StatusPedido::Pago => {}
+            StatusPedido::Cancelado => {
+                return Err(AppMessage::new("Pedido já está cancelado", 422).into());
+            }
+            StatusPedido::Enviado | StatusPedido::Entregue | StatusPedido::Devolvido => {
+                return Err(AppMessage::new("Pedido já enviado não pode ser cancelado", 422).into());
+            }
+            StatusPedido::Faturado => {
+                return Err(AppMessage::new("Pedido já faturado não pode ser cancelado pelo cliente", 422).into());
             }
         }
 
-        payload.get("idCliente")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| AppMessage::new("idCliente não encontrado", 400))?;
+        Self::validar_transicao(status_atual, StatusPedido::Cancelado)?;
 
-        payload.get("valorBruto")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| AppMessage::new("valorBruto não encontrado", 400))?;
+        let motivo = payload.motivo.or_else(|| Some("Cancelado pelo cliente".to_string()));
+        PedidoDal::cancelar(pool, id, status_atual, id_cliente, motivo).await
+    }
 
-        payload.get("valorDesconto")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| AppMessage::new("valorDesconto não encontrado", 400))?;
+    pub async fn transicionar_status(
+        pool: &DbPool,
+        id: &str,
+        status_novo: StatusPedido,
+        alterado_por: &str,
+        motivo: Option<String>
+    ) -> Result<Pedido, ApiError> {
+        let pedido = PedidoDal::get_pedido_by_id(pool, id, None).await?;
+        let status_atual = StatusPedido::from_str(&pedido.status)?;
+
+        Self::validar_transicao(status_atual, status_novo)?;
+
+        // Cancelar pelo atendimento tem os mesmos efeitos do cancelamento pelo cliente: estoque, cupom e pagamento
+        if status_novo == StatusPedido::Cancelado {
+            return PedidoDal::cancelar(pool, id, status_atual, alterado_por, motivo).await;
+        }
 
-        payload.get("valorFrete")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| AppMessage::new("valorFrete não encontrado", 400))?;
+        PedidoDal::update_status(pool, id, status_atual, status_novo, alterado_por, motivo).await
+    }
 
-        payload.get("valorLiquido")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| AppMessage::new("valorLiquido não encontrado", 400))?;
+    pub fn validar_transicao(status_atual: StatusPedido, status_novo: StatusPedido) -> Result<(), AppMessage> {
+        if !Self::transicao_permitida(status_atual, status_novo) {
+            return Err(AppMessage::new(
+                &format!("Não é possível alterar o status do pedido de {} para {}", status_atual, status_novo),
+                422
+            ));
+        }
+        Ok(())
+    }
 
-        payload.get("dataEntrega")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| AppMessage::new("dataEntrega não encontrado", 400))?;
+    // Devolvido não entra aqui: só a aprovação de uma devolução leva o pedido a esse status
+    fn transicao_permitida(status_atual: StatusPedido, status_novo: StatusPedido) -> bool {
+        use StatusPedido::*;
+
+        matches!(
+            (status_atual, status_novo),
+            (Pendente, Pago)
+                | (Pendente, Cancelado)
+                | (Pago, Faturado)
+                | (Pago, Cancelado)
+                | (Faturado, Enviado)
+                | (Faturado, Cancelado)
+                | (Enviado, Entregue)
+        )
+    }
 
-        if let Some(endereco) = payload.get("enderecoEntrega") {
-            if let Err(e) = serde_json::from_value::<EnderecoPayload>(endereco.clone()) {
-                return Err(AppMessage::new(&format!("Erro no endereço: {}", e), 400));
-            }
+    fn parse_data_filtro(valor: Option<String>, campo: &str) -> Result<Option<NaiveDate>, AppMessage> {
+        match valor {
+            Some(data) => NaiveDate::parse_from_str(&data, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| AppMessage::new(&format!("{} inválida (formato: YYYY-MM-DD)", campo), 400)),
+            None => Ok(None),
         }
+    }
+}
 
-        if let Some(pagamento) = payload.get("pagamento") {
-            if let Err(e) = serde_json::from_value::<PagamentoPayload>(pagamento.clone()) {
-                return Err(AppMessage::new(&format!("Erro no pagamento: {}", e), 400));
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use StatusPedido::*;
 
-        if let Some(produtos) = payload.get("produtos") {
-            if let Err(e) = serde_json::from_value::<Vec<ProdutoPayload>>(produtos.clone()) {
-                return Err(AppMessage::new(&format!("Erro nos produtos: {}", e), 400));
-            }
-        }
+    const TODOS: [StatusPedido; 7] = [Pendente, Pago, Faturado, Enviado, Entregue, Cancelado, Devolvido];
 
-        let payload_json_string = match serde_json::to_string(&payload) {
-            Ok(s) => {
-                s
-            },
-            Err(e) => {
-                return Err(AppMessage::new(&format!("Erro ao serializar payload: {}", e), 400));
-            }
-        };
+    #[test]
+    fn fluxo_normal_do_pedido() {
+        assert!(PedidoService::transicao_permitida(Pendente, Pago));
+        assert!(PedidoService::transicao_permitida(Pago, Faturado));
+        assert!(PedidoService::transicao_permitida(Faturado, Enviado));
+        assert!(PedidoService::transicao_permitida(Enviado, Entregue));
+    }
 
-        let pedido_payload: PedidoPayload = match serde_json::from_str(&payload_json_string) {
-            Ok(p) => {
-                p
-            },
-            Err(e) => {
-                match serde_json::from_value::<PedidoPayload>(payload.clone()) {
-                    Ok(_) => {
-                        return match serde_json::from_value(payload.clone()) {
-                            Ok(pedido_payload) => {
-
-                                let pedido = PedidoDal::create(pool, pedido_payload).await
-                                    .map_err(|e| AppMessage::new(&format!("Erro ao criar pedido: {}", e), 500))?;
-
-                                if let Some(produtos) = pedido.get("produtos").and_then(|p| p.as_array()) {
-                                    for produto in produtos {
-                                        if let (Some(sku), Some(quantidade)) = (
-                                            produto.get("skuProduto").and_then(|s| s.as_str()),
-                                            produto.get("quantidade").and_then(|q| q.as_i64())
-                                        ) {
-                                            ProdutoDal::update_estoque(
-                                                pool,
-                                                &sku.to_string(),
-                                                BigDecimal::from_str(&(-quantidade).to_string()).unwrap()
-                                            ).await
-                                                .map_err(|e| AppMessage::new(&format!("Erro ao atualizar estoque: {}", e), 500))?;
-
-                                            ProdutoDal::update_vendas(
-                                                pool,
-                                                &sku.to_string(),
-                                                BigDecimal::from_str(&quantidade.to_string()).unwrap()
-                                            ).await
-                                                .map_err(|e| AppMessage::new(&format!("Erro ao atualizar vendas: {}", e), 500))?;
-                                        }
-                                    }
-                                }
-
-                                Ok(pedido)
-                            },
-                            Err(e3) => Err(AppMessage::new(&format!("Erro ao converter payload: {}", e3), 400))
-                        };
-                    },
-                    Err(e2) => println!("from_value falha: {}", e2),
-                }
+    #[test]
+    fn cancelamento_somente_antes_do_envio() {
+        assert!(PedidoService::transicao_permitida(Pendente, Cancelado));
+        assert!(PedidoService::transicao_permitida(Pago, Cancelado));
+        assert!(PedidoService::transicao_permitida(Faturado, Cancelado));
+        assert!(!PedidoService::transicao_permitida(Enviado, Cancelado));
+        assert!(!PedidoService::transicao_permitida(Entregue, Cancelado));
+    }
 
-                return Err(AppMessage::new(&format!("Erro ao converter payload: {}", e), 400));
-            }
-        };
+    #[test]
+    fn devolvido_nao_e_alcancado_pela_troca_de_status() {
+        for status in TODOS {
+            assert!(!PedidoService::transicao_permitida(status, Devolvido));
+        }
+    }
 
-        let pedido = PedidoDal::create(pool, pedido_payload).await
-            .map_err(|e| AppMessage::new(&format!("Erro ao criar pedido: {}", e), 500))?;
-
-        if let Some(produtos) = pedido.get("produtos").and_then(|p| p.as_array()) {
-            for produto in produtos {
-                if let (Some(sku), Some(quantidade)) = (
-                    produto.get("skuProduto").and_then(|s| s.as_str()),
-                    produto.get("quantidade").and_then(|q| q.as_i64())
-                ) {
-                    ProdutoDal::update_estoque(
-                        pool,
-                        &sku.to_string(),
-                        BigDecimal::from_str(&(-quantidade).to_string()).unwrap()
-                    ).await
-                        .map_err(|e| AppMessage::new(&format!("Erro ao atualizar estoque: {}", e), 500))?;
-
-                    ProdutoDal::update_vendas(
-                        pool,
-                        &sku.to_string(),
-                        BigDecimal::from_str(&quantidade.to_string()).unwrap()
-                    ).await
-                        .map_err(|e| AppMessage::new(&format!("Erro ao atualizar vendas: {}", e), 500))?;
-                }
-            }
+    #[test]
+    fn nao_pula_etapas_nem_volta() {
+        assert!(!PedidoService::transicao_permitida(Pendente, Faturado));
+        assert!(!PedidoService::transicao_permitida(Pago, Enviado));
+        assert!(!PedidoService::transicao_permitida(Entregue, Enviado));
+        assert!(!PedidoService::transicao_permitida(Pago, Pendente));
+
+        for status in TODOS {
+            assert!(!PedidoService::transicao_permitida(status, status));
         }
+    }
+
+    #[test]
+    fn cancelado_e_devolvido_sao_finais() {
+        for status in TODOS {
+            assert!(!PedidoService::transicao_permitida(Cancelado, status));
+            assert!(!PedidoService::transicao_permitida(Devolvido, status));
+        }
+    }
 
-        Ok(pedido)
+    #[test]
+    fn transicao_recusada_responde_422() {
+        let erro = PedidoService::validar_transicao(Entregue, Pago).unwrap_err();
+        assert_eq!(erro.status_code, 422);
     }
-}
\ No newline at end of file
+}
@@ -96,6 +96,52 @@ impl ResponseError for ValidationError {
     }
 }
 
+impl From<validator::ValidationErrors> for ValidationError {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut messages = Vec::new();
+        collect_validation_messages(&errors, "", &mut messages);
+        messages.sort();
+
+        Self {
+            status: "error".to_string(),
+            message: "Dados inválidos".to_string(),
+            errors: messages,
+        }
+    }
+}
+
+// Monta mensagens no formato "campo.subcampo[indice]: mensagem" para erros aninhados
+fn collect_validation_messages(errors: &validator::ValidationErrors, prefix: &str, messages: &mut Vec<String>) {
+    use validator::ValidationErrorsKind;
+
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for error in field_errors {
+                    let message = error.message.as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| error.code.to_string());
+                    messages.push(format!("{}: {}", path, message));
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_validation_messages(nested, &path, messages);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_validation_messages(nested, &format!("{}[{}]", path, index), messages);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ApiError {
     App(AppMessage),
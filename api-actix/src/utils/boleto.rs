@@ -0,0 +1,349 @@
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDate};
+use crate::utils::app_message::AppMessage;
+use crate::utils::money;
+
+// Boleto bancário no padrão FEBRABAN: código de barras de 44 posições e linha digitável de 47
+//   código de barras: banco(3) moeda(1) DV(1) fator(4) valor(10) campo livre(25)
+//   linha digitável:  campo 1(10) campo 2(11) campo 3(11) DV geral(1) fator + valor(14)
+
+const MOEDA_REAL: char = '9';
+const VALOR_MAXIMO_CENTAVOS: i64 = 9_999_999_999;
+
+// O fator conta os dias desde 07/10/1997 e, ao passar de 9999, recomeça em 1000 (22/02/2025)
+const FATOR_MINIMO: i64 = 1000;
+const FATOR_MAXIMO: i64 = 9999;
+const CICLO_FATOR: i64 = FATOR_MAXIMO - FATOR_MINIMO + 1;
+
+pub struct DadosBoleto<'a> {
+    pub banco: &'a str,
+    pub vencimento: Option<NaiveDate>,
+    pub valor: &'a BigDecimal,
+    pub campo_livre: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Boleto {
+    pub codigo_barras: String,
+    pub linha_digitavel: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct BoletoDecodificado {
+    pub banco: String,
+    pub codigo_barras: String,
+    pub linha_digitavel: String,
+    pub campo_livre: String,
+    pub valor: BigDecimal,
+    pub vencimento: Option<NaiveDate>,
+}
+
+pub fn gerar(dados: &DadosBoleto) -> Result<Boleto, AppMessage> {
+    if !somente_digitos(dados.banco, 3) {
+        return Err(AppMessage::new("Código do banco deve conter 3 dígitos", 400));
+    }
+
+    if !somente_digitos(dados.campo_livre, 25) {
+        return Err(AppMessage::new("Campo livre do boleto deve conter 25 dígitos", 400));
+    }
+
+    let centavos = money::centavos(dados.valor);
+    if !(0..=VALOR_MAXIMO_CENTAVOS).contains(&centavos) {
+        return Err(AppMessage::new("Valor do boleto fora do limite permitido", 400));
+    }
+
+    let fator = match dados.vencimento {
+        Some(vencimento) => fator_vencimento(vencimento)?,
+        None => 0,
+    };
+
+    let sem_dv = format!("{}{}{:04}{:010}{}", dados.banco, MOEDA_REAL, fator, centavos, dados.campo_livre);
+    let codigo_barras = inserir_dv_geral(&sem_dv);
+    let linha_digitavel = linha_digitavel_de(&codigo_barras);
+
+    Ok(Boleto { codigo_barras, linha_digitavel })
+}
+
+// Layout do campo livre do Bradesco: agência(4) carteira(2) nosso número(11) conta(7) zero(1)
+pub fn montar_campo_livre(agencia: &str, carteira: &str, nosso_numero: &str, conta: &str) -> Result<String, AppMessage> {
+    if !somente_digitos(agencia, 4) || !somente_digitos(carteira, 2) || !somente_digitos(nosso_numero, 11) || !somente_digitos(conta, 7) {
+        return Err(AppMessage::new("Dados bancários do boleto inválidos", 400));
+    }
+    Ok(format!("{}{}{}{}0", agencia, carteira, nosso_numero, conta))
+}
+
+pub fn fator_vencimento(vencimento: NaiveDate) -> Result<i64, AppMessage> {
+    let dias = (vencimento - data_base()).num_days();
+    if dias < FATOR_MINIMO {
+        return Err(AppMessage::new("Data de vencimento anterior ao início do fator de vencimento", 400));
+    }
+
+    Ok((dias - FATOR_MINIMO) % CICLO_FATOR + FATOR_MINIMO)
+}
+
+// Como o fator se repete a cada 9000 dias, escolhe a data do ciclo mais próxima da referência
+pub fn data_vencimento(fator: i64, referencia: NaiveDate) -> Option<NaiveDate> {
+    if !(FATOR_MINIMO..=FATOR_MAXIMO).contains(&fator) {
+        return None;
+    }
+
+    let mut data = data_base() + Duration::days(fator);
+    while (referencia - data).num_days() > CICLO_FATOR / 2 {
+        data += Duration::days(CICLO_FATOR);
+    }
+    Some(data)
+}
+
+pub fn decodificar(codigo: &str, referencia: NaiveDate) -> Result<BoletoDecodificado, AppMessage> {
+    let digitos: String = codigo.chars().filter(|c| !c.is_whitespace() && *c != '.').collect();
+
+    if !digitos.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppMessage::new("Boleto deve conter apenas dígitos", 400));
+    }
+
+    let codigo_barras = match digitos.len() {
+        44 => digitos,
+        47 => codigo_barras_de(&digitos)?,
+        _ => return Err(AppMessage::new("Boleto deve ter 44 (código de barras) ou 47 (linha digitável) dígitos", 400)),
+    };
+
+    let sem_dv = format!("{}{}", &codigo_barras[0..4], &codigo_barras[5..44]);
+    if inserir_dv_geral(&sem_dv) != codigo_barras {
+        return Err(AppMessage::new("Dígito verificador geral do boleto inválido", 422));
+    }
+
+    let fator: i64 = codigo_barras[5..9].parse().unwrap_or(0);
+    let centavos: i64 = codigo_barras[9..19].parse().unwrap_or(0);
+
+    Ok(BoletoDecodificado {
+        banco: codigo_barras[0..3].to_string(),
+        linha_digitavel: linha_digitavel_de(&codigo_barras),
+        campo_livre: codigo_barras[19..44].to_string(),
+        valor: BigDecimal::new(centavos.into(), money::CASAS_DECIMAIS),
+        vencimento: data_vencimento(fator, referencia),
+        codigo_barras,
+    })
+}
+
+pub fn formatar_linha_digitavel(linha: &str) -> String {
+    if !somente_digitos(linha, 47) {
+        return linha.to_string();
+    }
+
+    format!(
+        "{}.{} {}.{} {}.{} {} {}",
+        &linha[0..5], &linha[5..10],
+        &linha[10..15], &linha[15..21],
+        &linha[21..26], &linha[26..32],
+        &linha[32..33],
+        &linha[33..47]
+    )
+}
+
+// Intercalado 2 de 5: cada par de dígitos vira cinco barras (primeiro dígito) entremeadas
+// com cinco espaços (segundo dígito); estreito = 1 módulo, largo = 3 módulos.
+// Devolve a sequência (barra?, largura em módulos) do início, dos pares e do fim
+pub fn barras_i25(codigo_barras: &str) -> Result<Vec<(bool, u32)>, AppMessage> {
+    if codigo_barras.is_empty() || !codigo_barras.len().is_multiple_of(2) || !codigo_barras.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppMessage::new("Código de barras deve ter uma quantidade par de dígitos", 400));
+    }
+
+    const ESTREITO: u32 = 1;
+    const LARGO: u32 = 3;
+
+    let largura = |largo: bool| if largo { LARGO } else { ESTREITO };
+
+    let mut elementos: Vec<(bool, u32)> = vec![(true, ESTREITO), (false, ESTREITO), (true, ESTREITO), (false, ESTREITO)];
+
+    let digitos: Vec<usize> = codigo_barras.bytes().map(|b| (b - b'0') as usize).collect();
+    for par in digitos.chunks(2) {
+        for (barra, espaco) in PADROES_I25[par[0]].iter().zip(PADROES_I25[par[1]].iter()) {
+            elementos.push((true, largura(*barra)));
+            elementos.push((false, largura(*espaco)));
+        }
+    }
+
+    elementos.extend([(true, LARGO), (false, ESTREITO), (true, ESTREITO)]);
+
+    Ok(elementos)
+}
+
+pub fn codigo_barras_svg(codigo_barras: &str) -> Result<String, AppMessage> {
+    const ALTURA: u32 = 50;
+    const MARGEM: u32 = 10;
+
+    let elementos = barras_i25(codigo_barras)?;
+
+    let largura_total: u32 = elementos.iter().map(|(_, l)| l).sum::<u32>() + 2 * MARGEM;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">",
+        largura_total, ALTURA, largura_total, ALTURA
+    );
+    svg.push_str(&format!("<rect width=\"{}\" height=\"{}\" fill=\"#fff\"/>", largura_total, ALTURA));
+
+    let mut x = MARGEM;
+    for (barra, l) in elementos {
+        if barra {
+            svg.push_str(&format!("<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#000\"/>", x, l, ALTURA));
+        }
+        x += l;
+    }
+    svg.push_str("</svg>");
+
+    Ok(svg)
+}
+
+// true = elemento largo
+const PADROES_I25: [[bool; 5]; 10] = [
+    [false, false, true, true, false],
+    [true, false, false, false, true],
+    [false, true, false, false, true],
+    [true, true, false, false, false],
+    [false, false, true, false, true],
+    [true, false, true, false, false],
+    [false, true, true, false, false],
+    [false, false, false, true, true],
+    [true, false, false, true, false],
+    [false, true, false, true, false],
+];
+
+fn data_base() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1997, 10, 7).expect("data base do fator de vencimento")
+}
+
+fn inserir_dv_geral(sem_dv: &str) -> String {
+    let dv = modulo11(sem_dv);
+    format!("{}{}{}", &sem_dv[0..4], dv, &sem_dv[4..])
+}
+
+fn linha_digitavel_de(codigo_barras: &str) -> String {
+    let campo1 = format!("{}{}", &codigo_barras[0..4], &codigo_barras[19..24]);
+    let campo2 = &codigo_barras[24..34];
+    let campo3 = &codigo_barras[34..44];
+
+    format!(
+        "{}{}{}{}{}{}{}{}",
+        campo1, modulo10(&campo1),
+        campo2, modulo10(campo2),
+        campo3, modulo10(campo3),
+        &codigo_barras[4..5],
+        &codigo_barras[5..19]
+    )
+}
+
+fn codigo_barras_de(linha: &str) -> Result<String, AppMessage> {
+    let campos = [(&linha[0..9], &linha[9..10]), (&linha[10..20], &linha[20..21]), (&linha[21..31], &linha[31..32])];
+
+    for (i, (campo, dv)) in campos.iter().enumerate() {
+        if modulo10(campo).to_string() != *dv {
+            return Err(AppMessage::new(&format!("Dígito verificador do campo {} da linha digitável inválido", i + 1), 422));
+        }
+    }
+
+    Ok(format!(
+        "{}{}{}{}{}{}",
+        &linha[0..4],
+        &linha[32..33],
+        &linha[33..47],
+        &linha[4..9],
+        &linha[10..20],
+        &linha[21..31]
+    ))
+}
+
+// Pesos 2 e 1 alternados da direita para a esquerda, somando os dígitos de cada produto
+fn modulo10(digitos: &str) -> u32 {
+    let soma: u32 = digitos.bytes().rev()
+        .map(|b| (b - b'0') as u32)
+        .zip([2, 1].iter().cycle())
+        .map(|(d, peso)| {
+            let produto = d * peso;
+            produto / 10 + produto % 10
+        })
+        .sum();
+
+    (10 - soma % 10) % 10
+}
+
+// Pesos de 2 a 9 da direita para a esquerda; resultados 0, 10 e 11 viram 1
+fn modulo11(digitos: &str) -> u32 {
+    let soma: u32 = digitos.bytes().rev()
+        .map(|b| (b - b'0') as u32)
+        .zip((2..=9).cycle())
+        .map(|(d, peso)| d * peso)
+        .sum();
+
+    match 11 - soma % 11 {
+        0 | 10 | 11 => 1,
+        dv => dv,
+    }
+}
+
+fn somente_digitos(valor: &str, tamanho: usize) -> bool {
+    valor.len() == tamanho && valor.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // Boleto de exemplo do Banco do Brasil: R$ 1,00 com vencimento em 31/12/2007
+    const LINHA_DIGITAVEL_BB: &str = "00190.50095 40144.816069 06809.350314 3 37370000000100";
+    const CODIGO_BARRAS_BB: &str = "00193373700000001000500940144816060680935031";
+
+    fn data(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(ano, mes, dia).unwrap()
+    }
+
+    #[test]
+    fn modulo10_dos_campos_da_linha_digitavel() {
+        assert_eq!(modulo10("001905009"), 5);
+        assert_eq!(modulo10("4014481606"), 9);
+        assert_eq!(modulo10("0680935031"), 4);
+    }
+
+    #[test]
+    fn modulo11_do_codigo_de_barras() {
+        let sem_dv = format!("{}{}", &CODIGO_BARRAS_BB[0..4], &CODIGO_BARRAS_BB[5..]);
+        assert_eq!(modulo11(&sem_dv), 3);
+    }
+
+    #[test]
+    fn gera_o_boleto_de_exemplo() {
+        let valor = BigDecimal::from_str("1.00").unwrap();
+        let boleto = gerar(&DadosBoleto {
+            banco: "001",
+            vencimento: Some(data(2007, 12, 31)),
+            valor: &valor,
+            campo_livre: "0500940144816060680935031",
+        }).unwrap();
+
+        assert_eq!(boleto.codigo_barras, CODIGO_BARRAS_BB);
+        assert_eq!(formatar_linha_digitavel(&boleto.linha_digitavel), LINHA_DIGITAVEL_BB);
+    }
+
+    #[test]
+    fn decodifica_a_linha_digitavel_de_exemplo() {
+        let boleto = decodificar(LINHA_DIGITAVEL_BB, data(2007, 12, 1)).unwrap();
+
+        assert_eq!(boleto.banco, "001");
+        assert_eq!(boleto.codigo_barras, CODIGO_BARRAS_BB);
+        assert_eq!(boleto.valor, BigDecimal::from_str("1.00").unwrap());
+        assert_eq!(boleto.vencimento, Some(data(2007, 12, 31)));
+    }
+
+    #[test]
+    fn rejeita_digito_verificador_alterado() {
+        assert!(decodificar("00190.50095 40144.816069 06809.350314 4 37370000000100", data(2007, 12, 1)).is_err());
+        assert!(decodificar("00190.50096 40144.816069 06809.350314 3 37370000000100", data(2007, 12, 1)).is_err());
+    }
+
+    #[test]
+    fn fator_de_vencimento_recomeca_em_1000_em_22_02_2025() {
+        assert_eq!(fator_vencimento(data(2025, 2, 21)).unwrap(), 9999);
+        assert_eq!(fator_vencimento(data(2025, 2, 22)).unwrap(), 1000);
+        assert_eq!(data_vencimento(1000, data(2025, 3, 1)), Some(data(2025, 2, 22)));
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::HashSet;
+
+// Domingo de Páscoa pelo algoritmo de Meeus/Jones/Butcher (calendário gregoriano)
+pub fn pascoa(ano: i32) -> NaiveDate {
+    let a = ano % 19;
+    let b = ano / 100;
+    let c = ano % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let mes = (h + l - 7 * m + 114) / 31;
+    let dia = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(ano, mes as u32, dia as u32).expect("data da Páscoa válida")
+}
+
+// Feriados nacionais fixos mais os móveis que dependem da Páscoa; Carnaval não é feriado
+// por lei, mas as transportadoras não operam na segunda e na terça
+pub fn feriados_nacionais(ano: i32) -> Vec<(NaiveDate, &'static str)> {
+    let fixos = [
+        (1, 1, "Confraternização Universal"),
+        (4, 21, "Tiradentes"),
+        (5, 1, "Dia do Trabalho"),
+        (9, 7, "Independência do Brasil"),
+        (10, 12, "Nossa Senhora Aparecida"),
+        (11, 2, "Finados"),
+        (11, 15, "Proclamação da República"),
+        (12, 25, "Natal"),
+    ];
+
+    let mut feriados: Vec<(NaiveDate, &'static str)> = fixos.iter()
+        .filter_map(|(mes, dia, descricao)| NaiveDate::from_ymd_opt(ano, *mes, *dia).map(|data| (data, *descricao)))
+        .collect();
+
+    // Lei 14.759/2023
+    if ano >= 2024
+        && let Some(data) = NaiveDate::from_ymd_opt(ano, 11, 20) {
+        feriados.push((data, "Dia Nacional de Zumbi e da Consciência Negra"));
+    }
+
+    let domingo_pascoa = pascoa(ano);
+    feriados.push((domingo_pascoa - Duration::days(48), "Carnaval"));
+    feriados.push((domingo_pascoa - Duration::days(47), "Carnaval"));
+    feriados.push((domingo_pascoa - Duration::days(2), "Sexta-feira Santa"));
+    feriados.push((domingo_pascoa + Duration::days(60), "Corpus Christi"));
+
+    feriados.sort_by_key(|(data, _)| *data);
+    feriados
+}
+
+pub fn eh_dia_util(data: NaiveDate, feriados: &HashSet<NaiveDate>) -> bool {
+    !matches!(data.weekday(), Weekday::Sat | Weekday::Sun) && !feriados.contains(&data)
+}
+
+// A contagem começa no dia seguinte ao início, que nunca conta como dia útil do prazo
+pub fn adicionar_dias_uteis(inicio: NaiveDate, dias_uteis: u32, feriados: &HashSet<NaiveDate>) -> NaiveDate {
+    let mut data = inicio;
+    let mut restantes = dias_uteis;
+
+    while restantes > 0 {
+        data += Duration::days(1);
+        if eh_dia_util(data, feriados) {
+            restantes -= 1;
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(ano: i32, mes: u32, dia: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(ano, mes, dia).unwrap()
+    }
+
+    fn feriados(ano: i32) -> HashSet<NaiveDate> {
+        feriados_nacionais(ano).into_iter().map(|(data, _)| data).collect()
+    }
+
+    #[test]
+    fn pascoa_de_anos_conhecidos() {
+        assert_eq!(pascoa(2000), data(2000, 4, 23));
+        assert_eq!(pascoa(2008), data(2008, 3, 23));
+        assert_eq!(pascoa(2024), data(2024, 3, 31));
+        assert_eq!(pascoa(2025), data(2025, 4, 20));
+        assert_eq!(pascoa(2026), data(2026, 4, 5));
+        // Datas extremas possíveis: 22 de março e 25 de abril
+        assert_eq!(pascoa(1818), data(1818, 3, 22));
+        assert_eq!(pascoa(2038), data(2038, 4, 25));
+    }
+
+    #[test]
+    fn feriados_moveis_de_2025() {
+        let feriados = feriados(2025);
+
+        assert!(feriados.contains(&data(2025, 3, 3)));
+        assert!(feriados.contains(&data(2025, 3, 4)));
+        assert!(feriados.contains(&data(2025, 4, 18)));
+        assert!(feriados.contains(&data(2025, 6, 19)));
+        assert!(!feriados.contains(&data(2025, 3, 5)));
+    }
+
+    #[test]
+    fn consciencia_negra_a_partir_de_2024() {
+        assert!(!feriados(2023).contains(&data(2023, 11, 20)));
+        assert!(feriados(2024).contains(&data(2024, 11, 20)));
+    }
+
+    #[test]
+    fn dias_uteis_pulam_fim_de_semana_e_feriados() {
+        let feriados = feriados(2025);
+
+        // Quinta antes da Sexta-feira Santa, com Tiradentes na segunda
+        assert_eq!(adicionar_dias_uteis(data(2025, 4, 17), 1, &feriados), data(2025, 4, 22));
+        // Sábado antes do Carnaval
+        assert_eq!(adicionar_dias_uteis(data(2025, 3, 1), 1, &feriados), data(2025, 3, 5));
+        assert_eq!(adicionar_dias_uteis(data(2025, 3, 1), 5, &feriados), data(2025, 3, 11));
+    }
+
+    #[test]
+    fn o_dia_inicial_nao_conta() {
+        let feriados = HashSet::new();
+
+        assert_eq!(adicionar_dias_uteis(data(2025, 6, 2), 0, &feriados), data(2025, 6, 2));
+        assert_eq!(adicionar_dias_uteis(data(2025, 6, 2), 1, &feriados), data(2025, 6, 3));
+        assert_eq!(adicionar_dias_uteis(data(2025, 6, 6), 1, &feriados), data(2025, 6, 9));
+    }
+}
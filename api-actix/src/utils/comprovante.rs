@@ -0,0 +1,285 @@
+use chrono::NaiveDateTime;
+use std::str::FromStr;
+use crate::models::frete::ModalidadeFrete;
+use crate::models::pedido::{ComprovantePedido, EnderecosEntrega, FormaPagamento, Pagamento, StatusPedido};
+use crate::utils::app_message::AppMessage;
+use crate::utils::pdf::{self, Documento, Fonte};
+use crate::utils::{boleto, ibge, money, pix};
+
+const MARGEM: f32 = 40.0;
+const DIREITA: f32 = pdf::LARGURA_A4 - MARGEM;
+const LARGURA_UTIL: f32 = DIREITA - MARGEM;
+
+// Colunas da tabela de itens; os valores são alinhados pela borda direita de cada coluna
+const COLUNA_QTD: f32 = 330.0;
+const COLUNA_UNITARIO: f32 = 400.0;
+const COLUNA_DESCONTO: f32 = 470.0;
+const COLUNA_TOTAL: f32 = DIREITA;
+
+const MODULO_QR_CODE: f32 = 3.0;
+const MODULO_CODIGO_BARRAS: f32 = 1.0;
+const ALTURA_CODIGO_BARRAS: f32 = 40.0;
+
+// Posição vertical de escrita, que abre uma nova página quando o próximo bloco não cabe
+struct Cursor {
+    documento: Documento,
+    y: f32,
+}
+
+impl Cursor {
+    fn new() -> Self {
+        Self { documento: Documento::new(), y: pdf::ALTURA_A4 - MARGEM }
+    }
+
+    fn reservar(&mut self, altura: f32) {
+        if self.y - altura < MARGEM {
+            self.documento.nova_pagina();
+            self.y = pdf::ALTURA_A4 - MARGEM;
+        }
+    }
+
+    fn linha_texto(&mut self, tamanho: f32, fonte: Fonte, texto: &str) {
+        self.reservar(tamanho + 4.0);
+        self.y -= tamanho + 4.0;
+        self.documento.texto(MARGEM, self.y, tamanho, fonte, texto);
+    }
+
+    fn par(&mut self, rotulo: &str, valor: &str) {
+        self.reservar(14.0);
+        self.y -= 14.0;
+        self.documento.texto(MARGEM, self.y, 10.0, Fonte::Negrito, rotulo);
+        self.documento.texto(MARGEM + 110.0, self.y, 10.0, Fonte::Normal, valor);
+    }
+
+    fn total(&mut self, rotulo: &str, valor: &str, fonte: Fonte) {
+        self.reservar(14.0);
+        self.y -= 14.0;
+        self.documento.texto_direita(COLUNA_DESCONTO, self.y, 10.0, fonte, rotulo);
+        self.documento.texto_direita(COLUNA_TOTAL, self.y, 10.0, fonte, valor);
+    }
+
+    fn secao(&mut self, titulo: &str) {
+        self.reservar(40.0);
+        self.y -= 24.0;
+        self.documento.texto(MARGEM, self.y, 12.0, Fonte::Negrito, titulo);
+        self.y -= 4.0;
+        self.documento.linha(MARGEM, self.y, DIREITA, self.y, 0.5);
+    }
+
+    fn paragrafo(&mut self, tamanho: f32, fonte: Fonte, texto: &str) {
+        for linha in pdf::quebrar_linhas(texto, LARGURA_UTIL, tamanho, fonte) {
+            self.linha_texto(tamanho, fonte, &linha);
+        }
+    }
+}
+
+pub fn gerar(comprovante: &ComprovantePedido, emitido_em: NaiveDateTime) -> Result<Vec<u8>, AppMessage> {
+    let pedido = &comprovante.pedido;
+    let mut cursor = Cursor::new();
+
+    cursor.y -= 18.0;
+    cursor.documento.texto(MARGEM, cursor.y, 18.0, Fonte::Negrito, "Comprovante do pedido");
+    cursor.documento.texto_direita(DIREITA, cursor.y, 9.0, Fonte::Normal, &format!("Emitido em {}", emitido_em.format("%d/%m/%Y %H:%M")));
+    cursor.y -= 8.0;
+
+    let status = StatusPedido::from_str(&pedido.status)
+        .map(|status| status.descricao().to_string())
+        .unwrap_or_else(|_| pedido.status.clone());
+    let modalidade = ModalidadeFrete::from_str(&pedido.modalidade_frete)
+        .map(|modalidade| modalidade.descricao().to_string())
+        .unwrap_or_else(|_| pedido.modalidade_frete.clone());
+
+    cursor.par("Pedido", &pedido.id);
+    cursor.par("Data", &pedido.created_at.format("%d/%m/%Y %H:%M").to_string());
+    cursor.par("Situação", &status);
+    cursor.par("Entrega", &modalidade);
+    cursor.par("Previsão", &pedido.data_entrega.format("%d/%m/%Y").to_string());
+
+    if let Some(endereco) = &comprovante.endereco {
+        escrever_endereco(&mut cursor, endereco);
+    }
+
+    escrever_itens(&mut cursor, comprovante);
+
+    cursor.y -= 6.0;
+    cursor.total("Subtotal", &money::formatar_reais(&pedido.valor_bruto), Fonte::Normal);
+    if pedido.valor_desconto > money::zero() {
+        let rotulo = match &comprovante.codigo_cupom {
+            Some(codigo) => format!("Descontos (cupom {})", codigo),
+            None => "Descontos".to_string(),
+        };
+        cursor.total(&rotulo, &money::formatar_reais(&-&pedido.valor_desconto), Fonte::Normal);
+    }
+    let frete = if pedido.valor_frete > money::zero() { money::formatar_reais(&pedido.valor_frete) } else { "Grátis".to_string() };
+    cursor.total("Frete", &frete, Fonte::Normal);
+    cursor.total("Total", &money::formatar_reais(&pedido.valor_liquido), Fonte::Negrito);
+
+    if let Some(pagamento) = &comprovante.pagamento {
+        escrever_pagamento(&mut cursor, pagamento)?;
+    }
+
+    Ok(cursor.documento.finalizar())
+}
+
+fn escrever_endereco(cursor: &mut Cursor, endereco: &EnderecosEntrega) {
+    let sigla_uf = ibge::uf(&endereco.codigo_ibge_uf).map(|(sigla, _)| sigla).unwrap_or(&endereco.codigo_ibge_uf);
+    let cidade = endereco.nome_cidade()
+        .unwrap_or_else(|| format!("Município {}", endereco.codigo_ibge_cidade));
+
+    let mut logradouro = format!("{}, {}", endereco.logradouro, endereco.numero);
+    if let Some(complemento) = &endereco.complemento {
+        logradouro.push_str(&format!(" - {}", complemento));
+    }
+
+    cursor.secao("Endereço de entrega");
+    cursor.linha_texto(10.0, Fonte::Normal, &endereco.nome_remetente);
+    cursor.paragrafo(10.0, Fonte::Normal, &logradouro);
+    cursor.linha_texto(10.0, Fonte::Normal, &format!("{} - {}/{}", endereco.bairro, cidade, sigla_uf));
+    let cep = match endereco.cep.len() {
+        8 => format!("{}-{}", &endereco.cep[..5], &endereco.cep[5..]),
+        _ => endereco.cep.clone(),
+    };
+    cursor.linha_texto(10.0, Fonte::Normal, &format!("CEP {}", cep));
+}
+
+fn escrever_itens(cursor: &mut Cursor, comprovante: &ComprovantePedido) {
+    cursor.secao("Itens");
+
+    cursor.reservar(14.0);
+    cursor.y -= 14.0;
+    let y = cursor.y;
+    let documento = &mut cursor.documento;
+    documento.texto(MARGEM, y, 9.0, Fonte::Negrito, "Produto");
+    documento.texto_direita(COLUNA_QTD, y, 9.0, Fonte::Negrito, "Qtd");
+    documento.texto_direita(COLUNA_UNITARIO, y, 9.0, Fonte::Negrito, "Unitário");
+    documento.texto_direita(COLUNA_DESCONTO, y, 9.0, Fonte::Negrito, "Desconto");
+    documento.texto_direita(COLUNA_TOTAL, y, 9.0, Fonte::Negrito, "Total");
+
+    // O nome ocupa o espaço até a coluna de quantidade, quebrando em mais linhas se preciso
+    let largura_nome = COLUNA_QTD - MARGEM - 40.0;
+
+    for (item, nome) in &comprovante.itens {
+        let descricao = match nome {
+            Some(nome) => format!("{} ({})", nome, item.sku_produto),
+            None => item.sku_produto.clone(),
+        };
+        let linhas_nome = pdf::quebrar_linhas(&descricao, largura_nome, 9.0, Fonte::Normal);
+        let em_oferta = item.valor_unitario_original > item.valor_unitario;
+        let altura = 13.0 * (linhas_nome.len() + usize::from(em_oferta)) as f32;
+
+        cursor.reservar(altura + 4.0);
+        cursor.y -= 13.0;
+        let y = cursor.y;
+        let documento = &mut cursor.documento;
+
+        documento.texto_direita(COLUNA_QTD, y, 9.0, Fonte::Normal, &item.quantidade.with_scale(0).to_string());
+        documento.texto_direita(COLUNA_UNITARIO, y, 9.0, Fonte::Normal, &money::formatar_reais(&item.valor_unitario));
+        documento.texto_direita(COLUNA_DESCONTO, y, 9.0, Fonte::Normal, &money::formatar_reais(&item.valor_desconto));
+        documento.texto_direita(COLUNA_TOTAL, y, 9.0, Fonte::Normal, &money::formatar_reais(&(&item.valor_bruto - &item.valor_desconto)));
+
+        for (i, linha) in linhas_nome.iter().enumerate() {
+            if i > 0 {
+                cursor.y -= 13.0;
+            }
+            cursor.documento.texto(MARGEM, cursor.y, 9.0, Fonte::Normal, linha);
+        }
+
+        if em_oferta {
+            cursor.y -= 13.0;
+            let oferta = match &item.promocao {
+                Some(promocao) => format!("De {} por {} ({})", money::formatar_reais(&item.valor_unitario_original), money::formatar_reais(&item.valor_unitario), promocao),
+                None => format!("De {} por {}", money::formatar_reais(&item.valor_unitario_original), money::formatar_reais(&item.valor_unitario)),
+            };
+            cursor.documento.texto(MARGEM + 10.0, cursor.y, 8.0, Fonte::Normal, &oferta);
+        }
+
+        cursor.y -= 4.0;
+        cursor.documento.linha(MARGEM, cursor.y, DIREITA, cursor.y, 0.25);
+    }
+}
+
+fn escrever_pagamento(cursor: &mut Cursor, pagamento: &Pagamento) -> Result<(), AppMessage> {
+    let forma = FormaPagamento::from_str(&pagamento.forma_pagamento).ok();
+
+    cursor.secao("Pagamento");
+    cursor.par("Forma", forma.map(|forma| forma.descricao()).unwrap_or(&pagamento.forma_pagamento));
+
+    // Mesmo rateio do detalhe do pedido: os centavos que sobram vão para as primeiras parcelas
+    let parcelas = money::ratear(&pagamento.valor_total, pagamento.numero_parcelas.max(1) as usize);
+    if parcelas.len() == 1 {
+        cursor.par("Valor", &format!("{} à vista", money::formatar_reais(&pagamento.valor_total)));
+    } else if parcelas.iter().all(|parcela| *parcela == parcelas[0]) {
+        cursor.par("Parcelamento", &format!("{}x de {}", parcelas.len(), money::formatar_reais(&parcelas[0])));
+    } else {
+        cursor.par("Parcelamento", &format!("{}x, total de {}", parcelas.len(), money::formatar_reais(&pagamento.valor_total)));
+        for (i, parcela) in parcelas.iter().enumerate() {
+            cursor.par("", &format!("{}ª parcela: {}", i + 1, money::formatar_reais(parcela)));
+        }
+    }
+
+    if let Some(tid) = &pagamento.tid {
+        cursor.par("Transação", tid);
+    }
+
+    match forma {
+        Some(FormaPagamento::Boleto) => {
+            if let Some(linha) = &pagamento.boleto {
+                escrever_boleto(cursor, linha);
+            }
+        }
+        Some(FormaPagamento::Pix) => {
+            if let Some(payload) = &pagamento.pix {
+                escrever_pix(cursor, payload)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// A linha digitável vem sempre; o código de barras só quando ela é um boleto válido
+fn escrever_boleto(cursor: &mut Cursor, linha_digitavel: &str) {
+    cursor.y -= 6.0;
+    cursor.linha_texto(10.0, Fonte::Negrito, "Linha digitável");
+    cursor.linha_texto(11.0, Fonte::Mono, &boleto::formatar_linha_digitavel(linha_digitavel));
+
+    let barras = boleto::decodificar(linha_digitavel, chrono::Utc::now().date_naive())
+        .and_then(|decodificado| boleto::barras_i25(&decodificado.codigo_barras));
+
+    if let Ok(barras) = barras {
+        cursor.reservar(ALTURA_CODIGO_BARRAS + 10.0);
+        cursor.y -= ALTURA_CODIGO_BARRAS + 10.0;
+
+        let mut x = MARGEM;
+        for (barra, modulos) in barras {
+            let largura = modulos as f32 * MODULO_CODIGO_BARRAS;
+            if barra {
+                cursor.documento.retangulo(x, cursor.y, largura, ALTURA_CODIGO_BARRAS);
+            }
+            x += largura;
+        }
+    }
+}
+
+fn escrever_pix(cursor: &mut Cursor, payload: &str) -> Result<(), AppMessage> {
+    let (modulos, escuros) = pix::qr_code_modulos(payload)?;
+    let lado = modulos as f32 * MODULO_QR_CODE;
+
+    cursor.y -= 6.0;
+    cursor.linha_texto(10.0, Fonte::Negrito, "PIX copia e cola");
+    cursor.paragrafo(8.0, Fonte::Mono, payload);
+
+    cursor.reservar(lado + 16.0);
+    cursor.y -= lado + 16.0;
+
+    // Linha 0 do QR Code é a de cima, e o eixo y do PDF cresce para cima
+    let topo = cursor.y + lado;
+    for (i, _) in escuros.iter().enumerate().filter(|(_, escuro)| **escuro) {
+        let x = MARGEM + (i % modulos) as f32 * MODULO_QR_CODE;
+        let y = topo - ((i / modulos) + 1) as f32 * MODULO_QR_CODE;
+        cursor.documento.retangulo(x, y, MODULO_QR_CODE, MODULO_QR_CODE);
+    }
+
+    Ok(())
+}
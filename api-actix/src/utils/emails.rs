@@ -0,0 +1,212 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use crate::models::cliente::ClienteResponse;
+use crate::models::frete::ModalidadeFrete;
+use crate::models::pedido::{ComprovantePedido, EnderecosEntrega, FormaPagamento, HistoricoStatusPedido, StatusPagamento, StatusPedido};
+use crate::utils::{ibge, money};
+
+// Textos das notificações ao cliente, em texto puro para aparecer igual em qualquer leitor
+pub struct Mensagem {
+    pub assunto: String,
+    pub texto: String,
+}
+
+pub fn conta_criada(loja: &str, cliente: &ClienteResponse) -> Mensagem {
+    let mut texto = saudacao(cliente);
+    let _ = writeln!(texto, "Sua conta na {} foi criada com o e-mail {}.", loja, cliente.email);
+    texto.push('\n');
+    texto.push_str("A partir de agora você acompanha seus pedidos, salva endereços de entrega e finaliza compras com mais rapidez.\n\n");
+    texto.push_str("Se não foi você quem fez este cadastro, entre em contato com o nosso atendimento.\n");
+
+    Mensagem {
+        assunto: format!("Sua conta na {} foi criada", loja),
+        texto: assinar(texto, loja),
+    }
+}
+
+pub fn pedido_recebido(loja: &str, cliente: &ClienteResponse, comprovante: &ComprovantePedido) -> Mensagem {
+    let pedido = &comprovante.pedido;
+
+    let mut texto = saudacao(cliente);
+    let _ = writeln!(texto, "Recebemos o seu pedido {}. Confira o resumo:", pedido.id);
+    texto.push('\n');
+    escrever_itens(&mut texto, comprovante);
+    escrever_entrega(&mut texto, comprovante);
+
+    if let Some(pagamento) = &comprovante.pagamento {
+        let forma = FormaPagamento::from_str(&pagamento.forma_pagamento).ok();
+        texto.push('\n');
+        let _ = writeln!(texto, "Pagamento: {}", forma.map(|forma| forma.descricao()).unwrap_or(&pagamento.forma_pagamento));
+
+        match StatusPagamento::from_str(&pagamento.status) {
+            Ok(StatusPagamento::Aprovado) => texto.push_str("O pagamento já foi aprovado e o pedido segue para separação.\n"),
+            Ok(StatusPagamento::Pendente) => {
+                // Boleto e PIX vão no e-mail para o cliente conseguir pagar sem voltar à loja
+                match (forma, &pagamento.boleto, &pagamento.pix) {
+                    (Some(FormaPagamento::Boleto), Some(linha), _) => {
+                        let _ = writeln!(texto, "Linha digitável do boleto: {}", linha);
+                    }
+                    (Some(FormaPagamento::Pix), _, Some(payload)) => {
+                        let _ = writeln!(texto, "PIX copia e cola: {}", payload);
+                    }
+                    _ => {}
+                }
+                texto.push_str("Assim que o pagamento for confirmado avisaremos por aqui.\n");
+            }
+            _ => {}
+        }
+    }
+
+    Mensagem {
+        assunto: format!("Recebemos o seu pedido {}", pedido.id),
+        texto: assinar(texto, loja),
+    }
+}
+
+pub fn pagamento_recebido(loja: &str, cliente: &ClienteResponse, comprovante: &ComprovantePedido) -> Mensagem {
+    let pedido = &comprovante.pedido;
+
+    let mut texto = saudacao(cliente);
+    match &comprovante.pagamento {
+        Some(pagamento) => {
+            let forma = FormaPagamento::from_str(&pagamento.forma_pagamento)
+                .map(|forma| forma.descricao().to_lowercase())
+                .unwrap_or_else(|_| pagamento.forma_pagamento.clone());
+            let _ = writeln!(
+                texto,
+                "Confirmamos o pagamento de {} via {} do pedido {}.",
+                money::formatar_reais(&pagamento.valor_total), forma, pedido.id
+            );
+        }
+        None => {
+            let _ = writeln!(texto, "Confirmamos o pagamento do pedido {}.", pedido.id);
+        }
+    }
+    texto.push('\n');
+    texto.push_str("O pedido já está em separação. ");
+    let _ = writeln!(texto, "{}", previsao(comprovante));
+
+    Mensagem {
+        assunto: format!("Pagamento do pedido {} confirmado", pedido.id),
+        texto: assinar(texto, loja),
+    }
+}
+
+// Nem toda transição vira e-mail: a criação e a aprovação do pagamento já têm mensagem própria
+pub fn status_alterado(
+    loja: &str,
+    cliente: &ClienteResponse,
+    comprovante: &ComprovantePedido,
+    historico: &HistoricoStatusPedido
+) -> Option<Mensagem> {
+    let pedido = &comprovante.pedido;
+    let status = StatusPedido::from_str(&historico.status_novo).ok()?;
+    let retirada = matches!(ModalidadeFrete::from_str(&pedido.modalidade_frete), Ok(ModalidadeFrete::Retirada));
+
+    let mut texto = saudacao(cliente);
+    match status {
+        StatusPedido::Pendente | StatusPedido::Pago => return None,
+        StatusPedido::Faturado => {
+            let _ = writeln!(texto, "O pedido {} foi faturado e está sendo preparado.", pedido.id);
+            let _ = writeln!(texto, "{}", previsao(comprovante));
+        }
+        StatusPedido::Enviado if retirada => {
+            let _ = writeln!(texto, "O pedido {} está pronto para retirada na loja.", pedido.id);
+        }
+        StatusPedido::Enviado => {
+            let _ = writeln!(texto, "O pedido {} saiu para entrega.", pedido.id);
+            let _ = writeln!(texto, "{}", previsao(comprovante));
+        }
+        StatusPedido::Entregue => {
+            let _ = writeln!(texto, "O pedido {} foi entregue. Obrigado por comprar na {}!", pedido.id, loja);
+        }
+        StatusPedido::Cancelado => {
+            let _ = writeln!(texto, "O pedido {} foi cancelado.", pedido.id);
+            if let Some(motivo) = &historico.motivo {
+                let _ = writeln!(texto, "Motivo: {}", motivo);
+            }
+            let estorno = comprovante.pagamento.as_ref()
+                .and_then(|pagamento| StatusPagamento::from_str(&pagamento.status).ok())
+                .is_some_and(|status| matches!(status, StatusPagamento::EstornoSolicitado | StatusPagamento::Estornado));
+            if estorno {
+                texto.push_str("O estorno do valor pago já foi solicitado e aparece conforme o prazo da forma de pagamento.\n");
+            }
+        }
+        StatusPedido::Devolvido => {
+            let _ = writeln!(texto, "Registramos a devolução do pedido {}.", pedido.id);
+        }
+    }
+
+    Some(Mensagem {
+        assunto: format!("Pedido {}: {}", pedido.id, status.descricao().to_lowercase()),
+        texto: assinar(texto, loja),
+    })
+}
+
+fn saudacao(cliente: &ClienteResponse) -> String {
+    format!("Olá, {}!\n\n", cliente.nome.trim())
+}
+
+fn assinar(mut texto: String, loja: &str) -> String {
+    let _ = write!(texto, "\nAtenciosamente,\nEquipe {}\n", loja);
+    texto
+}
+
+fn previsao(comprovante: &ComprovantePedido) -> String {
+    format!("Previsão de entrega: {}.", comprovante.pedido.data_entrega.format("%d/%m/%Y"))
+}
+
+fn escrever_itens(texto: &mut String, comprovante: &ComprovantePedido) {
+    let pedido = &comprovante.pedido;
+
+    for (item, nome) in &comprovante.itens {
+        let _ = writeln!(
+            texto,
+            "  {} x {} - {}",
+            item.quantidade.with_scale(0),
+            nome.as_deref().unwrap_or(&item.sku_produto),
+            money::formatar_reais(&item.valor_bruto)
+        );
+    }
+
+    texto.push('\n');
+    let _ = writeln!(texto, "Subtotal: {}", money::formatar_reais(&pedido.valor_bruto));
+    if pedido.valor_desconto > money::zero() {
+        let rotulo = match &comprovante.codigo_cupom {
+            Some(codigo) => format!("Descontos (cupom {})", codigo),
+            None => "Descontos".to_string(),
+        };
+        let _ = writeln!(texto, "{}: {}", rotulo, money::formatar_reais(&-&pedido.valor_desconto));
+    }
+    if pedido.valor_frete > money::zero() {
+        let _ = writeln!(texto, "Frete: {}", money::formatar_reais(&pedido.valor_frete));
+    } else {
+        texto.push_str("Frete: Grátis\n");
+    }
+    let _ = writeln!(texto, "Total: {}", money::formatar_reais(&pedido.valor_liquido));
+}
+
+fn escrever_entrega(texto: &mut String, comprovante: &ComprovantePedido) {
+    let modalidade = ModalidadeFrete::from_str(&comprovante.pedido.modalidade_frete)
+        .map(|modalidade| modalidade.descricao().to_string())
+        .unwrap_or_else(|_| comprovante.pedido.modalidade_frete.clone());
+
+    texto.push('\n');
+    let _ = writeln!(texto, "{}. {}", modalidade, previsao(comprovante));
+    if let Some(endereco) = &comprovante.endereco {
+        let _ = writeln!(texto, "Endereço: {}", endereco_em_linha(endereco));
+    }
+}
+
+fn endereco_em_linha(endereco: &EnderecosEntrega) -> String {
+    let sigla_uf = ibge::uf(&endereco.codigo_ibge_uf).map(|(sigla, _)| sigla).unwrap_or(&endereco.codigo_ibge_uf);
+    let cidade = endereco.nome_cidade()
+        .unwrap_or_else(|| format!("Município {}", endereco.codigo_ibge_cidade));
+
+    let mut linha = format!("{}, {}", endereco.logradouro, endereco.numero);
+    if let Some(complemento) = &endereco.complemento {
+        let _ = write!(linha, " - {}", complemento);
+    }
+    let _ = write!(linha, ", {} - {}/{}, CEP {}", endereco.bairro, cidade, sigla_uf, endereco.cep);
+    linha
+}
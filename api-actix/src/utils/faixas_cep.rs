@@ -0,0 +1,45 @@
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // Faixas de CEP dos Correios por UF (início, fim, código IBGE da UF); AM, DF e GO têm faixas descontínuas
+    pub static ref FAIXAS_CEP_UF: Vec<(u32, u32, &'static str)> = vec![
+        (1_000_000, 19_999_999, "35"),  // SP
+        (20_000_000, 28_999_999, "33"), // RJ
+        (29_000_000, 29_999_999, "32"), // ES
+        (30_000_000, 39_999_999, "31"), // MG
+        (40_000_000, 48_999_999, "29"), // BA
+        (49_000_000, 49_999_999, "28"), // SE
+        (50_000_000, 56_999_999, "26"), // PE
+        (57_000_000, 57_999_999, "27"), // AL
+        (58_000_000, 58_999_999, "25"), // PB
+        (59_000_000, 59_999_999, "24"), // RN
+        (60_000_000, 63_999_999, "23"), // CE
+        (64_000_000, 64_999_999, "22"), // PI
+        (65_000_000, 65_999_999, "21"), // MA
+        (66_000_000, 68_899_999, "15"), // PA
+        (68_900_000, 68_999_999, "16"), // AP
+        (69_000_000, 69_299_999, "13"), // AM
+        (69_300_000, 69_399_999, "14"), // RR
+        (69_400_000, 69_899_999, "13"), // AM
+        (69_900_000, 69_999_999, "12"), // AC
+        (70_000_000, 72_799_999, "53"), // DF
+        (72_800_000, 72_999_999, "52"), // GO
+        (73_000_000, 73_699_999, "53"), // DF
+        (73_700_000, 76_799_999, "52"), // GO
+        (76_800_000, 76_999_999, "11"), // RO
+        (77_000_000, 77_999_999, "17"), // TO
+        (78_000_000, 78_899_999, "51"), // MT
+        (79_000_000, 79_999_999, "50"), // MS
+        (80_000_000, 87_999_999, "41"), // PR
+        (88_000_000, 89_999_999, "42"), // SC
+        (90_000_000, 99_999_999, "43"), // RS
+    ];
+}
+
+pub fn codigo_ibge_uf_por_cep(cep: &str) -> Option<&'static str> {
+    let cep = cep.parse::<u32>().ok()?;
+
+    FAIXAS_CEP_UF.iter()
+        .find(|(inicio, fim, _)| (*inicio..=*fim).contains(&cep))
+        .map(|(_, _, codigo_ibge_uf)| *codigo_ibge_uf)
+}
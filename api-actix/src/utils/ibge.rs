@@ -0,0 +1,148 @@
+use lazy_static::lazy_static;
+
+// Código IBGE, sigla e nome das 27 unidades da federação
+pub const UFS: [(&str, &str, &str); 27] = [
+    ("11", "RO", "Rondônia"),
+    ("12", "AC", "Acre"),
+    ("13", "AM", "Amazonas"),
+    ("14", "RR", "Roraima"),
+    ("15", "PA", "Pará"),
+    ("16", "AP", "Amapá"),
+    ("17", "TO", "Tocantins"),
+    ("21", "MA", "Maranhão"),
+    ("22", "PI", "Piauí"),
+    ("23", "CE", "Ceará"),
+    ("24", "RN", "Rio Grande do Norte"),
+    ("25", "PB", "Paraíba"),
+    ("26", "PE", "Pernambuco"),
+    ("27", "AL", "Alagoas"),
+    ("28", "SE", "Sergipe"),
+    ("29", "BA", "Bahia"),
+    ("31", "MG", "Minas Gerais"),
+    ("32", "ES", "Espírito Santo"),
+    ("33", "RJ", "Rio de Janeiro"),
+    ("35", "SP", "São Paulo"),
+    ("41", "PR", "Paraná"),
+    ("42", "SC", "Santa Catarina"),
+    ("43", "RS", "Rio Grande do Sul"),
+    ("50", "MS", "Mato Grosso do Sul"),
+    ("51", "MT", "Mato Grosso"),
+    ("52", "GO", "Goiás"),
+    ("53", "DF", "Distrito Federal"),
+];
+
+// Municípios criados com o dígito verificador fora da regra e mantidos assim pelo IBGE
+const CODIGOS_DV_EXCECAO: [&str; 9] = [
+    "2201919", "2201988", "2202251", "2611533", "3117836",
+    "3152131", "4305871", "5203939", "5203962",
+];
+
+pub struct Municipio {
+    pub codigo: &'static str,
+    pub nome: &'static str,
+}
+
+lazy_static! {
+    // Tabela embutida no binário: codigoIbge;nome;cepInicial;cepFinal, uma linha por faixa de CEP da localidade.
+    // Regerada com data/gerar_municipios.js a partir da API de localidades do IBGE e do e-DNE dos Correios
+    static ref TABELA_MUNICIPIOS: Vec<(Municipio, Option<(u32, u32)>)> = include_str!("../../data/municipios.csv")
+        .lines()
+        .skip(1)
+        .filter(|linha| !linha.trim().is_empty())
+        .filter_map(|linha| {
+            let colunas: Vec<&'static str> = linha.split(';').map(str::trim).collect();
+            let (codigo, nome) = (*colunas.first()?, *colunas.get(1)?);
+
+            let faixa = match (colunas.get(2), colunas.get(3)) {
+                (Some(inicio), Some(fim)) => inicio.parse::<u32>().ok().zip(fim.parse::<u32>().ok()),
+                _ => None,
+            };
+
+            Some((Municipio { codigo, nome }, faixa))
+        })
+        .collect();
+}
+
+// Módulo 10 com pesos 1 e 2 alternados sobre os seis primeiros dígitos, somando os dígitos de cada produto
+pub fn digito_verificador_municipio(codigo: &str) -> Option<u32> {
+    let digitos: Vec<u32> = codigo.chars().take(6).map(|c| c.to_digit(10)).collect::<Option<_>>()?;
+    if digitos.len() != 6 {
+        return None;
+    }
+
+    let soma: u32 = digitos.iter()
+        .enumerate()
+        .map(|(i, digito)| {
+            let produto = digito * if i.is_multiple_of(2) { 1 } else { 2 };
+            produto / 10 + produto % 10
+        })
+        .sum();
+
+    Some((10 - soma % 10) % 10)
+}
+
+pub fn codigo_municipio_valido(codigo: &str) -> bool {
+    if codigo.len() != 7 || !codigo.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    if CODIGOS_DV_EXCECAO.contains(&codigo) {
+        return true;
+    }
+
+    uf(&codigo[0..2]).is_some() && digito_verificador_municipio(codigo) == codigo[6..].parse::<u32>().ok()
+}
+
+pub fn uf(codigo_ibge_uf: &str) -> Option<(&'static str, &'static str)> {
+    UFS.iter()
+        .find(|(codigo, _, _)| *codigo == codigo_ibge_uf)
+        .map(|(_, sigla, nome)| (*sigla, *nome))
+}
+
+pub fn municipio(codigo: &str) -> Option<&'static Municipio> {
+    TABELA_MUNICIPIOS.iter()
+        .map(|(municipio, _)| municipio)
+        .find(|municipio| municipio.codigo == codigo)
+}
+
+pub fn municipio_por_cep(cep: &str) -> Option<&'static Municipio> {
+    let cep = cep.parse::<u32>().ok()?;
+
+    TABELA_MUNICIPIOS.iter()
+        .find(|(_, faixa)| faixa.is_some_and(|(inicio, fim)| (inicio..=fim).contains(&cep)))
+        .map(|(municipio, _)| municipio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digito_verificador_de_codigos_publicados() {
+        assert_eq!(digito_verificador_municipio("3550308"), Some(8));
+        assert_eq!(digito_verificador_municipio("3549805"), Some(5));
+        assert_eq!(digito_verificador_municipio("5300108"), Some(8));
+        assert_eq!(digito_verificador_municipio("3304557"), Some(7));
+    }
+
+    #[test]
+    fn valida_codigo_do_municipio() {
+        assert!(codigo_municipio_valido("3550308"));
+        assert!(codigo_municipio_valido("3549805"));
+        assert!(!codigo_municipio_valido("3550307"));
+        assert!(!codigo_municipio_valido("0050308"));
+        assert!(!codigo_municipio_valido("355030"));
+    }
+
+    #[test]
+    fn aceita_codigos_que_fogem_da_regra_do_digito() {
+        assert_ne!(digito_verificador_municipio("2201919"), Some(9));
+        assert!(codigo_municipio_valido("2201919"));
+    }
+
+    #[test]
+    fn encontra_municipio_pelo_cep() {
+        assert_eq!(municipio_por_cep("01310100").map(|m| m.codigo), Some("3550308"));
+        assert_eq!(municipio_por_cep("70040010").map(|m| m.nome), Some("Brasília"));
+    }
+}
@@ -1,3 +1,15 @@
 pub mod app_message;
-pub(crate) mod tabela_frete;
-pub(crate) mod hash_password;
\ No newline at end of file
+pub mod boleto;
+pub mod calendario;
+pub mod comprovante;
+pub mod emails;
+pub mod pdf;
+pub mod nfe;
+pub mod pix;
+pub mod webhook;
+pub mod xmldsig;
+pub(crate) mod faixas_cep;
+pub(crate) mod ibge;
+pub(crate) mod prazo_entrega;
+pub(crate) mod hash_password;
+pub(crate) mod money;
\ No newline at end of file
@@ -0,0 +1,156 @@
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+
+// Valores monetários são sempre guardados com 2 casas decimais, arredondando meio para cima
+pub const CASAS_DECIMAIS: i64 = 2;
+
+pub fn arredondar(valor: &BigDecimal) -> BigDecimal {
+    valor.with_scale_round(CASAS_DECIMAIS, RoundingMode::HalfUp)
+}
+
+pub fn zero() -> BigDecimal {
+    BigDecimal::from(0).with_scale(CASAS_DECIMAIS)
+}
+
+// Divide o total em partes iguais; os centavos que sobram vão para as primeiras partes,
+// garantindo que a soma das partes seja exatamente o total arredondado
+pub fn ratear(total: &BigDecimal, partes: usize) -> Vec<BigDecimal> {
+    if partes == 0 {
+        return Vec::new();
+    }
+
+    let centavos_total = to_centavos(&arredondar(total));
+    let partes_i64 = partes as i64;
+    let base = centavos_total.div_euclid(partes_i64);
+    let resto = centavos_total.rem_euclid(partes_i64);
+
+    (0..partes_i64)
+        .map(|i| from_centavos(if i < resto { base + 1 } else { base }))
+        .collect()
+}
+
+// Divide o total proporcionalmente aos pesos pelo método dos maiores restos: cada parte recebe
+// o piso da sua fração em centavos e os centavos restantes vão para as maiores sobras
+pub fn ratear_proporcional(total: &BigDecimal, pesos: &[BigDecimal]) -> Vec<BigDecimal> {
+    let centavos_pesos: Vec<i128> = pesos.iter()
+        .map(|peso| to_centavos(&arredondar(peso)).max(0) as i128)
+        .collect();
+    let soma_pesos: i128 = centavos_pesos.iter().sum();
+
+    if soma_pesos == 0 {
+        return pesos.iter().map(|_| zero()).collect();
+    }
+
+    let centavos_total = to_centavos(&arredondar(total)) as i128;
+    let mut partes: Vec<i128> = centavos_pesos.iter()
+        .map(|peso| centavos_total * peso / soma_pesos)
+        .collect();
+
+    let mut ordem: Vec<usize> = (0..partes.len()).collect();
+    ordem.sort_by_key(|&i| std::cmp::Reverse(centavos_total * centavos_pesos[i] % soma_pesos));
+
+    let restantes = centavos_total - partes.iter().sum::<i128>();
+    for &i in ordem.iter().take(restantes as usize) {
+        partes[i] += 1;
+    }
+
+    partes.into_iter()
+        .map(|centavos| from_centavos(centavos as i64))
+        .collect()
+}
+
+pub fn centavos(valor: &BigDecimal) -> i64 {
+    to_centavos(&arredondar(valor))
+}
+
+// Formato brasileiro para exibição: R$ 1.234,56
+pub fn formatar_reais(valor: &BigDecimal) -> String {
+    let centavos = centavos(valor);
+    let reais = (centavos.abs() / 100).to_string();
+
+    let mut milhares = String::with_capacity(reais.len() + reais.len() / 3);
+    for (i, c) in reais.chars().enumerate() {
+        if i > 0 && (reais.len() - i).is_multiple_of(3) {
+            milhares.push('.');
+        }
+        milhares.push(c);
+    }
+
+    format!("{}R$ {},{:02}", if centavos < 0 { "-" } else { "" }, milhares, centavos.abs() % 100)
+}
+
+fn to_centavos(valor: &BigDecimal) -> i64 {
+    (valor * BigDecimal::from(100))
+        .with_scale(0)
+        .to_i64()
+        .unwrap_or(0)
+}
+
+fn from_centavos(centavos: i64) -> BigDecimal {
+    BigDecimal::new(centavos.into(), CASAS_DECIMAIS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn valor(texto: &str) -> BigDecimal {
+        BigDecimal::from_str(texto).unwrap()
+    }
+
+    fn valores(textos: &[&str]) -> Vec<BigDecimal> {
+        textos.iter().map(|texto| valor(texto)).collect()
+    }
+
+    #[test]
+    fn arredonda_meio_para_cima() {
+        assert_eq!(arredondar(&valor("10.005")).to_string(), "10.01");
+        assert_eq!(arredondar(&valor("10.004")).to_string(), "10.00");
+        assert_eq!(arredondar(&valor("7")).to_string(), "7.00");
+    }
+
+    #[test]
+    fn ratear_distribui_os_centavos_que_sobram_nas_primeiras_partes() {
+        assert_eq!(ratear(&valor("100"), 3), valores(&["33.34", "33.33", "33.33"]));
+        assert_eq!(ratear(&valor("0.05"), 3), valores(&["0.02", "0.02", "0.01"]));
+        assert_eq!(ratear(&valor("0.01"), 3), valores(&["0.01", "0", "0"]));
+    }
+
+    #[test]
+    fn ratear_soma_exatamente_o_total() {
+        let total = valor("1234.57");
+        for partes in 1..=12 {
+            let soma: BigDecimal = ratear(&total, partes).iter().sum();
+            assert_eq!(soma, total);
+        }
+    }
+
+    #[test]
+    fn ratear_sem_partes() {
+        assert!(ratear(&valor("10"), 0).is_empty());
+    }
+
+    #[test]
+    fn ratear_proporcional_pelos_maiores_restos() {
+        // 10,00 em três partes iguais: 333 centavos para cada, o que sobra vai para a primeira do empate
+        assert_eq!(ratear_proporcional(&valor("10"), &valores(&["1", "1", "1"])), valores(&["3.34", "3.33", "3.33"]));
+        // 1,00 em 10% / 30% / 60%
+        assert_eq!(ratear_proporcional(&valor("1"), &valores(&["10", "30", "60"])), valores(&["0.10", "0.30", "0.60"]));
+        // 0,05 em 33,33 / 33,33 / 33,34: sobram 2 centavos, um para a maior sobra (a última) e outro para a primeira do empate
+        assert_eq!(ratear_proporcional(&valor("0.05"), &valores(&["33.33", "33.33", "33.34"])), valores(&["0.02", "0.01", "0.02"]));
+    }
+
+    #[test]
+    fn ratear_proporcional_soma_exatamente_o_total() {
+        let total = valor("17.89");
+        let pesos = valores(&["19.90", "5.50", "0.99", "149.00", "32.45"]);
+        let soma: BigDecimal = ratear_proporcional(&total, &pesos).iter().sum();
+        assert_eq!(soma, total);
+    }
+
+    #[test]
+    fn ratear_proporcional_sem_peso() {
+        assert_eq!(ratear_proporcional(&valor("10"), &valores(&["0", "0"])), valores(&["0", "0"]));
+        assert_eq!(ratear_proporcional(&valor("10"), &valores(&["-5", "0"])), valores(&["0", "0"]));
+    }
+}
@@ -0,0 +1,451 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{DateTime, FixedOffset};
+use std::fmt::Write;
+use std::str::FromStr;
+use crate::models::frete::ModalidadeFrete;
+use crate::models::nfe::DadosNotaFiscal;
+use crate::models::pedido::FormaPagamento;
+use crate::utils::app_message::AppMessage;
+use crate::utils::{ibge, money};
+
+// NF-e modelo 55, leiaute 4.00. Todo o infNFe é escrito já na forma canônica (C14N) para que o
+// mesmo texto sirva para o digest da assinatura: sem espaços entre elementos, sem elementos
+// vazios abreviados e com os atributos em ordem alfabética
+pub const NAMESPACE_NFE: &str = "http://www.portalfiscal.inf.br/nfe";
+const VERSAO_LEIAUTE: &str = "4.00";
+const MODELO: &str = "55";
+const TIPO_EMISSAO_NORMAL: u8 = 1;
+const CODIGO_PAIS_BRASIL: &str = "1058";
+const SEM_GTIN: &str = "SEM GTIN";
+const UNIDADE: &str = "UN";
+// Em homologação a SEFAZ exige este texto no lugar do nome do destinatário
+const NOME_DESTINATARIO_HOMOLOGACAO: &str = "NF-E EMITIDA EM AMBIENTE DE HOMOLOGACAO - SEM VALOR FISCAL";
+
+pub struct Emitente<'a> {
+    pub cnpj: &'a str,
+    pub ie: &'a str,
+    pub razao_social: &'a str,
+    pub nome_fantasia: Option<&'a str>,
+    pub logradouro: &'a str,
+    pub numero: &'a str,
+    pub complemento: Option<&'a str>,
+    pub bairro: &'a str,
+    pub codigo_municipio: &'a str,
+    pub municipio: &'a str,
+    pub cep: &'a str,
+    pub telefone: Option<&'a str>,
+    pub crt: u8,
+}
+
+pub struct Identificacao<'a> {
+    pub ambiente: u8,
+    pub serie: u32,
+    pub numero: u32,
+    pub codigo_numerico: u32,
+    pub emissao: DateTime<FixedOffset>,
+    pub natureza_operacao: &'a str,
+}
+
+pub struct InfNfe {
+    pub chave_acesso: String,
+    conteudo: String,
+}
+
+impl InfNfe {
+    pub fn id(&self) -> String {
+        format!("NFe{}", self.chave_acesso)
+    }
+
+    // Forma canônica do infNFe isolado: o namespace herdado do NFe aparece declarado nele
+    pub fn canonico(&self) -> String {
+        format!("<infNFe xmlns=\"{}\" Id=\"{}\" versao=\"{}\">{}</infNFe>", NAMESPACE_NFE, self.id(), VERSAO_LEIAUTE, self.conteudo)
+    }
+
+    pub fn documento(&self, assinatura: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><NFe xmlns=\"{}\"><infNFe Id=\"{}\" versao=\"{}\">{}</infNFe>{}</NFe>",
+            NAMESPACE_NFE, self.id(), VERSAO_LEIAUTE, self.conteudo, assinatura
+        )
+    }
+}
+
+// cUF(2) AAMM(4) CNPJ(14) mod(2) série(3) nNF(9) tpEmis(1) cNF(8) + DV(1)
+pub fn chave_acesso(codigo_uf: &str, emissao: &DateTime<FixedOffset>, cnpj: &str, serie: u32, numero: u32, tipo_emissao: u8, codigo_numerico: u32) -> Result<String, AppMessage> {
+    let base = format!(
+        "{}{}{}{}{:03}{:09}{}{:08}",
+        codigo_uf, emissao.format("%y%m"), cnpj, MODELO, serie, numero, tipo_emissao, codigo_numerico
+    );
+
+    if base.len() != 43 || !base.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppMessage::new(&format!("Não foi possível montar a chave de acesso a partir de {}", base), 500));
+    }
+
+    let digito = digito_verificador_chave(&base);
+    Ok(format!("{}{}", base, digito))
+}
+
+// Módulo 11 com pesos de 2 a 9 aplicados da direita para a esquerda; restos 0 e 1 dão dígito 0
+pub fn digito_verificador_chave(chave: &str) -> u32 {
+    let soma: u32 = chave.chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, digito)| digito * (2 + i as u32 % 8))
+        .sum();
+
+    match soma % 11 {
+        0 | 1 => 0,
+        resto => 11 - resto,
+    }
+}
+
+pub fn gerar(dados: &DadosNotaFiscal, emitente: &Emitente, identificacao: &Identificacao) -> Result<InfNfe, AppMessage> {
+    let pedido = &dados.pedido;
+    let cliente = &dados.cliente;
+
+    let endereco = dados.endereco.as_ref()
+        .ok_or_else(|| AppMessage::new("Pedido não possui endereço de entrega", 422))?;
+    let cnpj_destinatario = cliente.cnpj.as_deref()
+        .filter(|cnpj| cliente.tipo_pessoa == "PJ" && cnpj.len() == 14)
+        .ok_or_else(|| AppMessage::new("NF-e só é emitida para clientes pessoa jurídica com CNPJ", 422))?;
+
+    if !matches!(emitente.crt, 1 | 4) {
+        return Err(AppMessage::new(&format!("Regime tributário CRT {} não suportado; apenas Simples Nacional (1) e MEI (4)", emitente.crt), 500));
+    }
+
+    let codigo_uf_emitente = &emitente.codigo_municipio[..2.min(emitente.codigo_municipio.len())];
+    let uf_emitente = ibge::uf(codigo_uf_emitente)
+        .ok_or_else(|| AppMessage::new(&format!("Município do emitente {} inválido", emitente.codigo_municipio), 500))?;
+    let uf_destinatario = ibge::uf(&endereco.codigo_ibge_uf)
+        .ok_or_else(|| AppMessage::new(&format!("UF {} do endereço de entrega inválida", endereco.codigo_ibge_uf), 422))?;
+    let municipio_destinatario = endereco.nome_cidade()
+        .ok_or_else(|| AppMessage::new(&format!("Endereço de entrega sem o nome da cidade {}", endereco.codigo_ibge_cidade), 422))?;
+
+    let sem_ncm: Vec<&str> = dados.itens.iter()
+        .filter(|item| item.ncm.is_none())
+        .map(|item| item.produto.sku_produto.as_str())
+        .collect();
+    if !sem_ncm.is_empty() {
+        return Err(AppMessage::new(&format!("Produtos sem NCM cadastrado: {}", sem_ncm.join(", ")), 422));
+    }
+
+    let chave = chave_acesso(
+        codigo_uf_emitente, &identificacao.emissao, emitente.cnpj,
+        identificacao.serie, identificacao.numero, TIPO_EMISSAO_NORMAL, identificacao.codigo_numerico
+    )?;
+
+    let operacao_interna = codigo_uf_emitente == endereco.codigo_ibge_uf;
+    let cfop = if operacao_interna { "5102" } else { "6102" };
+
+    // 1 = contribuinte com IE, 2 = isento de inscrição, 9 = não contribuinte
+    let ie_destinatario = cliente.ie.as_deref().map(str::trim).filter(|ie| !ie.is_empty());
+    let indicador_ie = match ie_destinatario {
+        Some(ie) if ie.eq_ignore_ascii_case("ISENTO") => "2",
+        Some(_) => "1",
+        None => "9",
+    };
+
+    let mut xml = Xml::default();
+
+    xml.abrir("ide");
+    xml.campo("cUF", codigo_uf_emitente);
+    xml.campo("cNF", &format!("{:08}", identificacao.codigo_numerico));
+    xml.texto("natOp", identificacao.natureza_operacao, 60);
+    xml.campo("mod", MODELO);
+    xml.campo("serie", &identificacao.serie.to_string());
+    xml.campo("nNF", &identificacao.numero.to_string());
+    xml.campo("dhEmi", &identificacao.emissao.format("%Y-%m-%dT%H:%M:%S%:z").to_string());
+    xml.campo("tpNF", "1");
+    xml.campo("idDest", if operacao_interna { "1" } else { "2" });
+    xml.campo("cMunFG", emitente.codigo_municipio);
+    xml.campo("tpImp", "1");
+    xml.campo("tpEmis", &TIPO_EMISSAO_NORMAL.to_string());
+    xml.campo("cDV", &chave[43..]);
+    xml.campo("tpAmb", &identificacao.ambiente.to_string());
+    xml.campo("finNFe", "1");
+    // Venda pela loja virtual: consumidor final, operação pela internet e sem intermediador
+    xml.campo("indFinal", "1");
+    xml.campo("indPres", "2");
+    xml.campo("indIntermed", "0");
+    xml.campo("procEmi", "0");
+    xml.texto("verProc", concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")), 20);
+    xml.fechar("ide");
+
+    xml.abrir("emit");
+    xml.campo("CNPJ", emitente.cnpj);
+    xml.texto("xNome", emitente.razao_social, 60);
+    if let Some(nome_fantasia) = emitente.nome_fantasia {
+        xml.texto("xFant", nome_fantasia, 60);
+    }
+    xml.abrir("enderEmit");
+    xml.texto("xLgr", emitente.logradouro, 60);
+    xml.texto("nro", emitente.numero, 60);
+    if let Some(complemento) = emitente.complemento {
+        xml.texto("xCpl", complemento, 60);
+    }
+    xml.texto("xBairro", emitente.bairro, 60);
+    xml.campo("cMun", emitente.codigo_municipio);
+    xml.texto("xMun", emitente.municipio, 60);
+    xml.campo("UF", uf_emitente.0);
+    xml.campo("CEP", emitente.cep);
+    xml.campo("cPais", CODIGO_PAIS_BRASIL);
+    xml.campo("xPais", "Brasil");
+    if let Some(telefone) = emitente.telefone.map(somente_digitos).filter(|fone| (6..=14).contains(&fone.len())) {
+        xml.campo("fone", &telefone);
+    }
+    xml.fechar("enderEmit");
+    xml.campo("IE", &somente_digitos(emitente.ie));
+    xml.campo("CRT", &emitente.crt.to_string());
+    xml.fechar("emit");
+
+    let nome_destinatario = if identificacao.ambiente == 2 {
+        NOME_DESTINATARIO_HOMOLOGACAO
+    } else {
+        cliente.razao_social.as_deref().unwrap_or(&cliente.nome)
+    };
+
+    xml.abrir("dest");
+    xml.campo("CNPJ", cnpj_destinatario);
+    xml.texto("xNome", nome_destinatario, 60);
+    xml.abrir("enderDest");
+    xml.texto("xLgr", &endereco.logradouro, 60);
+    xml.texto("nro", &endereco.numero, 60);
+    if let Some(complemento) = &endereco.complemento {
+        xml.texto("xCpl", complemento, 60);
+    }
+    xml.texto("xBairro", &endereco.bairro, 60);
+    xml.campo("cMun", &endereco.codigo_ibge_cidade);
+    xml.texto("xMun", &municipio_destinatario, 60);
+    xml.campo("UF", uf_destinatario.0);
+    xml.campo("CEP", &endereco.cep);
+    xml.campo("cPais", CODIGO_PAIS_BRASIL);
+    xml.campo("xPais", "Brasil");
+    let telefone = somente_digitos(&cliente.telefone);
+    if (6..=14).contains(&telefone.len()) {
+        xml.campo("fone", &telefone);
+    }
+    xml.fechar("enderDest");
+    xml.campo("indIEDest", indicador_ie);
+    if let (Some(ie), "1") = (ie_destinatario, indicador_ie) {
+        xml.campo("IE", &somente_digitos(ie));
+    }
+    if cliente.email.chars().count() <= 60 {
+        xml.texto("email", &cliente.email, 60);
+    }
+    xml.fechar("dest");
+
+    let mut total_produtos = money::zero();
+    let mut total_frete = money::zero();
+    let mut total_desconto = money::zero();
+
+    for (i, item) in dados.itens.iter().enumerate() {
+        let produto = &item.produto;
+        let quantidade = decimal(&produto.quantidade, 4);
+        let valor_unitario = decimal(&produto.valor_unitario, 2);
+
+        xml.abrir_item(i + 1);
+        xml.abrir("prod");
+        xml.texto("cProd", &produto.sku_produto, 60);
+        xml.campo("cEAN", SEM_GTIN);
+        xml.texto("xProd", item.nome.as_deref().unwrap_or(&produto.sku_produto), 120);
+        xml.campo("NCM", item.ncm.as_deref().unwrap_or_default());
+        xml.campo("CFOP", cfop);
+        xml.campo("uCom", UNIDADE);
+        xml.campo("qCom", &quantidade);
+        xml.campo("vUnCom", &valor_unitario);
+        xml.campo("vProd", &decimal(&produto.valor_bruto, 2));
+        xml.campo("cEANTrib", SEM_GTIN);
+        xml.campo("uTrib", UNIDADE);
+        xml.campo("qTrib", &quantidade);
+        xml.campo("vUnTrib", &valor_unitario);
+        if produto.valor_frete > money::zero() {
+            xml.campo("vFrete", &decimal(&produto.valor_frete, 2));
+        }
+        if produto.valor_desconto > money::zero() {
+            xml.campo("vDesc", &decimal(&produto.valor_desconto, 2));
+        }
+        xml.campo("indTot", "1");
+        xml.fechar("prod");
+
+        // Simples Nacional sem permissão de crédito; PIS e COFINS são recolhidos no DAS
+        xml.abrir("imposto");
+        xml.abrir("ICMS");
+        xml.abrir("ICMSSN102");
+        xml.campo("orig", "0");
+        xml.campo("CSOSN", "102");
+        xml.fechar("ICMSSN102");
+        xml.fechar("ICMS");
+        for (grupo, outros, aliquota, valor) in [("PIS", "PISOutr", "pPIS", "vPIS"), ("COFINS", "COFINSOutr", "pCOFINS", "vCOFINS")] {
+            xml.abrir(grupo);
+            xml.abrir(outros);
+            xml.campo("CST", "49");
+            xml.campo("vBC", "0.00");
+            xml.campo(aliquota, "0.0000");
+            xml.campo(valor, "0.00");
+            xml.fechar(outros);
+            xml.fechar(grupo);
+        }
+        xml.fechar("imposto");
+        xml.fechar("det");
+
+        total_produtos += &produto.valor_bruto;
+        total_frete += &produto.valor_frete;
+        total_desconto += &produto.valor_desconto;
+    }
+
+    // Os totais da nota são a soma dos itens e precisam fechar com o valor cobrado no pedido
+    let total_nota = &total_produtos - &total_desconto + &total_frete;
+    if money::centavos(&total_nota) != money::centavos(&pedido.valor_liquido) {
+        return Err(AppMessage::new(
+            &format!("Soma dos itens ({}) não confere com o total do pedido ({})", decimal(&total_nota, 2), decimal(&pedido.valor_liquido, 2)),
+            422
+        ));
+    }
+
+    xml.abrir("total");
+    xml.abrir("ICMSTot");
+    for campo in ["vBC", "vICMS", "vICMSDeson", "vFCP", "vBCST", "vST", "vFCPST", "vFCPSTRet"] {
+        xml.campo(campo, "0.00");
+    }
+    xml.campo("vProd", &decimal(&total_produtos, 2));
+    xml.campo("vFrete", &decimal(&total_frete, 2));
+    xml.campo("vSeg", "0.00");
+    xml.campo("vDesc", &decimal(&total_desconto, 2));
+    for campo in ["vII", "vIPI", "vIPIDevol", "vPIS", "vCOFINS", "vOutro"] {
+        xml.campo(campo, "0.00");
+    }
+    xml.campo("vNF", &decimal(&total_nota, 2));
+    xml.fechar("ICMSTot");
+    xml.fechar("total");
+
+    // 0 = frete por conta do remetente (CIF), 9 = sem transporte na retirada em loja
+    let retirada = matches!(ModalidadeFrete::from_str(&pedido.modalidade_frete), Ok(ModalidadeFrete::Retirada));
+    xml.abrir("transp");
+    xml.campo("modFrete", if retirada { "9" } else { "0" });
+    xml.fechar("transp");
+
+    xml.abrir("pag");
+    xml.abrir("detPag");
+    match &dados.pagamento {
+        Some(pagamento) => {
+            let forma = FormaPagamento::from_str(&pagamento.forma_pagamento)?;
+            xml.campo("indPag", if pagamento.numero_parcelas > 1 { "1" } else { "0" });
+            xml.campo("tPag", match forma {
+                FormaPagamento::Credito => "03",
+                FormaPagamento::Debito => "04",
+                FormaPagamento::Boleto => "15",
+                FormaPagamento::Pix => "17",
+            });
+            xml.campo("vPag", &decimal(&pagamento.valor_total, 2));
+            // Cartão processado fora do emissor de NF-e: pagamento não integrado
+            if matches!(forma, FormaPagamento::Credito | FormaPagamento::Debito) {
+                xml.abrir("card");
+                xml.campo("tpIntegra", "2");
+                xml.fechar("card");
+            }
+        }
+        None => {
+            xml.campo("tPag", "90");
+            xml.campo("vPag", "0.00");
+        }
+    }
+    xml.fechar("detPag");
+    xml.fechar("pag");
+
+    xml.abrir("infAdic");
+    xml.texto("infCpl", &format!("Pedido {}", pedido.id), 5000);
+    xml.fechar("infAdic");
+
+    Ok(InfNfe { chave_acesso: chave, conteudo: xml.conteudo })
+}
+
+#[derive(Default)]
+struct Xml {
+    conteudo: String,
+}
+
+impl Xml {
+    fn abrir(&mut self, tag: &str) {
+        let _ = write!(self.conteudo, "<{}>", tag);
+    }
+
+    fn abrir_item(&mut self, numero_item: usize) {
+        let _ = write!(self.conteudo, "<det nItem=\"{}\">", numero_item);
+    }
+
+    fn fechar(&mut self, tag: &str) {
+        let _ = write!(self.conteudo, "</{}>", tag);
+    }
+
+    fn campo(&mut self, tag: &str, valor: &str) {
+        let _ = write!(self.conteudo, "<{}>{}</{}>", tag, escapar(valor), tag);
+    }
+
+    // Campos livres seguem o tipo TString do leiaute: só caracteres até U+00FF, sem espaços
+    // nas pontas nem repetidos, limitados ao tamanho máximo do campo
+    fn texto(&mut self, tag: &str, valor: &str, tamanho_maximo: usize) {
+        let limpo: String = valor
+            .chars()
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .filter(|c| (' '..='\u{FF}').contains(c) && !c.is_control())
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(tamanho_maximo)
+            .collect();
+
+        self.campo(tag, limpo.trim_end());
+    }
+}
+
+// Escape de texto da forma canônica: aspas ficam literais e quebras de linha já foram removidas
+fn escapar(valor: &str) -> String {
+    valor
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn decimal(valor: &BigDecimal, casas: i64) -> String {
+    valor.with_scale_round(casas, RoundingMode::HalfUp).to_plain_string()
+}
+
+fn somente_digitos(valor: &str) -> String {
+    valor.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Exemplo do Manual de Orientação do Contribuinte para o cálculo do dígito da chave de acesso
+    const CHAVE_MOC: &str = "52060433009911002506550120000007800267301615";
+
+    #[test]
+    fn digito_verificador_do_exemplo_do_manual() {
+        assert_eq!(digito_verificador_chave(&CHAVE_MOC[..43]), 5);
+    }
+
+    #[test]
+    fn restos_zero_e_um_dao_digito_zero() {
+        assert_eq!(digito_verificador_chave("5206043300991100250655012000000780026730107"), 0);
+        assert_eq!(digito_verificador_chave("5206043300991100250655012000000780026730102"), 0);
+    }
+
+    #[test]
+    fn monta_a_chave_do_exemplo_do_manual() {
+        let emissao = DateTime::parse_from_rfc3339("2006-04-15T10:00:00-03:00").unwrap();
+        let chave = chave_acesso("52", &emissao, "33009911002506", 12, 780, 0, 26730161).unwrap();
+
+        assert_eq!(chave, CHAVE_MOC);
+    }
+
+    #[test]
+    fn rejeita_cnpj_com_tamanho_errado() {
+        let emissao = DateTime::parse_from_rfc3339("2006-04-15T10:00:00-03:00").unwrap();
+
+        assert!(chave_acesso("52", &emissao, "3300991100250", 12, 780, 0, 26730161).is_err());
+    }
+}
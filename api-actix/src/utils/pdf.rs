@@ -0,0 +1,252 @@
+use std::fmt::Write;
+
+// Gerador mínimo de PDF 1.4: texto com as fontes padrão (sem embutir arquivos de fonte),
+// linhas e retângulos preenchidos. Coordenadas em pontos, com a origem no canto inferior esquerdo
+pub const LARGURA_A4: f32 = 595.0;
+pub const ALTURA_A4: f32 = 842.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fonte {
+    Normal,
+    Negrito,
+    Mono,
+}
+
+impl Fonte {
+    fn recurso(&self) -> &'static str {
+        match self {
+            Self::Normal => "F1",
+            Self::Negrito => "F2",
+            Self::Mono => "F3",
+        }
+    }
+
+    // Larguras das métricas padrão (AFM) em milésimos do tamanho da fonte; letras acentuadas
+    // têm a mesma largura da letra base
+    fn largura_caractere(&self, c: char) -> u32 {
+        let tabela = match self {
+            Self::Mono => return 600,
+            Self::Normal => &LARGURAS_HELVETICA,
+            Self::Negrito => &LARGURAS_HELVETICA_NEGRITO,
+        };
+
+        let base = letra_base(c);
+        if (' '..='~').contains(&base) {
+            tabela[base as usize - 32] as u32
+        } else {
+            556
+        }
+    }
+}
+
+pub struct Documento {
+    paginas: Vec<String>,
+}
+
+impl Default for Documento {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Documento {
+    pub fn new() -> Self {
+        Self { paginas: vec![String::new()] }
+    }
+
+    pub fn nova_pagina(&mut self) {
+        self.paginas.push(String::new());
+    }
+
+    pub fn texto(&mut self, x: f32, y: f32, tamanho: f32, fonte: Fonte, texto: &str) {
+        let conteudo = self.pagina_atual();
+        let _ = writeln!(conteudo, "BT /{} {} Tf {} {} Td ({}) Tj ET", fonte.recurso(), num(tamanho), num(x), num(y), string_pdf(texto));
+    }
+
+    pub fn texto_direita(&mut self, x_direita: f32, y: f32, tamanho: f32, fonte: Fonte, texto: &str) {
+        let x = x_direita - largura_texto(texto, tamanho, fonte);
+        self.texto(x, y, tamanho, fonte, texto);
+    }
+
+    pub fn linha(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, espessura: f32) {
+        let conteudo = self.pagina_atual();
+        let _ = writeln!(conteudo, "{} w {} {} m {} {} l S", num(espessura), num(x1), num(y1), num(x2), num(y2));
+    }
+
+    pub fn retangulo(&mut self, x: f32, y: f32, largura: f32, altura: f32) {
+        let conteudo = self.pagina_atual();
+        let _ = writeln!(conteudo, "{} {} {} {} re f", num(x), num(y), num(largura), num(altura));
+    }
+
+    pub fn finalizar(self) -> Vec<u8> {
+        // 1 catálogo, 2 árvore de páginas, 3 a 5 fontes, depois página e conteúdo alternados
+        const PRIMEIRA_PAGINA: usize = 6;
+
+        let mut objetos: Vec<Vec<u8>> = Vec::new();
+
+        let kids: Vec<String> = (0..self.paginas.len())
+            .map(|i| format!("{} 0 R", PRIMEIRA_PAGINA + 2 * i))
+            .collect();
+
+        objetos.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objetos.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.paginas.len()).into_bytes());
+
+        for nome in ["Helvetica", "Helvetica-Bold", "Courier"] {
+            objetos.push(format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", nome).into_bytes());
+        }
+
+        for (i, conteudo) in self.paginas.iter().enumerate() {
+            objetos.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> /Contents {} 0 R >>",
+                num(LARGURA_A4), num(ALTURA_A4), PRIMEIRA_PAGINA + 2 * i + 1
+            ).into_bytes());
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", conteudo.len()).into_bytes();
+            stream.extend_from_slice(conteudo.as_bytes());
+            stream.extend_from_slice(b"\nendstream");
+            objetos.push(stream);
+        }
+
+        let mut pdf: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objetos.len());
+
+        for (i, objeto) in objetos.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            pdf.extend_from_slice(objeto);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        // Cada entrada da tabela xref tem exatamente 20 bytes, incluindo o fim de linha
+        let inicio_xref = pdf.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objetos.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(xref, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objetos.len() + 1, inicio_xref);
+        pdf.extend_from_slice(xref.as_bytes());
+
+        pdf
+    }
+
+    fn pagina_atual(&mut self) -> &mut String {
+        self.paginas.last_mut().expect("documento sempre tem ao menos uma página")
+    }
+}
+
+pub fn largura_texto(texto: &str, tamanho: f32, fonte: Fonte) -> f32 {
+    texto.chars().map(|c| fonte.largura_caractere(c)).sum::<u32>() as f32 * tamanho / 1000.0
+}
+
+// Quebra o texto em linhas que cabem na largura, preferindo quebrar nos espaços;
+// palavras maiores que a linha inteira (como o código PIX) são cortadas onde for preciso
+pub fn quebrar_linhas(texto: &str, largura_maxima: f32, tamanho: f32, fonte: Fonte) -> Vec<String> {
+    let cabe = |s: &str| largura_texto(s, tamanho, fonte) <= largura_maxima;
+    let mut linhas = Vec::new();
+    let mut atual = String::new();
+
+    for palavra in texto.split_whitespace() {
+        let candidata = if atual.is_empty() { palavra.to_string() } else { format!("{} {}", atual, palavra) };
+        if cabe(&candidata) {
+            atual = candidata;
+            continue;
+        }
+
+        if !atual.is_empty() {
+            linhas.push(std::mem::take(&mut atual));
+        }
+
+        for c in palavra.chars() {
+            atual.push(c);
+            if !cabe(&atual) && atual.chars().count() > 1 {
+                atual.pop();
+                linhas.push(std::mem::replace(&mut atual, c.to_string()));
+            }
+        }
+    }
+
+    if !atual.is_empty() {
+        linhas.push(atual);
+    }
+
+    linhas
+}
+
+fn num(valor: f32) -> String {
+    let texto = format!("{:.2}", valor);
+    texto.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// String literal do PDF em WinAnsiEncoding: Latin-1 é mapeado direto, alguns símbolos
+// tipográficos têm posição própria e o que não existe na codificação vira "?"
+fn string_pdf(texto: &str) -> String {
+    let mut saida = String::with_capacity(texto.len());
+
+    for c in texto.chars() {
+        let byte = match c {
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            ' '..='~' | '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            _ => b'?',
+        };
+
+        match byte {
+            b'(' | b')' | b'\\' => {
+                saida.push('\\');
+                saida.push(byte as char);
+            }
+            b' '..=b'~' => saida.push(byte as char),
+            _ => {
+                let _ = write!(saida, "\\{:03o}", byte);
+            }
+        }
+    }
+
+    saida
+}
+
+fn letra_base(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ç' => 'c',
+        'ñ' => 'n',
+        'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+        'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+        'Ç' => 'C',
+        'Ñ' => 'N',
+        _ => c,
+    }
+}
+
+// Caracteres de ' ' (32) a '~' (126)
+const LARGURAS_HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const LARGURAS_HELVETICA_NEGRITO: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
@@ -0,0 +1,220 @@
+use bigdecimal::BigDecimal;
+use qrcode::{Color, EcLevel, QrCode};
+use qrcode::render::svg;
+use crate::utils::app_message::AppMessage;
+use crate::utils::money;
+
+// BR Code do PIX no formato EMV: cada campo é ID(2) + tamanho(2) + valor,
+// encerrado pelo campo 63 com o CRC16-CCITT de todo o payload
+const GUI_PIX: &str = "br.gov.bcb.pix";
+const TAMANHO_MAXIMO_TXID: usize = 25;
+const TAMANHO_MAXIMO_NOME: usize = 25;
+const TAMANHO_MAXIMO_CIDADE: usize = 15;
+
+pub struct DadosPix<'a> {
+    pub chave: &'a str,
+    pub nome_recebedor: &'a str,
+    pub cidade: &'a str,
+    pub valor: Option<&'a BigDecimal>,
+    pub txid: &'a str,
+}
+
+pub fn gerar_payload(dados: &DadosPix) -> Result<String, AppMessage> {
+    if dados.chave.trim().is_empty() || dados.chave.len() > 77 {
+        return Err(AppMessage::new("Chave PIX inválida", 500));
+    }
+
+    if dados.txid.len() > TAMANHO_MAXIMO_TXID || !dados.txid.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AppMessage::new("txid do PIX deve ter até 25 caracteres alfanuméricos", 500));
+    }
+
+    let conta = format!("{}{}", campo("00", GUI_PIX)?, campo("01", dados.chave)?);
+    let txid = if dados.txid.is_empty() { "***" } else { dados.txid };
+
+    let mut payload = String::new();
+    payload.push_str(&campo("00", "01")?);
+    payload.push_str(&campo("01", "12")?);
+    payload.push_str(&campo("26", &conta)?);
+    payload.push_str(&campo("52", "0000")?);
+    payload.push_str(&campo("53", "986")?);
+
+    if let Some(valor) = dados.valor {
+        let valor = money::arredondar(valor);
+        if valor <= money::zero() {
+            return Err(AppMessage::new("Valor do PIX deve ser maior que zero", 400));
+        }
+        payload.push_str(&campo("54", &valor.to_string())?);
+    }
+
+    payload.push_str(&campo("58", "BR")?);
+    payload.push_str(&campo("59", &normalizar(dados.nome_recebedor, TAMANHO_MAXIMO_NOME))?);
+    payload.push_str(&campo("60", &normalizar(dados.cidade, TAMANHO_MAXIMO_CIDADE))?);
+    payload.push_str(&campo("62", &campo("05", txid)?)?);
+
+    payload.push_str("6304");
+    let crc = crc16_ccitt(payload.as_bytes());
+    payload.push_str(&format!("{:04X}", crc));
+
+    Ok(payload)
+}
+
+pub fn validar_payload(payload: &str) -> bool {
+    if payload.len() < 8 || !payload.is_ascii() {
+        return false;
+    }
+
+    let (corpo, crc) = payload.split_at(payload.len() - 4);
+    corpo.ends_with("6304") && format!("{:04X}", crc16_ccitt(corpo.as_bytes())) == crc.to_uppercase()
+}
+
+// CRC16-CCITT-FALSE: polinômio 0x1021, valor inicial 0xFFFF, sem reflexão
+pub fn crc16_ccitt(dados: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in dados {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+pub fn qr_code_svg(payload: &str) -> Result<String, AppMessage> {
+    let codigo = gerar_qr_code(payload)?;
+
+    Ok(codigo.render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .quiet_zone(true)
+        .build())
+}
+
+// PNG em tons de cinza com a zona silenciosa de 4 módulos exigida pela especificação do QR Code
+pub fn qr_code_png(payload: &str, escala: u32) -> Result<Vec<u8>, AppMessage> {
+    const ZONA_SILENCIOSA: u32 = 4;
+
+    let codigo = gerar_qr_code(payload)?;
+    let modulos = codigo.width() as u32;
+    let cores = codigo.to_colors();
+    let lado = (modulos + 2 * ZONA_SILENCIOSA) * escala;
+
+    let mut pixels = vec![255u8; (lado * lado) as usize];
+    for (i, cor) in cores.iter().enumerate() {
+        if *cor != Color::Dark {
+            continue;
+        }
+
+        let x0 = (i as u32 % modulos + ZONA_SILENCIOSA) * escala;
+        let y0 = (i as u32 / modulos + ZONA_SILENCIOSA) * escala;
+        for y in y0..y0 + escala {
+            let linha = (y * lado) as usize;
+            pixels[linha + x0 as usize..linha + (x0 + escala) as usize].fill(0);
+        }
+    }
+
+    let mut png_bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_bytes, lado, lado);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()
+            .map_err(|e| AppMessage::new(&format!("Erro ao gerar PNG do QR Code: {}", e), 500))?;
+        writer.write_image_data(&pixels)
+            .map_err(|e| AppMessage::new(&format!("Erro ao gerar PNG do QR Code: {}", e), 500))?;
+    }
+
+    Ok(png_bytes)
+}
+
+// Matriz quadrada de módulos (true = escuro) para quem desenha o QR Code em outro formato
+pub fn qr_code_modulos(payload: &str) -> Result<(usize, Vec<bool>), AppMessage> {
+    let codigo = gerar_qr_code(payload)?;
+    let modulos = codigo.width();
+
+    Ok((modulos, codigo.to_colors().into_iter().map(|cor| cor == Color::Dark).collect()))
+}
+
+fn gerar_qr_code(payload: &str) -> Result<QrCode, AppMessage> {
+    QrCode::with_error_correction_level(payload.as_bytes(), EcLevel::M)
+        .map_err(|e| AppMessage::new(&format!("Erro ao gerar QR Code: {}", e), 500))
+}
+
+fn campo(id: &str, valor: &str) -> Result<String, AppMessage> {
+    if valor.len() > 99 {
+        return Err(AppMessage::new(&format!("Campo {} do BR Code excede 99 caracteres", id), 500));
+    }
+    Ok(format!("{}{:02}{}", id, valor.len(), valor))
+}
+
+// Nome e cidade vão sem acentos e em maiúsculas, como recomenda o manual do BR Code
+fn normalizar(valor: &str, tamanho_maximo: usize) -> String {
+    valor.chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' | 'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+            'é' | 'è' | 'ê' | 'ë' | 'É' | 'È' | 'Ê' | 'Ë' => 'E',
+            'í' | 'ì' | 'î' | 'ï' | 'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' | 'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+            'ú' | 'ù' | 'û' | 'ü' | 'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+            'ç' | 'Ç' => 'C',
+            'ñ' | 'Ñ' => 'N',
+            _ => c.to_ascii_uppercase(),
+        })
+        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+        .take(tamanho_maximo)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // Exemplo de BR Code estático do manual do BR Code do Banco Central
+    const BR_CODE_BCB: &str = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+
+    #[test]
+    fn crc16_ccitt_do_valor_de_verificacao() {
+        assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn valida_o_exemplo_do_banco_central() {
+        assert!(validar_payload(BR_CODE_BCB));
+        assert!(validar_payload(&BR_CODE_BCB.replace("63041D3D", "63041d3d")));
+        assert!(!validar_payload(&BR_CODE_BCB.replace("63041D3D", "63041D3E")));
+        assert!(!validar_payload(&BR_CODE_BCB.replace("BRASILIA", "BRASILIO")));
+    }
+
+    #[test]
+    fn gera_payload_com_valor_e_txid() {
+        let valor = BigDecimal::from_str("10.5").unwrap();
+        let payload = gerar_payload(&DadosPix {
+            chave: "123e4567-e12b-12d1-a456-426655440000",
+            nome_recebedor: "Fulano de Tal",
+            cidade: "Brasília",
+            valor: Some(&valor),
+            txid: "PEDIDO123",
+        }).unwrap();
+
+        assert!(payload.starts_with("00020101021226580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-426655440000"));
+        assert!(payload.contains("540510.50"));
+        assert!(payload.contains("5913FULANO DE TAL6008BRASILIA"));
+        assert!(payload.contains("62130509PEDIDO123"));
+        assert!(validar_payload(&payload));
+    }
+
+    #[test]
+    fn rejeita_txid_invalido() {
+        let dados = DadosPix {
+            chave: "123e4567-e12b-12d1-a456-426655440000",
+            nome_recebedor: "Fulano de Tal",
+            cidade: "Brasília",
+            valor: None,
+            txid: "PEDIDO-123",
+        };
+
+        assert!(gerar_payload(&dados).is_err());
+    }
+}
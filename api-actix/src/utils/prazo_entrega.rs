@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // Prazo em dias úteis por código IBGE da UF de destino
+    pub static ref MAPA_PRAZO_ENTREGA: HashMap<i64, u32> = {
+        let mut map = HashMap::new();
+        map.insert(35, 2); // SP
+        map.insert(31, 3); // MG
+        map.insert(33, 3); // RJ
+        map.insert(41, 3); // PR
+        map.insert(50, 4); // MS
+        map.insert(32, 4); // ES
+        map.insert(29, 5); // BA
+        map.insert(52, 4); // GO
+        map.insert(51, 5); // MT
+        map.insert(53, 4); // DF
+        map.insert(42, 4); // SC
+        map.insert(43, 5); // RS
+        map.insert(11, 8); // RO
+        map.insert(13, 9); // AM
+        map.insert(15, 8); // PA
+        map.insert(17, 6); // TO
+        map.insert(22, 7); // PI
+        map.insert(26, 6); // PE
+        map.insert(27, 6); // AL
+        map.insert(28, 6); // SE
+        map.insert(12, 10); // AC
+        map.insert(14, 10); // RR
+        map.insert(16, 10); // AP
+        map.insert(21, 7); // MA
+        map.insert(23, 6); // CE
+        map.insert(25, 6); // PB
+        map.insert(24, 7); // RN
+        map
+    };
+
+    // Faixas de CEP com prazo próprio, consultadas antes do prazo da UF
+    pub static ref FAIXAS_CEP_PRAZO: Vec<(u32, u32, u32)> = vec![
+        (1_000_000, 5_999_999, 1),   // São Paulo (capital)
+        (8_000_000, 8_499_999, 1),   // São Paulo (capital)
+        (20_000_000, 23_799_999, 2), // Rio de Janeiro (capital)
+        (30_000_000, 31_999_999, 2), // Belo Horizonte
+        (80_000_000, 82_999_999, 2), // Curitiba
+        (70_000_000, 72_799_999, 3), // Brasília
+    ];
+}
+
+pub fn prazo_dias_uteis(codigo_ibge_uf: i64, cep: &str) -> Option<u32> {
+    let faixa = cep.parse::<u32>()
+        .ok()
+        .and_then(|cep| FAIXAS_CEP_PRAZO.iter().find(|(inicio, fim, _)| (*inicio..=*fim).contains(&cep)));
+
+    match faixa {
+        Some((_, _, prazo)) => Some(*prazo),
+        None => MAPA_PRAZO_ENTREGA.get(&codigo_ibge_uf).copied(),
+    }
+}
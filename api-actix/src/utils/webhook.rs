@@ -0,0 +1,87 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use crate::utils::app_message::AppMessage;
+
+// Assinatura dos webhooks: HMAC-SHA256 em hexadecimal de "{timestamp}.{corpo}",
+// enviada como "sha256=<hex>" junto com o timestamp Unix em segundos
+type HmacSha256 = Hmac<Sha256>;
+
+const PREFIXO_ASSINATURA: &str = "sha256=";
+
+pub fn verificar_assinatura(
+    segredo: &str,
+    timestamp: Option<&str>,
+    assinatura: Option<&str>,
+    corpo: &[u8],
+    agora: i64,
+    tolerancia_segundos: i64
+) -> Result<(), AppMessage> {
+    let timestamp = timestamp
+        .and_then(|valor| valor.trim().parse::<i64>().ok())
+        .ok_or_else(|| AppMessage::new("Timestamp do webhook ausente ou inválido", 401))?;
+
+    // Janela de tolerância contra reenvio de requisições capturadas; abs_diff não estoura
+    // com timestamps extremos enviados pelo cliente
+    if agora.abs_diff(timestamp) > tolerancia_segundos.unsigned_abs() {
+        return Err(AppMessage::new("Timestamp do webhook fora da janela de tolerância", 401));
+    }
+
+    let assinatura = assinatura
+        .map(|valor| valor.trim())
+        .map(|valor| valor.strip_prefix(PREFIXO_ASSINATURA).unwrap_or(valor))
+        .and_then(|valor| hex::decode(valor).ok())
+        .ok_or_else(|| AppMessage::new("Assinatura do webhook ausente ou inválida", 401))?;
+
+    // verify_slice compara em tempo constante
+    calcular_mac(segredo, timestamp, corpo)
+        .verify_slice(&assinatura)
+        .map_err(|_| AppMessage::new("Assinatura do webhook inválida", 401))
+}
+
+fn calcular_mac(segredo: &str, timestamp: i64, corpo: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(segredo.as_bytes())
+        .expect("HMAC aceita chaves de qualquer tamanho");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(corpo);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEGREDO: &str = "segredo";
+    const AGORA: i64 = 1_760_000_000;
+
+    fn assinar(timestamp: i64, corpo: &[u8]) -> String {
+        format!("{}{}", PREFIXO_ASSINATURA, hex::encode(calcular_mac(SEGREDO, timestamp, corpo).finalize().into_bytes()))
+    }
+
+    #[test]
+    fn aceita_assinatura_dentro_da_janela() {
+        let corpo = br#"{"evento":"pagamento.aprovado"}"#;
+        let timestamp = AGORA - 30;
+        let assinatura = assinar(timestamp, corpo);
+
+        assert!(verificar_assinatura(SEGREDO, Some(&timestamp.to_string()), Some(&assinatura), corpo, AGORA, 300).is_ok());
+    }
+
+    #[test]
+    fn recusa_assinatura_de_outro_corpo() {
+        let assinatura = assinar(AGORA, b"original");
+
+        let erro = verificar_assinatura(SEGREDO, Some(&AGORA.to_string()), Some(&assinatura), b"alterado", AGORA, 300).unwrap_err();
+        assert_eq!(erro.status_code, 401);
+    }
+
+    #[test]
+    fn timestamps_extremos_ficam_fora_da_janela() {
+        for timestamp in [i64::MIN, i64::MAX, AGORA - 301, AGORA + 301] {
+            let assinatura = assinar(timestamp, b"{}");
+
+            let erro = verificar_assinatura(SEGREDO, Some(&timestamp.to_string()), Some(&assinatura), b"{}", AGORA, 300).unwrap_err();
+            assert_eq!(erro.status_code, 401);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::signature::{SignatureEncoding, Signer};
+use rsa::traits::PublicKeyParts;
+use sha1::{Digest, Sha1};
+use x509_cert::Certificate;
+use x509_cert::der::{Decode, Encode};
+use crate::utils::app_message::AppMessage;
+
+// Assinatura XML-DSig envelopada no perfil exigido pela NF-e: C14N 1.0 sem comentários,
+// RSA-SHA1 e o certificado X.509 no KeyInfo. Quem chama entrega o elemento referenciado já
+// na forma canônica (sem elementos vazios abreviados, com o namespace herdado declarado)
+const NAMESPACE_DSIG: &str = "http://www.w3.org/2000/09/xmldsig#";
+const ALGORITMO_C14N: &str = "http://www.w3.org/TR/2001/REC-xml-c14n-20010315";
+const ALGORITMO_ENVELOPED: &str = "http://www.w3.org/2000/09/xmldsig#enveloped-signature";
+const ALGORITMO_RSA_SHA1: &str = "http://www.w3.org/2000/09/xmldsig#rsa-sha1";
+const ALGORITMO_SHA1: &str = "http://www.w3.org/2000/09/xmldsig#sha1";
+
+const TAMANHO_MINIMO_CHAVE_BITS: usize = 1024;
+
+pub struct Certificado {
+    certificado_der: Vec<u8>,
+    chave: SigningKey<Sha1>,
+}
+
+impl Certificado {
+    pub fn carregar(caminho_certificado: &str, caminho_chave: &str) -> Result<Self, AppMessage> {
+        let ler = |caminho: &str| std::fs::read(caminho)
+            .map_err(|e| AppMessage::new(&format!("Não foi possível ler {}: {}", caminho, e), 500));
+
+        Self::from_pem(&ler(caminho_certificado)?, &ler(caminho_chave)?)
+    }
+
+    // Aceita PEM com o certificado e a chave privada RSA sem senha (PKCS#1 ou PKCS#8), como os
+    // gerados por `openssl pkcs12 -in certificado.pfx -nodes` a partir do arquivo A1
+    pub fn from_pem(certificado_pem: &[u8], chave_pem: &[u8]) -> Result<Self, AppMessage> {
+        let blocos_certificado = pem::parse_many(certificado_pem)
+            .map_err(|e| AppMessage::new(&format!("Certificado em PEM inválido: {}", e), 500))?;
+        let blocos_chave = pem::parse_many(chave_pem)
+            .map_err(|e| AppMessage::new(&format!("Chave privada em PEM inválida: {}", e), 500))?;
+
+        let certificado_der = blocos_certificado.iter()
+            .find(|bloco| bloco.tag() == "CERTIFICATE")
+            .map(|bloco| bloco.contents().to_vec())
+            .ok_or_else(|| AppMessage::new("Arquivo do certificado não contém um bloco CERTIFICATE", 500))?;
+
+        let chave = blocos_chave.iter()
+            .find_map(|bloco| match bloco.tag() {
+                "RSA PRIVATE KEY" => Some(RsaPrivateKey::from_pkcs1_der(bloco.contents())
+                    .map_err(|e| AppMessage::new(&format!("Chave privada RSA inválida: {}", e), 500))),
+                "PRIVATE KEY" => Some(RsaPrivateKey::from_pkcs8_der(bloco.contents())
+                    .map_err(|e| AppMessage::new(&format!("Chave privada PKCS#8 inválida ou não RSA: {}", e), 500))),
+                "ENCRYPTED PRIVATE KEY" => Some(Err(AppMessage::new("Chave privada protegida por senha; exporte-a sem senha", 500))),
+                _ => None,
+            })
+            .ok_or_else(|| AppMessage::new("Arquivo da chave não contém uma chave privada RSA", 500))??;
+
+        if chave.n().bits() < TAMANHO_MINIMO_CHAVE_BITS {
+            return Err(AppMessage::new("Chave privada RSA deve ter ao menos 1024 bits", 500));
+        }
+
+        if chave_publica_certificado(&certificado_der)? != chave.to_public_key() {
+            return Err(AppMessage::new("Chave privada não corresponde ao certificado", 500));
+        }
+
+        Ok(Self { certificado_der, chave: SigningKey::new(chave) })
+    }
+
+    // RSASSA-PKCS1-v1_5 com SHA-1
+    pub fn assinar_rsa_sha1(&self, dados: &[u8]) -> Vec<u8> {
+        self.chave.sign(dados).to_vec()
+    }
+}
+
+// Elemento Signature para ser incluído como irmão seguinte do elemento assinado
+pub fn assinar(elemento_canonico: &str, id_referencia: &str, certificado: &Certificado) -> String {
+    let digest = STANDARD.encode(Sha1::digest(elemento_canonico.as_bytes()));
+
+    let conteudo_signed_info = format!(
+        "<CanonicalizationMethod Algorithm=\"{c14n}\"></CanonicalizationMethod>\
+         <SignatureMethod Algorithm=\"{rsa}\"></SignatureMethod>\
+         <Reference URI=\"#{id}\"><Transforms>\
+         <Transform Algorithm=\"{enveloped}\"></Transform>\
+         <Transform Algorithm=\"{c14n}\"></Transform>\
+         </Transforms><DigestMethod Algorithm=\"{sha1}\"></DigestMethod>\
+         <DigestValue>{digest}</DigestValue></Reference>",
+        c14n = ALGORITMO_C14N,
+        rsa = ALGORITMO_RSA_SHA1,
+        enveloped = ALGORITMO_ENVELOPED,
+        sha1 = ALGORITMO_SHA1,
+        id = id_referencia,
+        digest = digest,
+    );
+
+    // Na forma canônica o SignedInfo declara o namespace herdado do Signature
+    let signed_info_canonico = format!("<SignedInfo xmlns=\"{}\">{}</SignedInfo>", NAMESPACE_DSIG, conteudo_signed_info);
+    let assinatura = STANDARD.encode(certificado.assinar_rsa_sha1(signed_info_canonico.as_bytes()));
+
+    format!(
+        "<Signature xmlns=\"{}\"><SignedInfo>{}</SignedInfo><SignatureValue>{}</SignatureValue>\
+         <KeyInfo><X509Data><X509Certificate>{}</X509Certificate></X509Data></KeyInfo></Signature>",
+        NAMESPACE_DSIG,
+        conteudo_signed_info,
+        assinatura,
+        STANDARD.encode(&certificado.certificado_der),
+    )
+}
+
+fn chave_publica_certificado(der: &[u8]) -> Result<RsaPublicKey, AppMessage> {
+    let certificado = Certificate::from_der(der)
+        .map_err(|e| AppMessage::new(&format!("Certificado X.509 inválido: {}", e), 500))?;
+
+    certificado.tbs_certificate.subject_public_key_info.to_der()
+        .ok()
+        .and_then(|spki| RsaPublicKey::from_public_key_der(&spki).ok())
+        .ok_or_else(|| AppMessage::new("Certificado X.509 sem chave pública RSA", 500))
+}
@@ -0,0 +1,35 @@
+use bigdecimal::BigDecimal;
+use validator::ValidationError;
+use crate::utils::money;
+
+pub fn validate_codigo_cupom(codigo: &str) -> Result<(), ValidationError> {
+    if codigo.len() < 3 || codigo.len() > 30 {
+        return Err(ValidationError::new("Código do cupom deve ter entre 3 e 30 caracteres"));
+    }
+
+    if !codigo.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(ValidationError::new("Código do cupom deve conter apenas letras, números, - e _"));
+    }
+    Ok(())
+}
+
+pub fn validate_tipo_desconto(tipo: &str) -> Result<(), ValidationError> {
+    if !["P", "V"].contains(&tipo) {
+        return Err(ValidationError::new("Tipo de desconto deve ser P (percentual) ou V (valor)"));
+    }
+    Ok(())
+}
+
+pub fn validate_valor_desconto(valor: &BigDecimal) -> Result<(), ValidationError> {
+    if *valor <= money::zero() {
+        return Err(ValidationError::new("Valor do desconto deve ser maior que zero"));
+    }
+    Ok(())
+}
+
+pub fn validate_valor_minimo_pedido(valor: &BigDecimal) -> Result<(), ValidationError> {
+    if *valor < money::zero() {
+        return Err(ValidationError::new("Valor mínimo do pedido não pode ser negativo"));
+    }
+    Ok(())
+}
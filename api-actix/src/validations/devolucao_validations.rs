@@ -0,0 +1,10 @@
+use std::str::FromStr;
+use validator::ValidationError;
+use crate::models::devolucao::MotivoDevolucao;
+
+pub fn validate_motivo_devolucao(motivo: &str) -> Result<(), ValidationError> {
+    if MotivoDevolucao::from_str(motivo).is_err() {
+        return Err(ValidationError::new("Motivo deve ser arrependimento, defeito, produto_errado, avaria ou outro"));
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use bigdecimal::BigDecimal;
+use validator::ValidationError;
+use crate::utils::money;
+
+pub fn validate_peso_minimo(peso: &BigDecimal) -> Result<(), ValidationError> {
+    if *peso < money::zero() {
+        return Err(ValidationError::new("Peso mínimo não pode ser negativo"));
+    }
+    Ok(())
+}
+
+pub fn validate_peso_maximo(peso: &BigDecimal) -> Result<(), ValidationError> {
+    if *peso <= money::zero() {
+        return Err(ValidationError::new("Peso máximo deve ser maior que zero"));
+    }
+    Ok(())
+}
+
+pub fn validate_valor_frete(valor: &BigDecimal) -> Result<(), ValidationError> {
+    if *valor < money::zero() {
+        return Err(ValidationError::new("Valor do frete não pode ser negativo"));
+    }
+    Ok(())
+}
+
+pub fn validate_valor_minimo_frete_gratis(valor: &BigDecimal) -> Result<(), ValidationError> {
+    if *valor < money::zero() {
+        return Err(ValidationError::new("Valor mínimo para frete grátis não pode ser negativo"));
+    }
+    Ok(())
+}
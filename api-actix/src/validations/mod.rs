@@ -1 +1,5 @@
-pub mod cliente_validations;
\ No newline at end of file
+pub mod cliente_validations;
+pub mod cupom_validations;
+pub mod devolucao_validations;
+pub mod frete_validations;
+pub mod pedido_validations;
\ No newline at end of file
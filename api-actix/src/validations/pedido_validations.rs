@@ -0,0 +1,40 @@
+use validator::ValidationError;
+use crate::utils::ibge;
+
+fn somente_digitos(valor: &str, tamanho: usize) -> bool {
+    valor.len() == tamanho && valor.chars().all(|c| c.is_ascii_digit())
+}
+
+pub fn validate_cep(cep: &str) -> Result<(), ValidationError> {
+    if !somente_digitos(cep, 8) {
+        return Err(ValidationError::new("CEP deve conter 8 dígitos numéricos"));
+    }
+    Ok(())
+}
+
+pub fn validate_codigo_ibge_cidade(codigo: &str) -> Result<(), ValidationError> {
+    if !somente_digitos(codigo, 7) {
+        return Err(ValidationError::new("Código IBGE da cidade deve conter 7 dígitos numéricos"));
+    }
+    if !ibge::codigo_municipio_valido(codigo) {
+        return Err(ValidationError::new("Código IBGE da cidade inválido"));
+    }
+    Ok(())
+}
+
+pub fn validate_codigo_ibge_uf(codigo: &str) -> Result<(), ValidationError> {
+    if !somente_digitos(codigo, 2) {
+        return Err(ValidationError::new("Código IBGE da UF deve conter 2 dígitos numéricos"));
+    }
+    if ibge::uf(codigo).is_none() {
+        return Err(ValidationError::new("Código IBGE da UF não existe"));
+    }
+    Ok(())
+}
+
+pub fn validate_forma_pagamento(forma_pagamento: &str) -> Result<(), ValidationError> {
+    if !["B", "P", "D", "C"].contains(&forma_pagamento) {
+        return Err(ValidationError::new("Forma de pagamento deve ser B, P, D ou C"));
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+-- CreateTable
+CREATE TABLE "historicoStatusPedido" (
+    "id" VARCHAR(36) NOT NULL,
+    "idPedido" VARCHAR(36) NOT NULL,
+    "statusAnterior" CHAR(1),
+    "statusNovo" CHAR(1) NOT NULL,
+    "alteradoPor" VARCHAR(60) NOT NULL,
+    "motivo" VARCHAR(255),
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "historicoStatusPedido_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "idx_historico_status_pedido_pedido" ON "historicoStatusPedido"("idPedido");
+
+-- AddForeignKey
+ALTER TABLE "historicoStatusPedido" ADD CONSTRAINT "historicoStatusPedido_idPedido_fkey" FOREIGN KEY ("idPedido") REFERENCES "pedidos"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- Backfill
+INSERT INTO "historicoStatusPedido" ("id", "idPedido", "statusAnterior", "statusNovo", "alteradoPor", "motivo", "createdAt")
+SELECT gen_random_uuid()::text, "id", NULL, "status", "idCliente", 'Pedido criado', "createdAt"
+FROM "pedidos";
@@ -0,0 +1,2 @@
+-- AlterTable
+ALTER TABLE "pagamentos" ADD COLUMN     "status" CHAR(1) NOT NULL DEFAULT 'P';
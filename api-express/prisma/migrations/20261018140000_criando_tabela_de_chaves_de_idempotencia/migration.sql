@@ -0,0 +1,22 @@
+-- CreateTable
+CREATE TABLE "chavesIdempotencia" (
+    "id" VARCHAR(36) NOT NULL,
+    "idCliente" VARCHAR(36) NOT NULL,
+    "chave" VARCHAR(255) NOT NULL,
+    "hashRequisicao" CHAR(64) NOT NULL,
+    "statusCode" INTEGER,
+    "resposta" TEXT,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "expiraEm" TIMESTAMP(6) NOT NULL,
+
+    CONSTRAINT "chavesIdempotencia_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "idx_chaves_idempotencia_cliente_chave" ON "chavesIdempotencia"("idCliente", "chave");
+
+-- CreateIndex
+CREATE INDEX "idx_chaves_idempotencia_expira_em" ON "chavesIdempotencia"("expiraEm");
+
+-- AddForeignKey
+ALTER TABLE "chavesIdempotencia" ADD CONSTRAINT "chavesIdempotencia_idCliente_fkey" FOREIGN KEY ("idCliente") REFERENCES "clientes"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
@@ -0,0 +1,73 @@
+-- CreateTable
+CREATE TABLE "cupons" (
+    "id" VARCHAR(36) NOT NULL,
+    "codigo" VARCHAR(30) NOT NULL,
+    "descricao" VARCHAR(120),
+    "tipoDesconto" CHAR(1) NOT NULL,
+    "valorDesconto" DECIMAL(11,2) NOT NULL,
+    "valorMinimoPedido" DECIMAL(11,2) NOT NULL DEFAULT 0,
+    "validoDe" TIMESTAMP(6) NOT NULL,
+    "validoAte" TIMESTAMP(6) NOT NULL,
+    "limiteUsoCliente" INTEGER,
+    "limiteUsoTotal" INTEGER,
+    "quantidadeUtilizada" INTEGER NOT NULL DEFAULT 0,
+    "ativo" BOOLEAN NOT NULL DEFAULT true,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "cupons_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateTable
+CREATE TABLE "cuponsRestricoes" (
+    "id" VARCHAR(36) NOT NULL,
+    "idCupom" VARCHAR(36) NOT NULL,
+    "idCategoria" VARCHAR(36),
+    "skuProduto" TEXT,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "cuponsRestricoes_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "cuponsRestricoes_alvo_check" CHECK (("idCategoria" IS NULL) <> ("skuProduto" IS NULL))
+);
+
+-- CreateTable
+CREATE TABLE "cuponsUtilizados" (
+    "id" VARCHAR(36) NOT NULL,
+    "idCupom" VARCHAR(36) NOT NULL,
+    "idCliente" VARCHAR(36) NOT NULL,
+    "idPedido" VARCHAR(36) NOT NULL,
+    "valorDesconto" DECIMAL(11,2) NOT NULL,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "cuponsUtilizados_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "cupons_codigo_key" ON "cupons"("codigo");
+
+-- CreateIndex
+CREATE INDEX "idx_cupons_restricoes_cupom" ON "cuponsRestricoes"("idCupom");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "cuponsUtilizados_idPedido_key" ON "cuponsUtilizados"("idPedido");
+
+-- CreateIndex
+CREATE INDEX "idx_cupons_utilizados_cupom_cliente" ON "cuponsUtilizados"("idCupom", "idCliente");
+
+-- AddForeignKey
+ALTER TABLE "cuponsRestricoes" ADD CONSTRAINT "cuponsRestricoes_idCupom_fkey" FOREIGN KEY ("idCupom") REFERENCES "cupons"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "cuponsRestricoes" ADD CONSTRAINT "cuponsRestricoes_idCategoria_fkey" FOREIGN KEY ("idCategoria") REFERENCES "categorias"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "cuponsRestricoes" ADD CONSTRAINT "cuponsRestricoes_skuProduto_fkey" FOREIGN KEY ("skuProduto") REFERENCES "produtos"("sku") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "cuponsUtilizados" ADD CONSTRAINT "cuponsUtilizados_idCupom_fkey" FOREIGN KEY ("idCupom") REFERENCES "cupons"("id") ON DELETE NO ACTION ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "cuponsUtilizados" ADD CONSTRAINT "cuponsUtilizados_idCliente_fkey" FOREIGN KEY ("idCliente") REFERENCES "clientes"("id") ON DELETE NO ACTION ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "cuponsUtilizados" ADD CONSTRAINT "cuponsUtilizados_idPedido_fkey" FOREIGN KEY ("idPedido") REFERENCES "pedidos"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
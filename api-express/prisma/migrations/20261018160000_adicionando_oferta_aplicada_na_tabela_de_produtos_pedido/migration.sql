@@ -0,0 +1,10 @@
+-- AlterTable
+ALTER TABLE "produtosPedido" ADD COLUMN     "valorUnitarioOriginal" DECIMAL(11,2),
+ADD COLUMN     "pctOferta" DECIMAL(11,2) NOT NULL DEFAULT 0,
+ADD COLUMN     "promocao" VARCHAR(30);
+
+-- Backfill
+UPDATE "produtosPedido" SET "valorUnitarioOriginal" = "valorUnitario";
+
+-- AlterTable
+ALTER TABLE "produtosPedido" ALTER COLUMN "valorUnitarioOriginal" SET NOT NULL;
@@ -0,0 +1,20 @@
+-- CreateTable
+CREATE TABLE "eventosPagamento" (
+    "id" VARCHAR(36) NOT NULL,
+    "idEvento" VARCHAR(255) NOT NULL,
+    "idPedido" VARCHAR(36) NOT NULL,
+    "tipo" VARCHAR(20) NOT NULL,
+    "payload" TEXT NOT NULL,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "eventosPagamento_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "idx_eventos_pagamento_id_evento" ON "eventosPagamento"("idEvento");
+
+-- CreateIndex
+CREATE INDEX "idx_eventos_pagamento_pedido" ON "eventosPagamento"("idPedido");
+
+-- AddForeignKey
+ALTER TABLE "eventosPagamento" ADD CONSTRAINT "eventosPagamento_idPedido_fkey" FOREIGN KEY ("idPedido") REFERENCES "pedidos"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
@@ -0,0 +1,21 @@
+-- CreateTable
+CREATE TABLE "itensCarrinho" (
+    "id" VARCHAR(36) NOT NULL,
+    "idCliente" VARCHAR(36) NOT NULL,
+    "skuProduto" TEXT NOT NULL,
+    "quantidade" INTEGER NOT NULL,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "itensCarrinho_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "itensCarrinho_quantidade_check" CHECK ("quantidade" > 0)
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "idx_itens_carrinho_cliente_sku" ON "itensCarrinho"("idCliente", "skuProduto");
+
+-- AddForeignKey
+ALTER TABLE "itensCarrinho" ADD CONSTRAINT "itensCarrinho_idCliente_fkey" FOREIGN KEY ("idCliente") REFERENCES "clientes"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "itensCarrinho" ADD CONSTRAINT "itensCarrinho_skuProduto_fkey" FOREIGN KEY ("skuProduto") REFERENCES "produtos"("sku") ON DELETE CASCADE ON UPDATE NO ACTION;
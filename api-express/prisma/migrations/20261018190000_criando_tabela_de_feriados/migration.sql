@@ -0,0 +1,21 @@
+-- CreateTable
+CREATE TABLE "feriados" (
+    "id" VARCHAR(36) NOT NULL,
+    "descricao" VARCHAR(60) NOT NULL,
+    "dia" SMALLINT NOT NULL,
+    "mes" SMALLINT NOT NULL,
+    "ano" SMALLINT,
+    "codigoIbgeUF" VARCHAR(2),
+    "codigoIbgeCidade" VARCHAR(7),
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "feriados_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "feriados_dia_check" CHECK ("dia" BETWEEN 1 AND 31),
+    CONSTRAINT "feriados_mes_check" CHECK ("mes" BETWEEN 1 AND 12)
+);
+
+-- CreateIndex
+CREATE INDEX "idx_feriados_uf" ON "feriados"("codigoIbgeUF");
+
+-- CreateIndex
+CREATE INDEX "idx_feriados_cidade" ON "feriados"("codigoIbgeCidade");
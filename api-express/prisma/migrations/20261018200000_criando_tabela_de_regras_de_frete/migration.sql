@@ -0,0 +1,59 @@
+-- AlterTable
+ALTER TABLE "produtos" ADD COLUMN     "pesoKg" DECIMAL(11,3) NOT NULL DEFAULT 0,
+ADD COLUMN     "alturaCm" DECIMAL(11,2) NOT NULL DEFAULT 0,
+ADD COLUMN     "larguraCm" DECIMAL(11,2) NOT NULL DEFAULT 0,
+ADD COLUMN     "comprimentoCm" DECIMAL(11,2) NOT NULL DEFAULT 0;
+
+-- CreateTable
+CREATE TABLE "regrasFrete" (
+    "id" VARCHAR(36) NOT NULL,
+    "descricao" VARCHAR(60) NOT NULL,
+    "codigoIbgeUF" VARCHAR(2),
+    "cepInicial" CHAR(8),
+    "cepFinal" CHAR(8),
+    "pesoMinimo" DECIMAL(11,3) NOT NULL DEFAULT 0,
+    "pesoMaximo" DECIMAL(11,3),
+    "valorFrete" DECIMAL(11,2) NOT NULL,
+    "valorMinimoFreteGratis" DECIMAL(11,2),
+    "prioridade" INTEGER NOT NULL DEFAULT 0,
+    "ativo" BOOLEAN NOT NULL DEFAULT true,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "regrasFrete_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "regrasFrete_faixa_cep_check" CHECK (("cepInicial" IS NULL) = ("cepFinal" IS NULL) AND ("cepInicial" IS NULL OR "cepInicial" <= "cepFinal")),
+    CONSTRAINT "regrasFrete_faixa_peso_check" CHECK ("pesoMaximo" IS NULL OR "pesoMinimo" <= "pesoMaximo")
+);
+
+-- CreateIndex
+CREATE INDEX "idx_regras_frete_uf" ON "regrasFrete"("codigoIbgeUF");
+
+-- Valores da antiga tabela fixa de fretes por UF
+INSERT INTO "regrasFrete" ("id", "descricao", "codigoIbgeUF", "valorFrete") VALUES
+    (gen_random_uuid()::text, 'Frete padrão SP', '35', 0.00),
+    (gen_random_uuid()::text, 'Frete padrão MG', '31', 5.00),
+    (gen_random_uuid()::text, 'Frete padrão RJ', '33', 5.00),
+    (gen_random_uuid()::text, 'Frete padrão PR', '41', 5.00),
+    (gen_random_uuid()::text, 'Frete padrão MS', '50', 5.00),
+    (gen_random_uuid()::text, 'Frete padrão ES', '32', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão BA', '29', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão GO', '52', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão MT', '51', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão DF', '53', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão SC', '42', 10.00),
+    (gen_random_uuid()::text, 'Frete padrão RS', '43', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão RO', '11', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão AM', '13', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão PA', '15', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão TO', '17', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão PI', '22', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão PE', '26', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão AL', '27', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão SE', '28', 15.00),
+    (gen_random_uuid()::text, 'Frete padrão AC', '12', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão RR', '14', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão AP', '16', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão MA', '21', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão CE', '23', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão PB', '25', 20.00),
+    (gen_random_uuid()::text, 'Frete padrão RN', '24', 25.00);
@@ -0,0 +1,17 @@
+-- AlterTable
+ALTER TABLE "regrasFrete" ADD COLUMN     "modalidade" VARCHAR(20) NOT NULL DEFAULT 'economico',
+ADD COLUMN     "prazoDiasUteis" INTEGER,
+ADD CONSTRAINT "regrasFrete_modalidade_check" CHECK ("modalidade" IN ('economico', 'expresso')),
+ADD CONSTRAINT "regrasFrete_prazo_dias_uteis_check" CHECK ("prazoDiasUteis" IS NULL OR "prazoDiasUteis" > 0);
+
+-- AlterTable
+ALTER TABLE "pedidos" ADD COLUMN     "modalidadeFrete" VARCHAR(20) NOT NULL DEFAULT 'economico';
+
+-- CreateIndex
+CREATE INDEX "idx_regras_frete_modalidade" ON "regrasFrete"("modalidade");
+
+-- Frete expresso por UF a partir das regras padrão já cadastradas
+INSERT INTO "regrasFrete" ("id", "descricao", "codigoIbgeUF", "valorFrete", "modalidade")
+SELECT gen_random_uuid()::text, replace("descricao", 'Frete padrão', 'Frete expresso'), "codigoIbgeUF", "valorFrete" + 15.00, 'expresso'
+FROM "regrasFrete"
+WHERE "modalidade" = 'economico' AND "codigoIbgeUF" IS NOT NULL AND "cepInicial" IS NULL;
@@ -0,0 +1,32 @@
+-- CreateTable
+CREATE TABLE "enderecosCliente" (
+    "id" VARCHAR(36) NOT NULL,
+    "idCliente" VARCHAR(36) NOT NULL,
+    "apelido" VARCHAR(30),
+    "nomeRemetente" VARCHAR(120) NOT NULL,
+    "cep" CHAR(8) NOT NULL,
+    "logradouro" VARCHAR(60) NOT NULL,
+    "numero" VARCHAR(10) NOT NULL,
+    "complemento" VARCHAR(60),
+    "bairro" VARCHAR(60) NOT NULL,
+    "cidade" VARCHAR(60),
+    "codigoIbgeCidade" CHAR(7) NOT NULL,
+    "codigoIbgeUF" CHAR(2) NOT NULL,
+    "padrao" BOOLEAN NOT NULL DEFAULT false,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "enderecosCliente_pkey" PRIMARY KEY ("id")
+);
+
+-- AlterTable
+ALTER TABLE "enderecosEntrega" ADD COLUMN     "cidade" VARCHAR(60);
+
+-- CreateIndex
+CREATE INDEX "idx_enderecos_cliente_cliente" ON "enderecosCliente"("idCliente");
+
+-- Um único endereço padrão por cliente
+CREATE UNIQUE INDEX "idx_enderecos_cliente_padrao" ON "enderecosCliente"("idCliente") WHERE "padrao";
+
+-- AddForeignKey
+ALTER TABLE "enderecosCliente" ADD CONSTRAINT "enderecosCliente_idCliente_fkey" FOREIGN KEY ("idCliente") REFERENCES "clientes"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
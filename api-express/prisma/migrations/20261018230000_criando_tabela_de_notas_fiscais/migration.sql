@@ -0,0 +1,32 @@
+-- AlterTable
+ALTER TABLE "produtos" ADD COLUMN     "ncm" CHAR(8),
+ADD CONSTRAINT "produtos_ncm_check" CHECK ("ncm" IS NULL OR "ncm" ~ '^[0-9]{8}$');
+
+-- CreateTable
+CREATE TABLE "notasFiscais" (
+    "id" VARCHAR(36) NOT NULL,
+    "idPedido" VARCHAR(36) NOT NULL,
+    "ambiente" SMALLINT NOT NULL,
+    "serie" INTEGER NOT NULL,
+    "numero" INTEGER NOT NULL,
+    "chaveAcesso" CHAR(44) NOT NULL,
+    "xml" TEXT NOT NULL,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "notasFiscais_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "notasFiscais_ambiente_check" CHECK ("ambiente" IN (1, 2)),
+    CONSTRAINT "notasFiscais_serie_check" CHECK ("serie" BETWEEN 0 AND 999),
+    CONSTRAINT "notasFiscais_numero_check" CHECK ("numero" BETWEEN 1 AND 999999999)
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX "idx_notas_fiscais_pedido" ON "notasFiscais"("idPedido");
+
+-- CreateIndex
+CREATE UNIQUE INDEX "idx_notas_fiscais_chave_acesso" ON "notasFiscais"("chaveAcesso");
+
+-- A numeração é sequencial por série e independente entre produção e homologação
+CREATE UNIQUE INDEX "idx_notas_fiscais_numero" ON "notasFiscais"("ambiente", "serie", "numero");
+
+-- AddForeignKey
+ALTER TABLE "notasFiscais" ADD CONSTRAINT "notasFiscais_idPedido_fkey" FOREIGN KEY ("idPedido") REFERENCES "pedidos"("id") ON DELETE RESTRICT ON UPDATE CASCADE;
@@ -0,0 +1,24 @@
+-- CreateTable
+CREATE TABLE "eventosOutbox" (
+    "id" VARCHAR(36) NOT NULL,
+    "tipo" VARCHAR(40) NOT NULL,
+    "idAgregado" VARCHAR(36) NOT NULL,
+    "payload" TEXT NOT NULL,
+    "status" CHAR(1) NOT NULL DEFAULT 'P',
+    "tentativas" INTEGER NOT NULL DEFAULT 0,
+    "entregueA" TEXT[] NOT NULL DEFAULT ARRAY[]::TEXT[],
+    "ultimoErro" TEXT,
+    "proximaTentativa" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "processadoEm" TIMESTAMP(6),
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "eventosOutbox_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "eventosOutbox_status_check" CHECK ("status" IN ('P', 'E', 'F')),
+    CONSTRAINT "eventosOutbox_tentativas_check" CHECK ("tentativas" >= 0)
+);
+
+-- CreateIndex
+CREATE INDEX "idx_eventos_outbox_pendentes" ON "eventosOutbox"("status", "proximaTentativa");
+
+-- CreateIndex
+CREATE INDEX "idx_eventos_outbox_agregado" ON "eventosOutbox"("idAgregado");
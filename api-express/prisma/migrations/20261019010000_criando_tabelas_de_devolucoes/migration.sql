@@ -0,0 +1,74 @@
+-- AlterTable
+ALTER TABLE "pagamentos" ADD COLUMN     "valorEstornado" DECIMAL(11,2) NOT NULL DEFAULT 0;
+
+-- CreateTable
+CREATE TABLE "devolucoes" (
+    "id" VARCHAR(36) NOT NULL,
+    "idPedido" VARCHAR(36) NOT NULL,
+    "idCliente" VARCHAR(36) NOT NULL,
+    "status" CHAR(1) NOT NULL DEFAULT 'S',
+    "observacao" VARCHAR(500),
+    "valorReembolso" DECIMAL(11,2) NOT NULL,
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "updatedAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "devolucoes_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "devolucoes_status_check" CHECK ("status" IN ('S', 'A', 'R'))
+);
+
+-- CreateTable
+CREATE TABLE "itensDevolucao" (
+    "id" VARCHAR(36) NOT NULL,
+    "idDevolucao" VARCHAR(36) NOT NULL,
+    "idProdutoPedido" VARCHAR(36) NOT NULL,
+    "skuProduto" TEXT NOT NULL,
+    "quantidade" DECIMAL(11,2) NOT NULL,
+    "motivo" VARCHAR(30) NOT NULL,
+    "valorReembolso" DECIMAL(11,2) NOT NULL,
+
+    CONSTRAINT "itensDevolucao_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "itensDevolucao_quantidade_check" CHECK ("quantidade" > 0)
+);
+
+-- CreateTable
+CREATE TABLE "historicoStatusDevolucao" (
+    "id" VARCHAR(36) NOT NULL,
+    "idDevolucao" VARCHAR(36) NOT NULL,
+    "statusAnterior" CHAR(1),
+    "statusNovo" CHAR(1) NOT NULL,
+    "alteradoPor" VARCHAR(60) NOT NULL,
+    "motivo" VARCHAR(255),
+    "createdAt" TIMESTAMP(6) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT "historicoStatusDevolucao_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX "idx_devolucoes_pedido" ON "devolucoes"("idPedido");
+
+-- CreateIndex
+CREATE INDEX "idx_devolucoes_status" ON "devolucoes"("status");
+
+-- CreateIndex
+CREATE INDEX "idx_itens_devolucao_devolucao" ON "itensDevolucao"("idDevolucao");
+
+-- CreateIndex
+CREATE INDEX "idx_itens_devolucao_produto_pedido" ON "itensDevolucao"("idProdutoPedido");
+
+-- CreateIndex
+CREATE INDEX "idx_historico_status_devolucao_devolucao" ON "historicoStatusDevolucao"("idDevolucao");
+
+-- AddForeignKey
+ALTER TABLE "devolucoes" ADD CONSTRAINT "devolucoes_idPedido_fkey" FOREIGN KEY ("idPedido") REFERENCES "pedidos"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "devolucoes" ADD CONSTRAINT "devolucoes_idCliente_fkey" FOREIGN KEY ("idCliente") REFERENCES "clientes"("id") ON DELETE NO ACTION ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "itensDevolucao" ADD CONSTRAINT "itensDevolucao_idDevolucao_fkey" FOREIGN KEY ("idDevolucao") REFERENCES "devolucoes"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "itensDevolucao" ADD CONSTRAINT "itensDevolucao_idProdutoPedido_fkey" FOREIGN KEY ("idProdutoPedido") REFERENCES "produtosPedido"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
+
+-- AddForeignKey
+ALTER TABLE "historicoStatusDevolucao" ADD CONSTRAINT "historicoStatusDevolucao_idDevolucao_fkey" FOREIGN KEY ("idDevolucao") REFERENCES "devolucoes"("id") ON DELETE CASCADE ON UPDATE NO ACTION;
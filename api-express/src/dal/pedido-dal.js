@@ -37,6 +37,7 @@ export const create = async (payload) => {
         skuProduto: produto.skuProduto,
         quantidade: validateNumber(produto.quantidade, 1),
         valorUnitario: validateNumber(produto.valorUnitario),
+        valorUnitarioOriginal: validateNumber(produto.valorUnitario),
         valorBruto: validateNumber(produto.valorBruto),
         valorDesconto: validateNumber(produto.valorDesconto),
         valorLiquido: validateNumber(produto.valorLiquido),
@@ -35,17 +35,18 @@ func (p *Pedido) BeforeCreate(tx *gorm.DB) (err error) {
 }
 
 type ProdutoPedido struct {
-	ID            string    `json:"id" gorm:"primaryKey;column:id;type:varchar(36);not null"`
-	IdPedido      string    `json:"idPedido" gorm:"column:idPedido;not null"`
-	SKUProduto    string    `json:"skuProduto" gorm:"column:skuProduto;not null" binding:"required"`
-	Quantidade    float64   `json:"quantidade" gorm:"type:decimal(11,2);column:quantidade;not null" binding:"required"`
-	ValorUnitario float64   `json:"valorUnitario" gorm:"type:decimal(11,2);column:valorUnitario;not null"`
-	ValorBruto    float64   `json:"valorBruto" gorm:"type:decimal(11,2);column:valorBruto;not null"`
-	ValorFrete    float64   `json:"valorFrete" gorm:"type:decimal(11,2);column:valorFrete;not null"`
-	ValorDesconto float64   `json:"valorDesconto" gorm:"type:decimal(11,2);column:valorDesconto;not null"`
-	ValorLiquido  float64   `json:"valorLiquido" gorm:"type:decimal(11,2);column:valorLiquido;not null"`
-	CreatedAt     time.Time `json:"createdAt" gorm:"autoCreateTime;column:createdAt;not null"`
-	UpdatedAt     time.Time `json:"updatedAt" gorm:"autoUpdateTime;column:updatedAt;not null"`
+	ID                    string    `json:"id" gorm:"primaryKey;column:id;type:varchar(36);not null"`
+	IdPedido              string    `json:"idPedido" gorm:"column:idPedido;not null"`
+	SKUProduto            string    `json:"skuProduto" gorm:"column:skuProduto;not null" binding:"required"`
+	Quantidade            float64   `json:"quantidade" gorm:"type:decimal(11,2);column:quantidade;not null" binding:"required"`
+	ValorUnitario         float64   `json:"valorUnitario" gorm:"type:decimal(11,2);column:valorUnitario;not null"`
+	ValorUnitarioOriginal float64   `json:"valorUnitarioOriginal" gorm:"type:decimal(11,2);column:valorUnitarioOriginal;not null"`
+	ValorBruto            float64   `json:"valorBruto" gorm:"type:decimal(11,2);column:valorBruto;not null"`
+	ValorFrete            float64   `json:"valorFrete" gorm:"type:decimal(11,2);column:valorFrete;not null"`
+	ValorDesconto         float64   `json:"valorDesconto" gorm:"type:decimal(11,2);column:valorDesconto;not null"`
+	ValorLiquido          float64   `json:"valorLiquido" gorm:"type:decimal(11,2);column:valorLiquido;not null"`
+	CreatedAt             time.Time `json:"createdAt" gorm:"autoCreateTime;column:createdAt;not null"`
+	UpdatedAt             time.Time `json:"updatedAt" gorm:"autoUpdateTime;column:updatedAt;not null"`
 
 	// Relacionamentos
 	Pedido  Pedido  `json:"-" gorm:"foreignKey:IdPedido;constraint:OnUpdate:CASCADE,OnDelete:CASCADE;"`
@@ -56,6 +56,7 @@ func CreatePedido(pedido *models.Pedido) (*models.Pedido, *utils.RestErr) {
 		}
 
 		produto.ValorUnitario = produtoBanco.Preco
+		produto.ValorUnitarioOriginal = produtoBanco.Preco
 		produto.ValorBruto = produto.Quantidade * produto.ValorUnitario
 
 		desconto := math.Round(produto.ValorBruto*(*pedido.Pagamento.PorcentagemDesconto)*100) / 100
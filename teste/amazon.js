@@ -345,7 +345,7 @@ function createOrder(visitedProducts, token) {
             complemento: `Apto ${randomIntBetween(1, 300)}`,
             bairro: "Residencial Santa Filomena",
             codigoIbgeCidade: "3549805",
-            codigoIbgeUF: "31"
+            codigoIbgeUF: "35"
         },
         pagamento: {
             formaPagamento: formaPagamento,